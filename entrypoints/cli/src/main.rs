@@ -0,0 +1,251 @@
+use clap::{Parser, Subcommand};
+use ecommercers::core::models::{
+    export::ExportFormat,
+    import::{ImportFormat, ImportOptions},
+};
+use std::{fs, io::BufWriter, path::PathBuf, process::ExitCode};
+
+/// Maintenance tasks, run against the same configuration as the servers.
+#[derive(Parser)]
+#[command(name = "ecommercers-cli", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Replaces the search index with the documents of every product.
+    RebuildSearchIndex,
+    /// Publishes and archives the products whose scheduled time has come.
+    ApplyProductSchedules,
+    /// Computes the related products of every product from the orders and
+    /// categories.
+    RefreshRecommendations,
+    /// Saves the product view counts kept in Redis and updates the trending
+    /// products.
+    FlushProductViews,
+    /// Places the orders of the subscriptions due, skipping those out of stock.
+    RunSubscriptions,
+    /// Deletes for good the products and categories kept in the trash past the
+    /// retention period.
+    PurgeTrash,
+    /// Creates and updates products from a CSV or JSON catalog file.
+    ImportProducts {
+        file: PathBuf,
+        /// `csv` or `json`; guessed from the file extension by default.
+        #[arg(long)]
+        format: Option<ImportFormat>,
+        /// Validates the file and reports what would change without saving.
+        #[arg(long)]
+        dry_run: bool,
+        /// Creates the categories the file refers to that do not exist yet.
+        #[arg(long)]
+        create_categories: bool,
+    },
+    /// Writes the published products to a CSV, JSON Lines or Google Merchant
+    /// feed file.
+    ExportProducts {
+        file: PathBuf,
+        /// `csv`, `jsonl` or `xml`; guessed from the file extension by default.
+        #[arg(long)]
+        format: Option<ExportFormat>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let services = ecommercers::bootstrap::bootstrap_services();
+
+    match cli.command {
+        Command::RebuildSearchIndex => {
+            let mut product_service = services.product_service.lock().unwrap();
+            match product_service.rebuild_search_index() {
+                Ok(count) => {
+                    println!("# Indexed {} products", count);
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Failed to rebuild the search index: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::ApplyProductSchedules => {
+            let mut product_service = services.product_service.lock().unwrap();
+            match product_service.apply_schedules() {
+                Ok(count) => {
+                    println!("# Updated {} products", count);
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Failed to apply product schedules: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::RefreshRecommendations => {
+            let mut recommendation_service = services.recommendation_service.lock().unwrap();
+            match recommendation_service.refresh() {
+                Ok(count) => {
+                    println!("# Updated {} recommendations", count);
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Failed to compute product recommendations: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::FlushProductViews => {
+            let mut view_service = services.view_service.lock().unwrap();
+            match view_service.flush() {
+                Ok(count) => {
+                    println!("# Saved the views of {} products", count);
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Failed to flush product views: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::RunSubscriptions => {
+            let mut subscription_service = services.subscription_service.lock().unwrap();
+            match subscription_service.run_due() {
+                Ok(report) => {
+                    println!(
+                        "# Placed {} subscription orders, skipped {}, failed {}",
+                        report.ordered, report.skipped, report.failed
+                    );
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Failed to run subscriptions: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::PurgeTrash => {
+            let retention_days = services.cfg.lock().unwrap().trash.retention_days;
+            // Products first, so that the categories they leave unused go too.
+            let products = {
+                let mut product_service = services.product_service.lock().unwrap();
+                product_service.purge_trash(retention_days)
+            };
+            let products = match products {
+                Ok(count) => count,
+                Err(err) => {
+                    eprintln!("Failed to purge the product trash: {:?}", err);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let mut category_service = services.category_service.lock().unwrap();
+            match category_service.purge_trash(retention_days) {
+                Ok(categories) => {
+                    println!(
+                        "# Purged {} products and {} categories",
+                        products, categories
+                    );
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Failed to purge the category trash: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::ImportProducts {
+            file,
+            format,
+            dry_run,
+            create_categories,
+        } => {
+            let format = format.or_else(|| {
+                file.extension()
+                    .and_then(|extension| extension.to_str())
+                    .and_then(|extension| extension.to_lowercase().parse().ok())
+            });
+            let Some(format) = format else {
+                eprintln!(
+                    "Cannot tell the format of {}; pass --format",
+                    file.display()
+                );
+                return ExitCode::FAILURE;
+            };
+            let data = match fs::read(&file) {
+                Ok(data) => data,
+                Err(err) => {
+                    eprintln!("Failed to read {}: {}", file.display(), err);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let mut import_service = services.import_service.lock().unwrap();
+            let options = ImportOptions {
+                dry_run,
+                create_categories,
+            };
+            match import_service.import_products(&data, format, options) {
+                Ok(report) => {
+                    for error in &report.errors {
+                        eprintln!("line {}: {}", error.line, error.message);
+                    }
+                    if report.dry_run {
+                        println!(
+                            "# Would create {} products, update {} and create {} categories",
+                            report.created, report.updated, report.created_categories
+                        );
+                    } else {
+                        println!(
+                            "# Created {} products, updated {} and created {} categories",
+                            report.created, report.updated, report.created_categories
+                        );
+                    }
+                    if report.errors.is_empty() {
+                        ExitCode::SUCCESS
+                    } else {
+                        ExitCode::FAILURE
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Failed to import products: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::ExportProducts { file, format } => {
+            let format = format.or_else(|| {
+                file.extension()
+                    .and_then(|extension| extension.to_str())
+                    .and_then(|extension| extension.to_lowercase().parse().ok())
+            });
+            let Some(format) = format else {
+                eprintln!(
+                    "Cannot tell the format of {}; pass --format",
+                    file.display()
+                );
+                return ExitCode::FAILURE;
+            };
+            let mut out = match fs::File::create(&file) {
+                Ok(file) => BufWriter::new(file),
+                Err(err) => {
+                    eprintln!("Failed to create {}: {}", file.display(), err);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let export_service = services.export_service.lock().unwrap();
+            match export_service.export_products(format, &mut out) {
+                Ok(count) => {
+                    println!("# Exported {} products", count);
+                    ExitCode::SUCCESS
+                }
+                Err(err) => {
+                    eprintln!("Failed to export products: {:?}", err);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+    }
+}
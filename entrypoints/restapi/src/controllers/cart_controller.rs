@@ -0,0 +1,247 @@
+use crate::{
+    dto::cart_dto::{
+        CartActivateDTO, CartCreateDTO, CartCreatedDTO, CartDeleteDTO, CartDetailsDTO, CartGetDTO,
+        CartItemAddDTO, CartItemMoveDTO, CartItemRemoveDTO, CartItemUpdateDTO, CartItemUpdatedDTO,
+        CartRenameDTO, CartUpdatedDTO,
+    },
+    errors::{SimpleMessage, cart_errors::HttpCartError},
+    middlewares::auth::authenticate,
+};
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{StatusCode, header::ContentType},
+    post, web,
+};
+use ecommercers::core::services::{cart_service::CartService, user_service::UserService};
+use std::sync::{Arc, Mutex};
+
+pub fn new_cart_controller() -> Scope {
+    web::scope("/carts")
+        .service(get_all_action)
+        .service(get_action)
+        .service(get_active_action)
+        .service(create_action)
+        .service(rename_action)
+        .service(activate_action)
+        .service(delete_action)
+        .service(add_item_action)
+        .service(update_item_action)
+        .service(remove_item_action)
+        .service(move_item_action)
+}
+
+#[get("/get_all")]
+async fn get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let carts = cart_service.get_all(user.id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&carts).unwrap()))
+}
+
+#[get("/get")]
+async fn get_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartGetDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let (cart, items) = cart_service.get(user.id, data.0.cart_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&CartDetailsDTO { cart, items }).unwrap()))
+}
+
+#[get("/active")]
+async fn get_active_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let (cart, items) = cart_service.get_active(user.id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&CartDetailsDTO { cart, items }).unwrap()))
+}
+
+#[post("/create")]
+async fn create_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartCreateDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let cart = cart_service.create(user.id, data.0.name)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CartCreatedDTO {
+                message: "cart created".to_string(),
+                cart,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/rename")]
+async fn rename_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartRenameDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let cart = cart_service.rename(user.id, data.0.cart_id, data.0.name)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CartUpdatedDTO {
+                message: "cart renamed".to_string(),
+                cart,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/activate")]
+async fn activate_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartActivateDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let cart = cart_service.activate(user.id, data.0.cart_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CartUpdatedDTO {
+                message: "cart activated".to_string(),
+                cart,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/delete")]
+async fn delete_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartDeleteDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    cart_service.delete(user.id, data.0.cart_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "cart deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/items/add")]
+async fn add_item_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartItemAddDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let item = cart_service.add_item(
+        user.id,
+        data.0.cart_id,
+        data.0.product_id,
+        data.0.variant_id,
+        data.0.quantity,
+        data.0.customizations,
+    )?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CartItemUpdatedDTO {
+                message: "item added".to_string(),
+                item,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/items/update")]
+async fn update_item_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartItemUpdateDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let item = cart_service.update_item_quantity(user.id, data.0.cart_item_id, data.0.quantity)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CartItemUpdatedDTO {
+                message: "item updated".to_string(),
+                item,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/items/remove")]
+async fn remove_item_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartItemRemoveDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    cart_service.remove_item(user.id, data.0.cart_item_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "item removed".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/items/move")]
+async fn move_item_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    cart_service_guard: web::Data<Arc<Mutex<CartService>>>,
+    data: web::Json<CartItemMoveDTO>,
+) -> Result<impl Responder, HttpCartError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut cart_service = cart_service_guard.lock().unwrap();
+    let item = cart_service.move_item(user.id, data.0.cart_item_id, data.0.target_cart_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CartItemUpdatedDTO {
+                message: "item moved".to_string(),
+                item,
+            })
+            .unwrap(),
+        ))
+}
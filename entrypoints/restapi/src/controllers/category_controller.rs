@@ -1,15 +1,36 @@
 use crate::{
-    dto::category_dto::{
-        CategoryCreateDTO, CategoryCreatedDTO, CategoryDeleteDTO, CategoryGetDTO, CategoryUpdateDTO,
+    dto::{
+        category_dto::{
+            AttributeCreateDTO, AttributeDeleteDTO, AttributeGetAllDTO, AttributeSavedDTO,
+            AttributeUpdateDTO, CategoryCreateDTO, CategoryCreatedDTO, CategoryDeleteDTO,
+            CategoryGetDTO, CategoryMovedDTO, CategoryRestoreDTO, CategorySeoUpdateDTO,
+            CategoryUpdateDTO,
+            VariationCreateDTO, VariationDeleteDTO, VariationDetailsDTO, VariationGetAllDTO,
+            VariationOptionCreateDTO, VariationOptionDeleteDTO, VariationOptionSavedDTO,
+            VariationOptionUpdateDTO, VariationSavedDTO, VariationUpdateDTO,
+        },
+        pagination_dto::PaginationDTO,
     },
-    errors::{SimpleMessage, category_errors::HttpCategoryError},
+    errors::{
+        SimpleMessage, category_errors::HttpCategoryError, product_errors::HttpProductError,
+    },
+    middlewares::auth::{MANAGER_ROLES, authorize},
 };
 use actix_web::{
-    HttpResponse, Responder, Scope, get,
-    http::{StatusCode, header::ContentType},
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{
+        StatusCode,
+        header::{self, ContentType},
+    },
     post, web,
 };
-use ecommercers::core::services::category_service::CategoryService;
+use ecommercers::core::{
+    models::seo::SlugLookup,
+    services::{
+        category_service::CategoryService, product_service::ProductService,
+        user_service::UserService,
+    },
+};
 use std::sync::{Arc, Mutex};
 
 pub fn new_category_controller() -> Scope {
@@ -17,9 +38,24 @@ pub fn new_category_controller() -> Scope {
         .service(create_action)
         .service(get_all_action)
         .service(get_action)
+        .service(get_by_slug_action)
         .service(create_action)
         .service(delete_action)
+        .service(trash_get_all_action)
+        .service(restore_action)
         .service(update_action)
+        .service(update_seo_action)
+        .service(get_variations_action)
+        .service(create_variation_action)
+        .service(update_variation_action)
+        .service(delete_variation_action)
+        .service(create_variation_option_action)
+        .service(update_variation_option_action)
+        .service(delete_variation_option_action)
+        .service(get_attributes_action)
+        .service(create_attribute_action)
+        .service(update_attribute_action)
+        .service(delete_attribute_action)
 }
 
 #[get("/get")]
@@ -34,12 +70,36 @@ async fn get_action(
         .body(serde_json::to_string(&category).unwrap()))
 }
 
+#[get("/slug/{slug}")]
+async fn get_by_slug_action(
+    category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
+    slug: web::Path<String>,
+) -> Result<impl Responder, HttpCategoryError> {
+    let mut category_service = category_service_guard.lock().unwrap();
+    match category_service.get_by_slug(&slug)? {
+        SlugLookup::Found(category) => Ok(HttpResponse::build(StatusCode::OK)
+            .insert_header(ContentType::json())
+            .body(serde_json::to_string(&category).unwrap())),
+        SlugLookup::Moved(slug) => Ok(HttpResponse::build(StatusCode::MOVED_PERMANENTLY)
+            .insert_header(ContentType::json())
+            .insert_header((header::LOCATION, format!("/categories/slug/{}", slug)))
+            .body(
+                serde_json::to_string(&CategoryMovedDTO {
+                    message: "category moved".to_string(),
+                    slug,
+                })
+                .unwrap(),
+            )),
+    }
+}
+
 #[get("/get_all")]
 async fn get_all_action(
     category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
+    page: web::Query<PaginationDTO>,
 ) -> Result<impl Responder, HttpCategoryError> {
     let mut category_service = category_service_guard.lock().unwrap();
-    let categories = category_service.get_all()?;
+    let categories = category_service.get_all(page.to_pagination()?)?;
     Ok(HttpResponse::build(StatusCode::OK)
         .insert_header(ContentType::json())
         .body(serde_json::to_string(&categories).unwrap()))
@@ -47,9 +107,12 @@ async fn get_all_action(
 
 #[post("/create")]
 async fn create_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
     category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
     data: web::Json<CategoryCreateDTO>,
 ) -> Result<impl Responder, HttpCategoryError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
     let mut category_service = category_service_guard.lock().unwrap();
     let category = category_service.create(data.0.name, data.0.description, data.0.parent_id)?;
     Ok(HttpResponse::build(StatusCode::CREATED)
@@ -65,9 +128,12 @@ async fn create_action(
 
 #[post("/update")]
 async fn update_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
     category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
     data: web::Json<CategoryUpdateDTO>,
 ) -> Result<impl Responder, HttpCategoryError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
     let mut category_service = category_service_guard.lock().unwrap();
     let category = category_service.update(
         data.0.category_id,
@@ -86,11 +152,35 @@ async fn update_action(
         ))
 }
 
+#[post("/seo/update")]
+async fn update_seo_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
+    data: web::Json<CategorySeoUpdateDTO>,
+) -> Result<impl Responder, HttpCategoryError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut category_service = category_service_guard.lock().unwrap();
+    let category = category_service.update_seo(data.0.category_id, data.0.slug, data.0.seo)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CategoryCreatedDTO {
+                category,
+                message: "category seo updated".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
 #[post("/delete")]
 async fn delete_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
     category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
     data: web::Json<CategoryDeleteDTO>,
 ) -> Result<impl Responder, HttpCategoryError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
     let mut category_service = category_service_guard.lock().unwrap();
     category_service.delete(data.0.category_id)?;
     Ok(HttpResponse::build(StatusCode::OK)
@@ -102,3 +192,268 @@ async fn delete_action(
             .unwrap(),
         ))
 }
+
+#[get("/trash/get_all")]
+async fn trash_get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpCategoryError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut category_service = category_service_guard.lock().unwrap();
+    let categories = category_service.get_trash(page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&categories).unwrap()))
+}
+
+#[post("/trash/restore")]
+async fn restore_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    category_service_guard: web::Data<Arc<Mutex<CategoryService>>>,
+    data: web::Json<CategoryRestoreDTO>,
+) -> Result<impl Responder, HttpCategoryError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut category_service = category_service_guard.lock().unwrap();
+    let category = category_service.restore(data.0.category_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&CategoryCreatedDTO {
+                category,
+                message: "category restored".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+#[get("/variations/get_all")]
+async fn get_variations_action(
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<VariationGetAllDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    let mut product_service = product_service_guard.lock().unwrap();
+    let variations: Vec<VariationDetailsDTO> = product_service
+        .get_variations(data.0.category_id)?
+        .into_iter()
+        .map(|(variation, options)| VariationDetailsDTO { variation, options })
+        .collect();
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&variations).unwrap()))
+}
+
+#[post("/variations/create")]
+async fn create_variation_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<VariationCreateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let variation = product_service.create_variation(data.0.category_id, data.0.name)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&VariationSavedDTO {
+                message: "variation created".to_string(),
+                variation,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variations/update")]
+async fn update_variation_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<VariationUpdateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let variation = product_service.update_variation(data.0.variation_id, data.0.name)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&VariationSavedDTO {
+                message: "variation updated".to_string(),
+                variation,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variations/delete")]
+async fn delete_variation_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<VariationDeleteDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    product_service.delete_variation(data.0.variation_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "variation deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variations/options/create")]
+async fn create_variation_option_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<VariationOptionCreateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let option = product_service.create_variation_option(data.0.variation_id, data.0.value)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&VariationOptionSavedDTO {
+                message: "variation option created".to_string(),
+                option,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variations/options/update")]
+async fn update_variation_option_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<VariationOptionUpdateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let option =
+        product_service.update_variation_option(data.0.variation_option_id, data.0.value)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&VariationOptionSavedDTO {
+                message: "variation option updated".to_string(),
+                option,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variations/options/delete")]
+async fn delete_variation_option_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<VariationOptionDeleteDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    product_service.delete_variation_option(data.0.variation_option_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "variation option deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+
+#[get("/attributes/get_all")]
+async fn get_attributes_action(
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<AttributeGetAllDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    let mut product_service = product_service_guard.lock().unwrap();
+    let attributes = product_service.get_attributes(data.0.category_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&attributes).unwrap()))
+}
+
+#[post("/attributes/create")]
+async fn create_attribute_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<AttributeCreateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let attribute = product_service.create_attribute(
+        data.0.category_id,
+        data.0.name,
+        data.0.data_type,
+        data.0.unit,
+        data.0.is_filterable,
+        data.0.options,
+    )?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&AttributeSavedDTO {
+                message: "attribute created".to_string(),
+                attribute,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/attributes/update")]
+async fn update_attribute_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<AttributeUpdateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let attribute = product_service.update_attribute(
+        data.0.attribute_id,
+        data.0.name,
+        data.0.unit,
+        data.0.is_filterable,
+        data.0.options,
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&AttributeSavedDTO {
+                message: "attribute updated".to_string(),
+                attribute,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/attributes/delete")]
+async fn delete_attribute_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<AttributeDeleteDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    product_service.delete_attribute(data.0.attribute_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "attribute deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use actix_web::{
+    HttpResponse, Responder, Scope, get,
+    http::{
+        StatusCode,
+        header::{ContentDisposition, DispositionParam, DispositionType},
+    },
+    web,
+};
+use ecommercers::core::{
+    models::download::DOWNLOAD_CHUNK_SIZE, services::download_service::DownloadService,
+};
+use futures_util::stream;
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use crate::{dto::order_dto::DownloadQueryDTO, errors::order_errors::HttpOrderError};
+
+/// Serves the files of digital products through the signed links of paid orders.
+pub fn new_download_controller() -> Scope {
+    web::scope("/downloads").service(get_action)
+}
+
+#[get("/{order_item_id}/{file_id}")]
+async fn get_action(
+    download_service_guard: web::Data<Arc<Mutex<DownloadService>>>,
+    path: web::Path<(i64, i64)>,
+    query: web::Query<DownloadQueryDTO>,
+) -> Result<impl Responder, HttpOrderError> {
+    let (order_item_id, file_id) = path.into_inner();
+    let (file, reader) = {
+        let mut download_service = download_service_guard.lock().unwrap();
+        download_service.download(order_item_id, file_id, query.expires, &query.signature)?
+    };
+
+    let body = stream::try_unfold(reader, |mut reader| async move {
+        let (reader, chunk) = web::block(move || {
+            let mut chunk = vec![0; DOWNLOAD_CHUNK_SIZE];
+            let len = reader.read(&mut chunk)?;
+            chunk.truncate(len);
+            Ok::<_, io::Error>((reader, chunk))
+        })
+        .await
+        .map_err(io::Error::other)??;
+        let next = (!chunk.is_empty()).then(|| (web::Bytes::from(chunk), reader));
+        Ok::<_, io::Error>(next)
+    });
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(file.content_type.as_str())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(file.file_name)],
+        })
+        .streaming(body))
+}
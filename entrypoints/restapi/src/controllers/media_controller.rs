@@ -0,0 +1,29 @@
+use actix_web::{HttpResponse, Responder, Scope, get, http::StatusCode, web};
+use ecommercers::core::{
+    models::product::image_content_type, services::product_service::ProductService,
+};
+use std::sync::{Arc, Mutex};
+
+use crate::errors::product_errors::HttpProductError;
+
+/// Serves files kept by the local storage backend.
+pub fn new_media_controller() -> Scope {
+    web::scope("/media").service(get_action)
+}
+
+#[get("/{key:.*}")]
+async fn get_action(
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    key: web::Path<String>,
+) -> Result<impl Responder, HttpProductError> {
+    let content_type = key
+        .rsplit_once('.')
+        .and_then(|(_, extension)| image_content_type(extension))
+        .unwrap_or("application/octet-stream");
+
+    let mut product_service = product_service_guard.lock().unwrap();
+    let data = product_service.get_media(&key)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(content_type)
+        .body(data))
+}
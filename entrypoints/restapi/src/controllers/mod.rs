@@ -1,3 +1,11 @@
 pub mod user_controller;
 pub mod product_controller;
-pub mod category_controller;
\ No newline at end of file
+pub mod category_controller;
+pub mod cart_controller;
+pub mod order_controller;
+pub mod media_controller;
+pub mod review_controller;
+pub mod question_controller;
+pub mod recommendation_controller;
+pub mod download_controller;
+pub mod subscription_controller;
\ No newline at end of file
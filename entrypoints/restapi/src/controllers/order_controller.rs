@@ -0,0 +1,121 @@
+use crate::{
+    dto::{
+        order_dto::{
+            OrderCheckoutDTO, OrderCreatedDTO, OrderDetailsDTO, OrderDownloadsDTO, OrderGetDTO,
+            OrderUpdatedDTO,
+        },
+        pagination_dto::PaginationDTO,
+    },
+    errors::order_errors::HttpOrderError,
+    middlewares::auth::{MANAGER_ROLES, authenticate, authorize},
+};
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{StatusCode, header::ContentType},
+    post, web,
+};
+use ecommercers::core::services::{
+    download_service::DownloadService, order_service::OrderService, user_service::UserService,
+};
+use std::sync::{Arc, Mutex};
+
+pub fn new_order_controller() -> Scope {
+    web::scope("/orders")
+        .service(checkout_action)
+        .service(get_all_action)
+        .service(get_action)
+        .service(downloads_action)
+        .service(mark_paid_action)
+}
+
+#[post("/checkout")]
+async fn checkout_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    order_service_guard: web::Data<Arc<Mutex<OrderService>>>,
+    data: web::Json<OrderCheckoutDTO>,
+) -> Result<impl Responder, HttpOrderError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut order_service = order_service_guard.lock().unwrap();
+    let (order, items) = order_service.checkout(user.id, data.0.cart_id)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&OrderCreatedDTO {
+                message: "order created".to_string(),
+                order,
+                items,
+            })
+            .unwrap(),
+        ))
+}
+
+#[get("/get_all")]
+async fn get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    order_service_guard: web::Data<Arc<Mutex<OrderService>>>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpOrderError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut order_service = order_service_guard.lock().unwrap();
+    let orders = order_service.get_all(user.id, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&orders).unwrap()))
+}
+
+#[get("/get")]
+async fn get_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    order_service_guard: web::Data<Arc<Mutex<OrderService>>>,
+    data: web::Json<OrderGetDTO>,
+) -> Result<impl Responder, HttpOrderError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut order_service = order_service_guard.lock().unwrap();
+    let (order, items) = order_service.get(user.id, data.0.order_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&OrderDetailsDTO { order, items }).unwrap()))
+}
+
+/// Signed links to the files of the digital products of a paid order.
+#[get("/downloads")]
+async fn downloads_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    download_service_guard: web::Data<Arc<Mutex<DownloadService>>>,
+    data: web::Json<OrderGetDTO>,
+) -> Result<impl Responder, HttpOrderError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut download_service = download_service_guard.lock().unwrap();
+    let links = download_service.get_links(user.id, data.0.order_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&OrderDownloadsDTO { links }).unwrap()))
+}
+
+/// Records the payment of a pending order, e.g. once the payment provider has
+/// confirmed it.
+#[post("/manage/mark_paid")]
+async fn mark_paid_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    order_service_guard: web::Data<Arc<Mutex<OrderService>>>,
+    data: web::Json<OrderGetDTO>,
+) -> Result<impl Responder, HttpOrderError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut order_service = order_service_guard.lock().unwrap();
+    let (order, items) = order_service.mark_paid(data.0.order_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&OrderUpdatedDTO {
+                message: "order paid".to_string(),
+                order,
+                items,
+            })
+            .unwrap(),
+        ))
+}
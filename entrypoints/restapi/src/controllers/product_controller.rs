@@ -1,33 +1,106 @@
+use actix_multipart::Multipart;
 use actix_web::{
-    HttpResponse, Responder, Scope, get,
-    http::{StatusCode, header::ContentType},
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{
+        StatusCode,
+        header::{self, ContentType},
+    },
     post, web,
 };
-use ecommercers::core::services::product_service::ProductService;
-use std::sync::{Arc, Mutex};
+use ecommercers::core::{
+    models::{
+        customization::CustomizationField,
+        download::MAX_FILE_SIZE,
+        export::{ExportFormat, FEED_CHUNK_SIZE},
+        import::{ImportFormat, ImportOptions, MAX_IMPORT_SIZE},
+        product::{MAX_IMAGE_SIZE, ProductVisibility},
+        seo::SlugLookup,
+    },
+    services::{
+        export_service::ExportService, import_service::ImportService,
+        product_service::ProductService, user_service::UserService, view_service::ViewService,
+    },
+};
+use futures_util::{TryStreamExt, stream};
+use std::{
+    fs::File,
+    io::{self, Read},
+    sync::{Arc, Mutex},
+};
 
 use crate::{
-    dto::product_dto::{
-        ProductCreateDTO, ProductCreatedDTO, ProductDeleteDTO, ProductGetDTO, ProductSearchDTO,
-        ProductUpdateDTO, ProductUpdatedDTO,
+    dto::{
+        pagination_dto::PaginationDTO,
+        product_dto::{
+            ProductAttributesUpdatedDTO, ProductByCategoryDTO, ProductCategoryDTO,
+            ProductCreateDTO, ProductCreatedDTO, ProductCustomizationsUpdatedDTO, ProductDeleteDTO,
+            ProductFileDTO, ProductFileUploadedDTO, ProductFilesDTO, ProductGetDTO,
+            ProductImageDTO, ProductImageUpdateDTO, ProductImageUpdatedDTO,
+            ProductImagesReorderDTO, ProductImagesUpdatedDTO, ProductMovedDTO, ProductQueryDTO,
+            ProductRemoveBundleDTO, ProductRestoreDTO, ProductSearchDTO, ProductSeoUpdateDTO,
+            ProductSetAttributesDTO, ProductSetBundleDTO, ProductSetCustomizationsDTO,
+            ProductSetDigitalDTO, ProductStatusUpdateDTO, ProductSuggestionsDTO, ProductUpdateDTO,
+            ProductUpdatedDTO, ProductVariantCreateDTO, ProductVariantDeleteDTO,
+            ProductVariantUpdateDTO, ProductVariantUpdatedDTO, ProductVariationOptionDTO,
+        },
     },
     errors::{SimpleMessage, product_errors::HttpProductError},
+    middlewares::auth::{MANAGER_ROLES, authorize, identify_viewer},
 };
 
 pub fn new_product_controller() -> Scope {
     web::scope("/products")
         .service(create_action)
         .service(get_action)
+        .service(get_by_slug_action)
+        .service(recently_viewed_action)
+        .service(get_all_action)
         .service(search_action)
+        .service(suggestions_action)
+        .service(query_action)
+        .service(by_category_action)
+        .service(manage_get_action)
+        .service(manage_get_all_action)
+        .service(manage_query_action)
         .service(update_action)
+        .service(update_status_action)
+        .service(update_seo_action)
         .service(delete_action)
+        .service(trash_get_all_action)
+        .service(restore_action)
+        .service(set_customizations_action)
+        .service(set_attributes_action)
+        .service(add_category_action)
+        .service(remove_category_action)
+        .service(set_primary_category_action)
+        .service(add_variation_option_action)
+        .service(remove_variation_option_action)
+        .service(set_bundle_action)
+        .service(remove_bundle_action)
+        .service(set_digital_action)
+        .service(get_files_action)
+        .service(upload_file_action)
+        .service(delete_file_action)
+        .service(create_variant_action)
+        .service(update_variant_action)
+        .service(delete_variant_action)
+        .service(upload_image_action)
+        .service(update_image_action)
+        .service(reorder_images_action)
+        .service(set_primary_image_action)
+        .service(delete_image_action)
+        .service(import_action)
+        .service(feed_action)
 }
 
 #[post("/create")]
 async fn create_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
     product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
     data: web::Json<ProductCreateDTO>,
 ) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
     let mut product_service = product_service_guard.lock().unwrap();
     let product = product_service.create(
         data.0.name,
@@ -35,7 +108,8 @@ async fn create_action(
         data.0.price,
         data.0.stock,
         data.0.product_image,
-        data.0.category_id
+        data.0.category_id,
+        data.0.status,
     )?;
 
     Ok(HttpResponse::build(StatusCode::CREATED)
@@ -49,35 +123,218 @@ async fn create_action(
         ))
 }
 
+/// Counts a view of the product and adds it to the recently viewed products of
+/// the viewer. Failures are only logged, the product being served anyway.
+fn record_view(
+    req: &HttpRequest,
+    user_service_guard: &web::Data<Arc<Mutex<UserService>>>,
+    view_service_guard: &web::Data<Arc<Mutex<ViewService>>>,
+    product_id: i64,
+) {
+    let viewer = identify_viewer(req, user_service_guard);
+    let mut view_service = view_service_guard.lock().unwrap();
+    if let Err(err) = view_service.record(viewer.as_ref(), product_id) {
+        eprintln!(
+            "Failed to record a view of product {}: {:?}",
+            product_id, err
+        );
+    }
+}
+
 #[get("/get")]
 async fn get_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
     product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    view_service_guard: web::Data<Arc<Mutex<ViewService>>>,
     data: web::Json<ProductGetDTO>,
 ) -> Result<impl Responder, HttpProductError> {
-    let mut product_service = product_service_guard.lock().unwrap();
-    let product = product_service.get(data.0.product_id)?;
+    let product = {
+        let mut product_service = product_service_guard.lock().unwrap();
+        product_service.get(data.0.product_id, ProductVisibility::Published)?
+    };
+    record_view(&req, &user_service_guard, &view_service_guard, product.id);
     Ok(HttpResponse::build(StatusCode::CREATED)
         .insert_header(ContentType::json())
         .body(serde_json::to_string(&product).unwrap()))
 }
 
+#[get("/slug/{slug}")]
+async fn get_by_slug_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    view_service_guard: web::Data<Arc<Mutex<ViewService>>>,
+    slug: web::Path<String>,
+) -> Result<impl Responder, HttpProductError> {
+    let lookup = {
+        let mut product_service = product_service_guard.lock().unwrap();
+        product_service.get_by_slug(&slug, ProductVisibility::Published)?
+    };
+    match lookup {
+        SlugLookup::Found(product) => {
+            record_view(&req, &user_service_guard, &view_service_guard, product.id);
+            Ok(HttpResponse::build(StatusCode::OK)
+                .insert_header(ContentType::json())
+                .body(serde_json::to_string(&product).unwrap()))
+        }
+        SlugLookup::Moved(slug) => Ok(HttpResponse::build(StatusCode::MOVED_PERMANENTLY)
+            .insert_header(ContentType::json())
+            .insert_header((header::LOCATION, format!("/products/slug/{}", slug)))
+            .body(
+                serde_json::to_string(&ProductMovedDTO {
+                    message: "product moved".to_string(),
+                    slug,
+                })
+                .unwrap(),
+            )),
+    }
+}
+
+/// Published products the user, or the visitor sending an anonymous token,
+/// viewed last, latest first.
+#[get("/recently_viewed")]
+async fn recently_viewed_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    view_service_guard: web::Data<Arc<Mutex<ViewService>>>,
+) -> Result<impl Responder, HttpProductError> {
+    let viewer =
+        identify_viewer(&req, &user_service_guard).ok_or(HttpProductError::Unauthorized)?;
+    let mut view_service = view_service_guard.lock().unwrap();
+    let products = view_service.get_recently_viewed(&viewer)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&products).unwrap()))
+}
+
 #[get("/search")]
 async fn search_action(
     product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
     data: web::Json<ProductSearchDTO>,
+    page: web::Query<PaginationDTO>,
 ) -> Result<impl Responder, HttpProductError> {
     let mut product_service = product_service_guard.lock().unwrap();
-    let products = product_service.search(data.0.search)?;
+    let products = product_service.search(data.0.search, page.to_search_pagination()?)?;
     Ok(HttpResponse::build(StatusCode::CREATED)
         .insert_header(ContentType::json())
         .body(serde_json::to_string(&products).unwrap()))
 }
 
+#[get("/get_all")]
+async fn get_all_action(
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    let mut product_service = product_service_guard.lock().unwrap();
+    let products = product_service.get_all(ProductVisibility::Published, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&products).unwrap()))
+}
+
+#[get("/suggestions")]
+async fn suggestions_action(
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductSuggestionsDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    let mut product_service = product_service_guard.lock().unwrap();
+    let suggestions = product_service.suggest(data.0.query, data.0.limit)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&suggestions).unwrap()))
+}
+
+#[get("/query")]
+async fn query_action(
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductQueryDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    let mut product_service = product_service_guard.lock().unwrap();
+    let listing = product_service.query(
+        data.0.filter,
+        ProductVisibility::Published,
+        data.0.sort,
+        page.to_pagination()?,
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&listing).unwrap()))
+}
+
+#[get("/by_category")]
+async fn by_category_action(
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductByCategoryDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    let mut product_service = product_service_guard.lock().unwrap();
+    let products = product_service.get_by_category(data.0.category_id, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&products).unwrap()))
+}
+
+#[get("/manage/get")]
+async fn manage_get_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductGetDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.get(data.0.product_id, ProductVisibility::All)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&product).unwrap()))
+}
+
+#[get("/manage/get_all")]
+async fn manage_get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let products = product_service.get_all(ProductVisibility::All, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&products).unwrap()))
+}
+
+#[get("/manage/query")]
+async fn manage_query_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductQueryDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let listing = product_service.query(
+        data.0.filter,
+        ProductVisibility::All,
+        data.0.sort,
+        page.to_pagination()?,
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&listing).unwrap()))
+}
+
 #[post("/update")]
 async fn update_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
     product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
     data: web::Json<ProductUpdateDTO>,
 ) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
     let mut product_service = product_service_guard.lock().unwrap();
     let product = product_service.update(
         data.0.product_id,
@@ -99,11 +356,61 @@ async fn update_action(
         ))
 }
 
+#[post("/status/update")]
+async fn update_status_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductStatusUpdateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.update_status(
+        data.0.product_id,
+        data.0.status,
+        data.0.publish_at,
+        data.0.unpublish_at,
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "product status updated".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/seo/update")]
+async fn update_seo_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductSeoUpdateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.update_seo(data.0.product_id, data.0.slug, data.0.seo)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "product seo updated".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
 #[post("/delete")]
 async fn delete_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
     product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
     data: web::Json<ProductDeleteDTO>,
 ) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
     let mut product_service = product_service_guard.lock().unwrap();
     product_service.delete(data.0.product_id)?;
     Ok(HttpResponse::build(StatusCode::CREATED)
@@ -115,3 +422,702 @@ async fn delete_action(
             .unwrap(),
         ))
 }
+
+#[get("/trash/get_all")]
+async fn trash_get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let products = product_service.get_trash(page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&products).unwrap()))
+}
+
+#[post("/trash/restore")]
+async fn restore_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductRestoreDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.restore(data.0.product_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "product restored".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/customizations/set")]
+async fn set_customizations_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductSetCustomizationsDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let fields = product_service.set_customization_fields(
+        data.0.product_id,
+        data.0
+            .fields
+            .into_iter()
+            .map(|field| CustomizationField {
+                id: 0,
+                product_id: data.0.product_id,
+                name: field.name,
+                field_type: field.field_type,
+                required: field.required,
+                max_length: field.max_length,
+                extra_price: field.extra_price,
+            })
+            .collect(),
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductCustomizationsUpdatedDTO {
+                message: "customizations updated".to_string(),
+                fields,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/attributes/set")]
+async fn set_attributes_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductSetAttributesDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let attributes = product_service.set_attributes(
+        data.0.product_id,
+        data.0
+            .attributes
+            .into_iter()
+            .map(|attribute| (attribute.attribute_id, attribute.value))
+            .collect(),
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductAttributesUpdatedDTO {
+                message: "attributes updated".to_string(),
+                attributes,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/categories/add")]
+async fn add_category_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductCategoryDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.add_category(data.0.product_id, data.0.category_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "category added".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/categories/remove")]
+async fn remove_category_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductCategoryDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.remove_category(data.0.product_id, data.0.category_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "category removed".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/categories/set_primary")]
+async fn set_primary_category_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductCategoryDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.set_primary_category(data.0.product_id, data.0.category_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "primary category updated".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variation_options/add")]
+async fn add_variation_option_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductVariationOptionDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product =
+        product_service.add_variation_option(data.0.product_id, data.0.variation_option_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "variation option added".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variation_options/remove")]
+async fn remove_variation_option_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductVariationOptionDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product =
+        product_service.remove_variation_option(data.0.product_id, data.0.variation_option_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "variation option removed".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/bundle/set")]
+async fn set_bundle_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductSetBundleDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product =
+        product_service.set_bundle(data.0.product_id, data.0.discount_percent, data.0.items)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "bundle updated".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/bundle/remove")]
+async fn remove_bundle_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductRemoveBundleDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.remove_bundle(data.0.product_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "bundle removed".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/digital/set")]
+async fn set_digital_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductSetDigitalDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let product = product_service.set_digital(data.0.product_id, data.0.is_digital)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductUpdatedDTO {
+                message: "product updated".to_string(),
+                product,
+            })
+            .unwrap(),
+        ))
+}
+
+#[get("/files/get_all")]
+async fn get_files_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductFilesDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let files = product_service.get_files(data.0.product_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&files).unwrap()))
+}
+
+/// Expects a multipart form with `product_id` and the file itself in `file`,
+/// downloaded under the name it is uploaded with. The product must be digital.
+#[post("/files/upload")]
+async fn upload_file_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    mut payload: Multipart,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+
+    let mut product_id: Option<i64> = None;
+    let mut file: Option<(String, Option<String>, Vec<u8>)> = None;
+
+    while let Some(mut field) = payload
+        .try_next()
+        .await
+        .map_err(|_| HttpProductError::InvalidData)?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        let file_name = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .map(str::to_string);
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string());
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field
+            .try_next()
+            .await
+            .map_err(|_| HttpProductError::InvalidData)?
+        {
+            if data.len() + chunk.len() > MAX_FILE_SIZE {
+                return Err(HttpProductError::InvalidData);
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        match name.as_str() {
+            "product_id" => {
+                product_id = String::from_utf8(data)
+                    .ok()
+                    .and_then(|value| value.trim().parse().ok())
+            }
+            "file" => file = Some((file_name.unwrap_or_default(), content_type, data)),
+            _ => {}
+        }
+    }
+
+    let product_id = product_id.ok_or(HttpProductError::InvalidData)?;
+    let (file_name, content_type, data) = file.ok_or(HttpProductError::InvalidData)?;
+
+    let mut product_service = product_service_guard.lock().unwrap();
+    let file = product_service.upload_file(product_id, &file_name, content_type, data)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductFileUploadedDTO {
+                message: "file uploaded".to_string(),
+                file,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/files/delete")]
+async fn delete_file_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductFileDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    product_service.delete_file(data.0.file_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "file deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variants/create")]
+async fn create_variant_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductVariantCreateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let variant = product_service.create_variant(
+        data.0.product_id,
+        data.0.sku,
+        data.0.price,
+        data.0.stock,
+        data.0.image,
+        data.0.variation_option_ids,
+    )?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductVariantUpdatedDTO {
+                message: "variant created".to_string(),
+                variant,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variants/update")]
+async fn update_variant_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductVariantUpdateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let variant = product_service.update_variant(
+        data.0.variant_id,
+        data.0.sku,
+        data.0.price,
+        data.0.stock,
+        data.0.image,
+        data.0.variation_option_ids,
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductVariantUpdatedDTO {
+                message: "variant updated".to_string(),
+                variant,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/variants/delete")]
+async fn delete_variant_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductVariantDeleteDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    product_service.delete_variant(data.0.variant_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "variant deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+/// Expects a multipart form with `product_id`, an optional `alt_text` and the
+/// image itself in `file`. The image format is detected from its content.
+#[post("/images/upload")]
+async fn upload_image_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    mut payload: Multipart,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+
+    let mut product_id: Option<i64> = None;
+    let mut alt_text: Option<String> = None;
+    let mut file: Option<Vec<u8>> = None;
+
+    while let Some(mut field) = payload
+        .try_next()
+        .await
+        .map_err(|_| HttpProductError::InvalidData)?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field
+            .try_next()
+            .await
+            .map_err(|_| HttpProductError::InvalidData)?
+        {
+            if data.len() + chunk.len() > MAX_IMAGE_SIZE {
+                return Err(HttpProductError::InvalidImage);
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        match name.as_str() {
+            "product_id" => {
+                product_id = String::from_utf8(data)
+                    .ok()
+                    .and_then(|value| value.trim().parse().ok())
+            }
+            "alt_text" => alt_text = String::from_utf8(data).ok(),
+            "file" => file = Some(data),
+            _ => {}
+        }
+    }
+
+    let product_id = product_id.ok_or(HttpProductError::InvalidData)?;
+    let data = file.ok_or(HttpProductError::InvalidData)?;
+
+    let mut product_service = product_service_guard.lock().unwrap();
+    let image = product_service.upload_image(product_id, data, alt_text)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductImageUpdatedDTO {
+                message: "image uploaded".to_string(),
+                image,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/images/update")]
+async fn update_image_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductImageUpdateDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let image = product_service.update_image(data.0.image_id, data.0.alt_text)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductImageUpdatedDTO {
+                message: "image updated".to_string(),
+                image,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/images/reorder")]
+async fn reorder_images_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductImagesReorderDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let images = product_service.reorder_images(data.0.product_id, data.0.image_ids)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductImagesUpdatedDTO {
+                message: "images reordered".to_string(),
+                images,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/images/set_primary")]
+async fn set_primary_image_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductImageDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    let image = product_service.set_primary_image(data.0.image_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ProductImageUpdatedDTO {
+                message: "primary image updated".to_string(),
+                image,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/images/delete")]
+async fn delete_image_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    product_service_guard: web::Data<Arc<Mutex<ProductService>>>,
+    data: web::Json<ProductImageDTO>,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut product_service = product_service_guard.lock().unwrap();
+    product_service.delete_image(data.0.image_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "image deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+/// Imports a catalog file sent as the `file` field, along with the optional
+/// `format`, `dry_run` and `create_categories` fields. The format defaults to
+/// the extension of the file name.
+#[post("/import")]
+async fn import_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    import_service_guard: web::Data<Arc<Mutex<ImportService>>>,
+    mut payload: Multipart,
+) -> Result<impl Responder, HttpProductError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+
+    let mut format: Option<ImportFormat> = None;
+    let mut options = ImportOptions::default();
+    let mut file: Option<Vec<u8>> = None;
+
+    while let Some(mut field) = payload
+        .try_next()
+        .await
+        .map_err(|_| HttpProductError::InvalidData)?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" && format.is_none() {
+            format = field
+                .content_disposition()
+                .and_then(|disposition| disposition.get_filename())
+                .and_then(|filename| filename.rsplit_once('.'))
+                .and_then(|(_, extension)| extension.to_lowercase().parse().ok());
+        }
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field
+            .try_next()
+            .await
+            .map_err(|_| HttpProductError::InvalidData)?
+        {
+            if data.len() + chunk.len() > MAX_IMPORT_SIZE {
+                return Err(HttpProductError::InvalidData);
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        let value = || String::from_utf8(data.clone()).unwrap_or_default();
+        match name.as_str() {
+            "format" => {
+                format = Some(
+                    value()
+                        .trim()
+                        .parse()
+                        .map_err(|_| HttpProductError::InvalidData)?,
+                )
+            }
+            "dry_run" => options.dry_run = value().trim() == "true",
+            "create_categories" => options.create_categories = value().trim() == "true",
+            "file" => file = Some(data),
+            _ => {}
+        }
+    }
+
+    let data = file.ok_or(HttpProductError::InvalidData)?;
+    let format = format.ok_or(HttpProductError::InvalidData)?;
+
+    let mut import_service = import_service_guard.lock().unwrap();
+    let report = import_service.import_products(&data, format, options)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&report).unwrap()))
+}
+
+/// Serves the published products as `csv`, `jsonl` or `xml` (a Google Merchant
+/// feed). The export is cached on disk and streamed from there.
+#[get("/feed/{format}")]
+async fn feed_action(
+    export_service_guard: web::Data<Arc<Mutex<ExportService>>>,
+    format: web::Path<String>,
+) -> Result<impl Responder, HttpProductError> {
+    let format: ExportFormat = format.parse().map_err(|_| HttpProductError::InvalidData)?;
+
+    let export_service_guard = export_service_guard.into_inner();
+    let export = web::block(move || export_service_guard.lock().unwrap().cached_export(format))
+        .await
+        .map_err(|_| HttpProductError::InternalError)??;
+    let file = File::open(&export.path).map_err(|_| HttpProductError::InternalError)?;
+
+    let body = stream::try_unfold(file, |mut file| async move {
+        let (file, chunk) = web::block(move || {
+            let mut chunk = vec![0; FEED_CHUNK_SIZE];
+            let len = file.read(&mut chunk)?;
+            chunk.truncate(len);
+            Ok::<_, io::Error>((file, chunk))
+        })
+        .await
+        .map_err(io::Error::other)??;
+        let next = (!chunk.is_empty()).then(|| (web::Bytes::from(chunk), file));
+        Ok::<_, io::Error>(next)
+    });
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(format.content_type())
+        .insert_header((
+            header::CACHE_CONTROL,
+            format!("public, max-age={}", export.max_age.as_secs()),
+        ))
+        .streaming(body))
+}
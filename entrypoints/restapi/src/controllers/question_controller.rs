@@ -0,0 +1,159 @@
+use crate::{
+    dto::{
+        pagination_dto::PaginationDTO,
+        question_dto::{
+            AnswerModerateDTO, AnswerUpdatedDTO, QuestionAnswerDTO, QuestionAskDTO,
+            QuestionByProductDTO, QuestionManageGetAllDTO, QuestionModerateDTO, QuestionUpdatedDTO,
+        },
+    },
+    errors::question_errors::HttpQuestionError,
+    middlewares::auth::{MANAGER_ROLES, authenticate, authorize},
+};
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{StatusCode, header::ContentType},
+    post, web,
+};
+use ecommercers::core::services::{question_service::QuestionService, user_service::UserService};
+use std::sync::{Arc, Mutex};
+
+pub fn new_question_controller() -> Scope {
+    web::scope("/questions")
+        .service(ask_action)
+        .service(by_product_action)
+        .service(answer_action)
+        .service(manage_get_all_action)
+        .service(manage_answers_action)
+        .service(moderate_action)
+        .service(moderate_answer_action)
+}
+
+#[post("/ask")]
+async fn ask_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    question_service_guard: web::Data<Arc<Mutex<QuestionService>>>,
+    data: web::Json<QuestionAskDTO>,
+) -> Result<impl Responder, HttpQuestionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut question_service = question_service_guard.lock().unwrap();
+    let question = question_service.ask(user.id, data.0.product_id, data.0.body)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&QuestionUpdatedDTO {
+                message: "question submitted for moderation".to_string(),
+                question,
+            })
+            .unwrap(),
+        ))
+}
+
+#[get("/by_product")]
+async fn by_product_action(
+    question_service_guard: web::Data<Arc<Mutex<QuestionService>>>,
+    data: web::Json<QuestionByProductDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpQuestionError> {
+    let mut question_service = question_service_guard.lock().unwrap();
+    let questions = question_service.get_by_product(data.0.product_id, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&questions).unwrap()))
+}
+
+#[post("/answer")]
+async fn answer_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    question_service_guard: web::Data<Arc<Mutex<QuestionService>>>,
+    data: web::Json<QuestionAnswerDTO>,
+) -> Result<impl Responder, HttpQuestionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut question_service = question_service_guard.lock().unwrap();
+    let answer =
+        question_service.answer(user.id, user.user_role, data.0.question_id, data.0.body)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&AnswerUpdatedDTO {
+                message: "answer submitted".to_string(),
+                answer,
+            })
+            .unwrap(),
+        ))
+}
+
+#[get("/manage/get_all")]
+async fn manage_get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    question_service_guard: web::Data<Arc<Mutex<QuestionService>>>,
+    data: web::Json<QuestionManageGetAllDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpQuestionError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut question_service = question_service_guard.lock().unwrap();
+    let questions = question_service.get_by_status(data.0.status, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&questions).unwrap()))
+}
+
+#[get("/manage/answers")]
+async fn manage_answers_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    question_service_guard: web::Data<Arc<Mutex<QuestionService>>>,
+    data: web::Json<QuestionManageGetAllDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpQuestionError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut question_service = question_service_guard.lock().unwrap();
+    let answers = question_service.get_answers_by_status(data.0.status, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&answers).unwrap()))
+}
+
+#[post("/moderate")]
+async fn moderate_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    question_service_guard: web::Data<Arc<Mutex<QuestionService>>>,
+    data: web::Json<QuestionModerateDTO>,
+) -> Result<impl Responder, HttpQuestionError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut question_service = question_service_guard.lock().unwrap();
+    let question = question_service.moderate_question(data.0.question_id, data.0.status)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&QuestionUpdatedDTO {
+                message: "question moderated".to_string(),
+                question,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/answers/moderate")]
+async fn moderate_answer_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    question_service_guard: web::Data<Arc<Mutex<QuestionService>>>,
+    data: web::Json<AnswerModerateDTO>,
+) -> Result<impl Responder, HttpQuestionError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut question_service = question_service_guard.lock().unwrap();
+    let answer = question_service.moderate_answer(data.0.answer_id, data.0.status)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&AnswerUpdatedDTO {
+                message: "answer moderated".to_string(),
+                answer,
+            })
+            .unwrap(),
+        ))
+}
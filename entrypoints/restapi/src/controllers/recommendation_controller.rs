@@ -0,0 +1,31 @@
+use crate::{
+    errors::recommendation_errors::HttpRecommendationError, middlewares::auth::authenticate,
+};
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{StatusCode, header::ContentType},
+    web,
+};
+use ecommercers::core::services::{
+    recommendation_service::RecommendationService, user_service::UserService,
+};
+use std::sync::{Arc, Mutex};
+
+pub fn new_recommendation_controller() -> Scope {
+    web::scope("/recommendations").service(cart_action)
+}
+
+/// "You may also like" products for the active cart of the user.
+#[get("/cart")]
+async fn cart_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    recommendation_service_guard: web::Data<Arc<Mutex<RecommendationService>>>,
+) -> Result<impl Responder, HttpRecommendationError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut recommendation_service = recommendation_service_guard.lock().unwrap();
+    let products = recommendation_service.get_for_cart(user.id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&products).unwrap()))
+}
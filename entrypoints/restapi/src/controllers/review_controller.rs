@@ -0,0 +1,141 @@
+use crate::{
+    dto::{
+        pagination_dto::PaginationDTO,
+        review_dto::{
+            ReviewByProductDTO, ReviewCreateDTO, ReviewManageGetAllDTO, ReviewModerateDTO,
+            ReviewRespondDTO, ReviewUpdatedDTO, ReviewVoteDTO,
+        },
+    },
+    errors::review_errors::HttpReviewError,
+    middlewares::auth::{MANAGER_ROLES, authenticate, authorize},
+};
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{StatusCode, header::ContentType},
+    post, web,
+};
+use ecommercers::core::services::{review_service::ReviewService, user_service::UserService};
+use std::sync::{Arc, Mutex};
+
+pub fn new_review_controller() -> Scope {
+    web::scope("/reviews")
+        .service(create_action)
+        .service(by_product_action)
+        .service(manage_get_all_action)
+        .service(moderate_action)
+        .service(respond_action)
+        .service(vote_helpful_action)
+}
+
+#[post("/create")]
+async fn create_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    review_service_guard: web::Data<Arc<Mutex<ReviewService>>>,
+    data: web::Json<ReviewCreateDTO>,
+) -> Result<impl Responder, HttpReviewError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut review_service = review_service_guard.lock().unwrap();
+    let review = review_service.create(user.id, data.0.product_id, data.0.rating, data.0.body)?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ReviewUpdatedDTO {
+                message: "review submitted for moderation".to_string(),
+                review,
+            })
+            .unwrap(),
+        ))
+}
+
+#[get("/by_product")]
+async fn by_product_action(
+    review_service_guard: web::Data<Arc<Mutex<ReviewService>>>,
+    data: web::Json<ReviewByProductDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpReviewError> {
+    let mut review_service = review_service_guard.lock().unwrap();
+    let reviews = review_service.get_by_product(data.0.product_id, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&reviews).unwrap()))
+}
+
+#[get("/manage/get_all")]
+async fn manage_get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    review_service_guard: web::Data<Arc<Mutex<ReviewService>>>,
+    data: web::Json<ReviewManageGetAllDTO>,
+    page: web::Query<PaginationDTO>,
+) -> Result<impl Responder, HttpReviewError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut review_service = review_service_guard.lock().unwrap();
+    let reviews = review_service.get_by_status(data.0.status, page.to_pagination()?)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&reviews).unwrap()))
+}
+
+#[post("/moderate")]
+async fn moderate_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    review_service_guard: web::Data<Arc<Mutex<ReviewService>>>,
+    data: web::Json<ReviewModerateDTO>,
+) -> Result<impl Responder, HttpReviewError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut review_service = review_service_guard.lock().unwrap();
+    let review = review_service.moderate(data.0.review_id, data.0.status)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ReviewUpdatedDTO {
+                message: "review moderated".to_string(),
+                review,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/respond")]
+async fn respond_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    review_service_guard: web::Data<Arc<Mutex<ReviewService>>>,
+    data: web::Json<ReviewRespondDTO>,
+) -> Result<impl Responder, HttpReviewError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut review_service = review_service_guard.lock().unwrap();
+    let review = review_service.respond(data.0.review_id, data.0.response)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ReviewUpdatedDTO {
+                message: "review response updated".to_string(),
+                review,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/helpful")]
+async fn vote_helpful_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    review_service_guard: web::Data<Arc<Mutex<ReviewService>>>,
+    data: web::Json<ReviewVoteDTO>,
+) -> Result<impl Responder, HttpReviewError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut review_service = review_service_guard.lock().unwrap();
+    let review = review_service.vote_helpful(user.id, data.0.review_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&ReviewUpdatedDTO {
+                message: "review voted helpful".to_string(),
+                review,
+            })
+            .unwrap(),
+        ))
+}
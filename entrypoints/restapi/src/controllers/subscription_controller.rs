@@ -0,0 +1,239 @@
+use crate::{
+    dto::subscription_dto::{
+        SubscriptionCreateDTO, SubscriptionIdDTO, SubscriptionPlanCreateDTO,
+        SubscriptionPlanDeleteDTO, SubscriptionPlanUpdateDTO, SubscriptionPlanUpdatedDTO,
+        SubscriptionPlansByProductDTO, SubscriptionUpdatedDTO,
+    },
+    errors::{SimpleMessage, subscription_errors::HttpSubscriptionError},
+    middlewares::auth::{MANAGER_ROLES, authenticate, authorize},
+};
+use actix_web::{
+    HttpRequest, HttpResponse, Responder, Scope, get,
+    http::{StatusCode, header::ContentType},
+    post, web,
+};
+use ecommercers::core::services::{
+    subscription_service::SubscriptionService, user_service::UserService,
+};
+use std::sync::{Arc, Mutex};
+
+pub fn new_subscription_controller() -> Scope {
+    web::scope("/subscriptions")
+        .service(plans_get_all_action)
+        .service(plans_create_action)
+        .service(plans_update_action)
+        .service(plans_delete_action)
+        .service(create_action)
+        .service(get_all_action)
+        .service(pause_action)
+        .service(resume_action)
+        .service(skip_action)
+        .service(cancel_action)
+}
+
+#[get("/plans/get_all")]
+async fn plans_get_all_action(
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionPlansByProductDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let plans = subscription_service.get_plans(data.0.product_id, true)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&plans).unwrap()))
+}
+
+#[post("/plans/create")]
+async fn plans_create_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionPlanCreateDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let plan = subscription_service.create_plan(
+        data.0.product_id,
+        data.0.interval_days,
+        data.0.discount_percent,
+    )?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SubscriptionPlanUpdatedDTO {
+                message: "subscription plan created".to_string(),
+                plan,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/plans/update")]
+async fn plans_update_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionPlanUpdateDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let plan = subscription_service.update_plan(
+        data.0.plan_id,
+        data.0.discount_percent,
+        data.0.is_active,
+    )?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SubscriptionPlanUpdatedDTO {
+                message: "subscription plan updated".to_string(),
+                plan,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/plans/delete")]
+async fn plans_delete_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionPlanDeleteDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    authorize(&req, &user_service_guard, MANAGER_ROLES)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    subscription_service.delete_plan(data.0.plan_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SimpleMessage {
+                message: "subscription plan deleted".to_string(),
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/create")]
+async fn create_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionCreateDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let subscription = subscription_service.subscribe(
+        user.id,
+        data.0.plan_id,
+        data.0.variant_id,
+        data.0.quantity,
+        data.0.first_run_at,
+    )?;
+    Ok(HttpResponse::build(StatusCode::CREATED)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SubscriptionUpdatedDTO {
+                message: "subscription created".to_string(),
+                subscription,
+            })
+            .unwrap(),
+        ))
+}
+
+#[get("/get_all")]
+async fn get_all_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let subscriptions = subscription_service.get_all(user.id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(serde_json::to_string(&subscriptions).unwrap()))
+}
+
+#[post("/pause")]
+async fn pause_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionIdDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let subscription = subscription_service.pause(user.id, data.0.subscription_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SubscriptionUpdatedDTO {
+                message: "subscription paused".to_string(),
+                subscription,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/resume")]
+async fn resume_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionIdDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let subscription = subscription_service.resume(user.id, data.0.subscription_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SubscriptionUpdatedDTO {
+                message: "subscription resumed".to_string(),
+                subscription,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/skip")]
+async fn skip_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionIdDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let subscription = subscription_service.skip(user.id, data.0.subscription_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SubscriptionUpdatedDTO {
+                message: "next delivery skipped".to_string(),
+                subscription,
+            })
+            .unwrap(),
+        ))
+}
+
+#[post("/cancel")]
+async fn cancel_action(
+    req: HttpRequest,
+    user_service_guard: web::Data<Arc<Mutex<UserService>>>,
+    subscription_service_guard: web::Data<Arc<Mutex<SubscriptionService>>>,
+    data: web::Json<SubscriptionIdDTO>,
+) -> Result<impl Responder, HttpSubscriptionError> {
+    let user = authenticate(&req, &user_service_guard)?;
+    let mut subscription_service = subscription_service_guard.lock().unwrap();
+    let subscription = subscription_service.cancel(user.id, data.0.subscription_id)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .insert_header(ContentType::json())
+        .body(
+            serde_json::to_string(&SubscriptionUpdatedDTO {
+                message: "subscription cancelled".to_string(),
+                subscription,
+            })
+            .unwrap(),
+        ))
+}
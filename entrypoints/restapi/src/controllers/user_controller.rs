@@ -92,8 +92,8 @@ async fn authorization_action(
 
     if let Some(auth_header) = req.headers().get("Authorization") {
         if let Ok(auth_str) = auth_header.to_str() {
-            if auth_str.starts_with("Bearer ") {
-                let access_token = auth_str[7..].to_string(); // Extract token after "Bearer "
+            if let Some(access_token) = auth_str.strip_prefix("Bearer ") {
+                let access_token = access_token.to_string();
 
                 let user = user_service.authorization(access_token)?;
 
@@ -0,0 +1,81 @@
+use ecommercers::core::models::cart::{Cart, CartItem};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartCreateDTO {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartCreatedDTO {
+    pub message: String,
+    pub cart: Cart,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartGetDTO {
+    pub cart_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartDetailsDTO {
+    pub cart: Cart,
+    pub items: Vec<CartItem>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartRenameDTO {
+    pub cart_id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartUpdatedDTO {
+    pub message: String,
+    pub cart: Cart,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartActivateDTO {
+    pub cart_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartDeleteDTO {
+    pub cart_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartItemAddDTO {
+    pub cart_id: Option<i64>,
+    pub product_id: i64,
+    #[serde(default)]
+    pub variant_id: Option<i64>,
+    pub quantity: i32,
+    #[serde(default)]
+    pub customizations: Value,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartItemUpdateDTO {
+    pub cart_item_id: i64,
+    pub quantity: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartItemRemoveDTO {
+    pub cart_item_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartItemMoveDTO {
+    pub cart_item_id: i64,
+    pub target_cart_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CartItemUpdatedDTO {
+    pub message: String,
+    pub item: CartItem,
+}
@@ -1,4 +1,9 @@
-use ecommercers::core::models::category::Category;
+use ecommercers::core::models::{
+    attribute::{Attribute, AttributeType},
+    category::Category,
+    product::{Variation, VariationOption},
+    seo::SeoMetadata,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -19,6 +24,11 @@ pub struct CategoryDeleteDTO {
     pub category_id: i64
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CategoryRestoreDTO {
+    pub category_id: i64
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CategoryGetDTO {
     pub category_id: i64
@@ -31,3 +41,115 @@ pub struct CategoryUpdateDTO {
     pub description: String,
     pub parent_id: Option<i64>,
 }
+
+/// Body of the redirect answered for a former category slug.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CategoryMovedDTO {
+    pub message: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CategorySeoUpdateDTO {
+    pub category_id: i64,
+    /// Keeps the current slug when unset.
+    pub slug: Option<String>,
+    #[serde(flatten)]
+    pub seo: SeoMetadata,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationGetAllDTO {
+    pub category_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationDetailsDTO {
+    pub variation: Variation,
+    pub options: Vec<VariationOption>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationCreateDTO {
+    pub category_id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationUpdateDTO {
+    pub variation_id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationDeleteDTO {
+    pub variation_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationSavedDTO {
+    pub message: String,
+    pub variation: Variation,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationOptionCreateDTO {
+    pub variation_id: i64,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationOptionUpdateDTO {
+    pub variation_option_id: i64,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationOptionDeleteDTO {
+    pub variation_option_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VariationOptionSavedDTO {
+    pub message: String,
+    pub option: VariationOption,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AttributeGetAllDTO {
+    pub category_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AttributeCreateDTO {
+    pub category_id: i64,
+    pub name: String,
+    pub data_type: AttributeType,
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub is_filterable: bool,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AttributeUpdateDTO {
+    pub attribute_id: i64,
+    pub name: String,
+    pub unit: Option<String>,
+    #[serde(default)]
+    pub is_filterable: bool,
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AttributeDeleteDTO {
+    pub attribute_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AttributeSavedDTO {
+    pub message: String,
+    pub attribute: Attribute,
+}
@@ -1,3 +1,9 @@
 pub mod user_dto;
 pub mod product_dto;
-pub mod category_dto;
\ No newline at end of file
+pub mod category_dto;
+pub mod cart_dto;
+pub mod order_dto;
+pub mod pagination_dto;
+pub mod review_dto;
+pub mod question_dto;
+pub mod subscription_dto;
\ No newline at end of file
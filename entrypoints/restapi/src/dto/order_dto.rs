@@ -0,0 +1,47 @@
+use ecommercers::core::models::{
+    download::DownloadLink,
+    order::{Order, OrderItem},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderCheckoutDTO {
+    pub cart_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderGetDTO {
+    pub order_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderDetailsDTO {
+    pub order: Order,
+    pub items: Vec<OrderItem>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderDownloadsDTO {
+    pub links: Vec<DownloadLink>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderUpdatedDTO {
+    pub message: String,
+    pub order: Order,
+    pub items: Vec<OrderItem>,
+}
+
+/// Query of a signed download link.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DownloadQueryDTO {
+    pub expires: i64,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderCreatedDTO {
+    pub message: String,
+    pub order: Order,
+    pub items: Vec<OrderItem>,
+}
@@ -0,0 +1,22 @@
+use ecommercers::core::models::pagination::{Pagination, PaginationError, SearchPagination};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PaginationDTO {
+    /// `next_cursor` of the previous page.
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    #[serde(default)]
+    pub with_total: bool,
+}
+
+impl PaginationDTO {
+    pub fn to_pagination(&self) -> Result<Pagination, PaginationError> {
+        Pagination::new(self.cursor.as_deref(), self.limit, self.with_total)
+    }
+
+    /// Search results are not counted, so `with_total` is ignored.
+    pub fn to_search_pagination(&self) -> Result<SearchPagination, PaginationError> {
+        SearchPagination::new(self.cursor.as_deref(), self.limit)
+    }
+}
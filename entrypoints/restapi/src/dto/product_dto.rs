@@ -1,4 +1,12 @@
-use ecommercers::core::models::product::Product;
+use chrono::NaiveDateTime;
+use ecommercers::core::models::{
+    attribute::{AttributeValue, ProductAttribute},
+    bundle::BundleItem,
+    customization::{CustomizationField, CustomizationFieldType},
+    download::ProductFile,
+    product::{Product, ProductFilter, ProductImage, ProductSort, ProductStatus, ProductVariant},
+    seo::SeoMetadata,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -9,6 +17,9 @@ pub struct ProductCreateDTO {
     pub stock: i32,
     pub product_image: Option<String>,
     pub category_id: Option<i64>,
+    /// Products start as drafts unless told otherwise.
+    #[serde(default)]
+    pub status: ProductStatus,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -39,12 +50,208 @@ pub struct ProductGetDTO {
     pub product_id: i64,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductStatusUpdateDTO {
+    pub product_id: i64,
+    pub status: ProductStatus,
+    pub publish_at: Option<NaiveDateTime>,
+    pub unpublish_at: Option<NaiveDateTime>,
+}
+
+/// Body of the redirect answered for a former product slug.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductMovedDTO {
+    pub message: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductSeoUpdateDTO {
+    pub product_id: i64,
+    /// Keeps the current slug when unset.
+    pub slug: Option<String>,
+    #[serde(flatten)]
+    pub seo: SeoMetadata,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ProductSearchDTO {
     pub search: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductSuggestionsDTO {
+    pub query: String,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductQueryDTO {
+    #[serde(default)]
+    pub filter: ProductFilter,
+    #[serde(default)]
+    pub sort: ProductSort,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductByCategoryDTO {
+    pub category_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductCategoryDTO {
+    pub product_id: i64,
+    pub category_id: i64,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ProductDeleteDTO {
     pub product_id: i64,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductRestoreDTO {
+    pub product_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CustomizationFieldDTO {
+    pub name: String,
+    pub field_type: CustomizationFieldType,
+    #[serde(default)]
+    pub required: bool,
+    pub max_length: Option<i32>,
+    #[serde(default)]
+    pub extra_price: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductSetCustomizationsDTO {
+    pub product_id: i64,
+    pub fields: Vec<CustomizationFieldDTO>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductCustomizationsUpdatedDTO {
+    pub message: String,
+    pub fields: Vec<CustomizationField>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductAttributeDTO {
+    pub attribute_id: i64,
+    pub value: AttributeValue,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductSetAttributesDTO {
+    pub product_id: i64,
+    pub attributes: Vec<ProductAttributeDTO>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductAttributesUpdatedDTO {
+    pub message: String,
+    pub attributes: Vec<ProductAttribute>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductSetBundleDTO {
+    pub product_id: i64,
+    /// Leave unset to keep the price of the product fixed.
+    pub discount_percent: Option<f64>,
+    pub items: Vec<BundleItem>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductRemoveBundleDTO {
+    pub product_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductSetDigitalDTO {
+    pub product_id: i64,
+    pub is_digital: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductFilesDTO {
+    pub product_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductFileDTO {
+    pub file_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductFileUploadedDTO {
+    pub message: String,
+    pub file: ProductFile,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductVariationOptionDTO {
+    pub product_id: i64,
+    pub variation_option_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductVariantCreateDTO {
+    pub product_id: i64,
+    pub sku: String,
+    pub price: Option<f64>,
+    pub stock: i32,
+    pub image: Option<String>,
+    pub variation_option_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductVariantUpdateDTO {
+    pub variant_id: i64,
+    pub sku: String,
+    pub price: Option<f64>,
+    pub stock: i32,
+    pub image: Option<String>,
+    pub variation_option_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductVariantDeleteDTO {
+    pub variant_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductVariantUpdatedDTO {
+    pub message: String,
+    pub variant: ProductVariant,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductImageUpdateDTO {
+    pub image_id: i64,
+    pub alt_text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductImageDTO {
+    pub image_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductImagesReorderDTO {
+    pub product_id: i64,
+    pub image_ids: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductImageUpdatedDTO {
+    pub message: String,
+    pub image: ProductImage,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProductImagesUpdatedDTO {
+    pub message: String,
+    pub images: Vec<ProductImage>,
+}
@@ -0,0 +1,52 @@
+use ecommercers::core::models::{
+    moderation::ModerationStatus,
+    question::{ProductAnswer, ProductQuestion},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionAskDTO {
+    pub product_id: i64,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionByProductDTO {
+    pub product_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionAnswerDTO {
+    pub question_id: i64,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionManageGetAllDTO {
+    #[serde(default)]
+    pub status: ModerationStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionModerateDTO {
+    pub question_id: i64,
+    pub status: ModerationStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnswerModerateDTO {
+    pub answer_id: i64,
+    pub status: ModerationStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QuestionUpdatedDTO {
+    pub message: String,
+    pub question: ProductQuestion,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnswerUpdatedDTO {
+    pub message: String,
+    pub answer: ProductAnswer,
+}
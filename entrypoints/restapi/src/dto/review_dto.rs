@@ -0,0 +1,44 @@
+use ecommercers::core::models::{moderation::ModerationStatus, review::Review};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewCreateDTO {
+    pub product_id: i64,
+    pub rating: i32,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewByProductDTO {
+    pub product_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewManageGetAllDTO {
+    #[serde(default)]
+    pub status: ModerationStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewModerateDTO {
+    pub review_id: i64,
+    pub status: ModerationStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewRespondDTO {
+    pub review_id: i64,
+    /// Empty to remove the response.
+    pub response: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewVoteDTO {
+    pub review_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReviewUpdatedDTO {
+    pub message: String,
+    pub review: Review,
+}
@@ -0,0 +1,54 @@
+use chrono::NaiveDateTime;
+use ecommercers::core::models::subscription::{Subscription, SubscriptionPlan};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionPlansByProductDTO {
+    pub product_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionPlanCreateDTO {
+    pub product_id: i64,
+    pub interval_days: i32,
+    #[serde(default)]
+    pub discount_percent: f64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionPlanUpdateDTO {
+    pub plan_id: i64,
+    pub discount_percent: f64,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionPlanDeleteDTO {
+    pub plan_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionPlanUpdatedDTO {
+    pub message: String,
+    pub plan: SubscriptionPlan,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionCreateDTO {
+    pub plan_id: i64,
+    pub variant_id: Option<i64>,
+    pub quantity: i32,
+    /// When the first order is placed; as soon as possible when unset.
+    pub first_run_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionIdDTO {
+    pub subscription_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubscriptionUpdatedDTO {
+    pub message: String,
+    pub subscription: Subscription,
+}
@@ -0,0 +1,75 @@
+use super::ErrorMessage;
+use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
+use derive_more::derive::{Display, Error};
+use ecommercers::core::{models::auth::AuthError, ports::cart_repository::CartError};
+
+#[derive(Debug, Display, Error)]
+pub enum HttpCartError {
+    #[display("internal error")]
+    InternalError,
+
+    #[display("cart not found")]
+    NotFound,
+
+    #[display("invalid data")]
+    InvalidData,
+
+    #[display("cart already exists")]
+    Conflict,
+
+    #[display("invalid quantity")]
+    InvalidQuantity,
+
+    #[display("invalid customization")]
+    InvalidCustomization,
+
+    #[display("unauthorized")]
+    Unauthorized,
+}
+
+impl From<CartError> for HttpCartError {
+    fn from(value: CartError) -> Self {
+        match value {
+            CartError::NotFound => HttpCartError::NotFound,
+            CartError::InvalidData => HttpCartError::InvalidData,
+            CartError::DatabaseError => HttpCartError::InternalError,
+            CartError::Conflict => HttpCartError::Conflict,
+            CartError::InvalidQuantity => HttpCartError::InvalidQuantity,
+            CartError::InvalidCustomization => HttpCartError::InvalidCustomization,
+        }
+    }
+}
+
+impl From<AuthError> for HttpCartError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpCartError::InternalError,
+            _ => HttpCartError::Unauthorized,
+        }
+    }
+}
+
+impl ResponseError for HttpCartError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            HttpCartError::InternalError => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+            HttpCartError::NotFound => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpCartError::InvalidData => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpCartError::Conflict => actix_web::http::StatusCode::CONFLICT,
+            HttpCartError::InvalidQuantity => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpCartError::InvalidCustomization => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpCartError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .body(
+                serde_json::to_string(&ErrorMessage {
+                    error: self.to_string(),
+                })
+                .unwrap(),
+            )
+    }
+}
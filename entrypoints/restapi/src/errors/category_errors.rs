@@ -1,7 +1,9 @@
 use super::ErrorMessage;
 use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
 use derive_more::derive::{Display, Error};
-use ecommercers::core::models::category::CategoryError;
+use ecommercers::core::models::{
+    auth::AuthError, category::CategoryError, pagination::PaginationError,
+};
 
 #[derive(Debug, Display, Error)]
 pub enum HttpCategoryError {
@@ -16,6 +18,15 @@ pub enum HttpCategoryError {
 
     #[display("category already exist")]
     CategoryAlreadyExist,
+
+    #[display("slug already taken")]
+    SlugTaken,
+
+    #[display("permission denied")]
+    PermissionDenied,
+
+    #[display("unauthorized")]
+    Unauthorized,
 }
 
 impl From<CategoryError> for HttpCategoryError {
@@ -25,10 +36,27 @@ impl From<CategoryError> for HttpCategoryError {
             CategoryError::NotFound => HttpCategoryError::NotFound,
             CategoryError::InvalidData => HttpCategoryError::InvalidData,
             CategoryError::CategoryAlreadyExist => HttpCategoryError::CategoryAlreadyExist,
+            CategoryError::SlugTaken => HttpCategoryError::SlugTaken,
         }
     }
 }
 
+impl From<AuthError> for HttpCategoryError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpCategoryError::InternalError,
+            AuthError::PermissionDenied => HttpCategoryError::PermissionDenied,
+            _ => HttpCategoryError::Unauthorized,
+        }
+    }
+}
+
+impl From<PaginationError> for HttpCategoryError {
+    fn from(_: PaginationError) -> Self {
+        HttpCategoryError::InvalidData
+    }
+}
+
 impl ResponseError for HttpCategoryError {
     fn status_code(&self) -> actix_web::http::StatusCode {
         match self {
@@ -36,6 +64,9 @@ impl ResponseError for HttpCategoryError {
             HttpCategoryError::NotFound => actix_web::http::StatusCode::BAD_REQUEST,
             HttpCategoryError::InvalidData => actix_web::http::StatusCode::BAD_REQUEST,
             HttpCategoryError::CategoryAlreadyExist => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpCategoryError::SlugTaken => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpCategoryError::PermissionDenied => actix_web::http::StatusCode::FORBIDDEN,
+            HttpCategoryError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
         }
     }
 
@@ -2,6 +2,12 @@ use serde::Serialize;
 pub mod user_errors;
 pub mod product_errors;
 pub mod category_errors;
+pub mod cart_errors;
+pub mod order_errors;
+pub mod review_errors;
+pub mod question_errors;
+pub mod recommendation_errors;
+pub mod subscription_errors;
 
 #[derive(Debug, Serialize)]
 pub(crate) struct ErrorMessage {
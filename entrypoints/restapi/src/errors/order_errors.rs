@@ -0,0 +1,91 @@
+use super::ErrorMessage;
+use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
+use derive_more::derive::{Display, Error};
+use ecommercers::core::models::{auth::AuthError, order::OrderError, pagination::PaginationError};
+
+#[derive(Debug, Display, Error)]
+pub enum HttpOrderError {
+    #[display("internal error")]
+    InternalError,
+
+    #[display("order not found")]
+    NotFound,
+
+    #[display("invalid data")]
+    InvalidData,
+
+    #[display("invalid status transition")]
+    InvalidStatusTransition,
+
+    #[display("out of stock")]
+    OutOfStock,
+
+    #[display("product unavailable")]
+    ProductUnavailable,
+
+    #[display("download expired")]
+    DownloadExpired,
+
+    #[display("download limit reached")]
+    DownloadLimitReached,
+
+    #[display("unauthorized")]
+    Unauthorized,
+}
+
+impl From<OrderError> for HttpOrderError {
+    fn from(value: OrderError) -> Self {
+        match value {
+            OrderError::NotFound => HttpOrderError::NotFound,
+            OrderError::InvalidData => HttpOrderError::InvalidData,
+            OrderError::DatabaseError => HttpOrderError::InternalError,
+            OrderError::InvalidStatusTransition => HttpOrderError::InvalidStatusTransition,
+            OrderError::OutOfStock => HttpOrderError::OutOfStock,
+            OrderError::ProductUnavailable => HttpOrderError::ProductUnavailable,
+            OrderError::DownloadExpired => HttpOrderError::DownloadExpired,
+            OrderError::DownloadLimitReached => HttpOrderError::DownloadLimitReached,
+        }
+    }
+}
+
+impl From<AuthError> for HttpOrderError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpOrderError::InternalError,
+            _ => HttpOrderError::Unauthorized,
+        }
+    }
+}
+
+impl From<PaginationError> for HttpOrderError {
+    fn from(_: PaginationError) -> Self {
+        HttpOrderError::InvalidData
+    }
+}
+
+impl ResponseError for HttpOrderError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            HttpOrderError::InternalError => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+            HttpOrderError::NotFound => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpOrderError::InvalidData => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpOrderError::InvalidStatusTransition => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpOrderError::OutOfStock => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpOrderError::ProductUnavailable => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpOrderError::DownloadExpired => actix_web::http::StatusCode::GONE,
+            HttpOrderError::DownloadLimitReached => actix_web::http::StatusCode::FORBIDDEN,
+            HttpOrderError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .body(
+                serde_json::to_string(&ErrorMessage {
+                    error: self.to_string(),
+                })
+                .unwrap(),
+            )
+    }
+}
@@ -1,7 +1,10 @@
 use super::ErrorMessage;
 use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
 use derive_more::derive::{Display, Error};
-use ecommercers::core::models::product::ProductError;
+use ecommercers::core::models::{
+    auth::AuthError, export::ExportError, import::ImportError, pagination::PaginationError,
+    product::ProductError, view::ViewError,
+};
 
 #[derive(Debug, Display, Error)]
 pub enum HttpProductError {
@@ -19,6 +22,36 @@ pub enum HttpProductError {
 
     #[display("permission denied")]
     PermissionDenied,
+
+    #[display("unauthorized")]
+    Unauthorized,
+
+    #[display("out of stock")]
+    OutOfStock,
+
+    #[display("variation option does not belong to the product category")]
+    InvalidVariationOption,
+
+    #[display("variation option used by product variants")]
+    VariationOptionInUse,
+
+    #[display("invalid variant")]
+    InvalidVariant,
+
+    #[display("attribute does not apply or does not accept the value")]
+    InvalidAttribute,
+
+    #[display("bundles cannot be nested or have variants")]
+    InvalidBundle,
+
+    #[display("unsupported or too large image")]
+    InvalidImage,
+
+    #[display("slug already taken")]
+    SlugTaken,
+
+    #[display("sku already taken")]
+    SkuTaken,
 }
 
 impl From<ProductError> for HttpProductError {
@@ -29,10 +62,61 @@ impl From<ProductError> for HttpProductError {
             ProductError::InvalidData => HttpProductError::InvalidData,
             ProductError::PermissionDenied => HttpProductError::PermissionDenied,
             ProductError::InvalidCategory => HttpProductError::InvalidCategory,
+            ProductError::OutOfStock => HttpProductError::OutOfStock,
+            ProductError::InvalidVariationOption => HttpProductError::InvalidVariationOption,
+            ProductError::VariationOptionInUse => HttpProductError::VariationOptionInUse,
+            ProductError::InvalidVariant => HttpProductError::InvalidVariant,
+            ProductError::InvalidAttribute => HttpProductError::InvalidAttribute,
+            ProductError::InvalidBundle => HttpProductError::InvalidBundle,
+            ProductError::InvalidImage => HttpProductError::InvalidImage,
+            ProductError::SlugTaken => HttpProductError::SlugTaken,
+            ProductError::SkuTaken => HttpProductError::SkuTaken,
+        }
+    }
+}
+
+impl From<AuthError> for HttpProductError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpProductError::InternalError,
+            AuthError::PermissionDenied => HttpProductError::PermissionDenied,
+            _ => HttpProductError::Unauthorized,
         }
     }
 }
 
+impl From<ImportError> for HttpProductError {
+    fn from(value: ImportError) -> Self {
+        match value {
+            ImportError::InternalError => HttpProductError::InternalError,
+            ImportError::InvalidFile => HttpProductError::InvalidData,
+        }
+    }
+}
+
+impl From<ExportError> for HttpProductError {
+    fn from(value: ExportError) -> Self {
+        match value {
+            ExportError::InternalError => HttpProductError::InternalError,
+        }
+    }
+}
+
+impl From<ViewError> for HttpProductError {
+    fn from(value: ViewError) -> Self {
+        match value {
+            ViewError::InvalidData => HttpProductError::InvalidData,
+            ViewError::InternalError => HttpProductError::InternalError,
+        }
+    }
+}
+
+impl From<PaginationError> for HttpProductError {
+    fn from(_: PaginationError) -> Self {
+        HttpProductError::InvalidData
+    }
+}
+
 impl ResponseError for HttpProductError {
     fn status_code(&self) -> actix_web::http::StatusCode {
         match self {
@@ -40,7 +124,17 @@ impl ResponseError for HttpProductError {
             HttpProductError::NotFound => actix_web::http::StatusCode::BAD_REQUEST,
             HttpProductError::InvalidData => actix_web::http::StatusCode::BAD_REQUEST,
             HttpProductError::InvalidCategory => actix_web::http::StatusCode::BAD_REQUEST,
-            HttpProductError::PermissionDenied => actix_web::http::StatusCode::UNAUTHORIZED,
+            HttpProductError::PermissionDenied => actix_web::http::StatusCode::FORBIDDEN,
+            HttpProductError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+            HttpProductError::OutOfStock => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::InvalidVariationOption => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::VariationOptionInUse => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::InvalidVariant => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::InvalidAttribute => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::InvalidBundle => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::InvalidImage => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::SlugTaken => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpProductError::SkuTaken => actix_web::http::StatusCode::BAD_REQUEST,
         }
     }
 
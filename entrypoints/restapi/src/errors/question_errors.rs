@@ -0,0 +1,73 @@
+use super::ErrorMessage;
+use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
+use derive_more::derive::{Display, Error};
+use ecommercers::core::models::{
+    auth::AuthError, pagination::PaginationError, question::QuestionError,
+};
+
+#[derive(Debug, Display, Error)]
+pub enum HttpQuestionError {
+    #[display("internal error")]
+    InternalError,
+
+    #[display("question not found")]
+    NotFound,
+
+    #[display("invalid data")]
+    InvalidData,
+
+    #[display("permission denied")]
+    PermissionDenied,
+
+    #[display("unauthorized")]
+    Unauthorized,
+}
+
+impl From<QuestionError> for HttpQuestionError {
+    fn from(value: QuestionError) -> Self {
+        match value {
+            QuestionError::NotFound => HttpQuestionError::NotFound,
+            QuestionError::InvalidData => HttpQuestionError::InvalidData,
+            QuestionError::DatabaseError => HttpQuestionError::InternalError,
+            QuestionError::PermissionDenied => HttpQuestionError::PermissionDenied,
+        }
+    }
+}
+
+impl From<AuthError> for HttpQuestionError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpQuestionError::InternalError,
+            _ => HttpQuestionError::Unauthorized,
+        }
+    }
+}
+
+impl From<PaginationError> for HttpQuestionError {
+    fn from(_: PaginationError) -> Self {
+        HttpQuestionError::InvalidData
+    }
+}
+
+impl ResponseError for HttpQuestionError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            HttpQuestionError::InternalError => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+            HttpQuestionError::NotFound => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpQuestionError::InvalidData => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpQuestionError::PermissionDenied => actix_web::http::StatusCode::FORBIDDEN,
+            HttpQuestionError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .body(
+                serde_json::to_string(&ErrorMessage {
+                    error: self.to_string(),
+                })
+                .unwrap(),
+            )
+    }
+}
@@ -0,0 +1,52 @@
+use super::ErrorMessage;
+use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
+use derive_more::derive::{Display, Error};
+use ecommercers::core::models::{auth::AuthError, recommendation::RecommendationError};
+
+#[derive(Debug, Display, Error)]
+pub enum HttpRecommendationError {
+    #[display("internal error")]
+    InternalError,
+
+    #[display("unauthorized")]
+    Unauthorized,
+}
+
+impl From<RecommendationError> for HttpRecommendationError {
+    fn from(value: RecommendationError) -> Self {
+        match value {
+            RecommendationError::DatabaseError => HttpRecommendationError::InternalError,
+        }
+    }
+}
+
+impl From<AuthError> for HttpRecommendationError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpRecommendationError::InternalError,
+            _ => HttpRecommendationError::Unauthorized,
+        }
+    }
+}
+
+impl ResponseError for HttpRecommendationError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            HttpRecommendationError::InternalError => {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            }
+            HttpRecommendationError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .body(
+                serde_json::to_string(&ErrorMessage {
+                    error: self.to_string(),
+                })
+                .unwrap(),
+            )
+    }
+}
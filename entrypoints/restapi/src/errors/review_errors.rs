@@ -0,0 +1,83 @@
+use super::ErrorMessage;
+use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
+use derive_more::derive::{Display, Error};
+use ecommercers::core::models::{
+    auth::AuthError, pagination::PaginationError, review::ReviewError,
+};
+
+#[derive(Debug, Display, Error)]
+pub enum HttpReviewError {
+    #[display("internal error")]
+    InternalError,
+
+    #[display("review not found")]
+    NotFound,
+
+    #[display("invalid data")]
+    InvalidData,
+
+    #[display("product not purchased")]
+    NotPurchased,
+
+    #[display("product already reviewed")]
+    AlreadyReviewed,
+
+    #[display("review already voted")]
+    AlreadyVoted,
+
+    #[display("unauthorized")]
+    Unauthorized,
+}
+
+impl From<ReviewError> for HttpReviewError {
+    fn from(value: ReviewError) -> Self {
+        match value {
+            ReviewError::NotFound => HttpReviewError::NotFound,
+            ReviewError::InvalidData => HttpReviewError::InvalidData,
+            ReviewError::DatabaseError => HttpReviewError::InternalError,
+            ReviewError::NotPurchased => HttpReviewError::NotPurchased,
+            ReviewError::AlreadyReviewed => HttpReviewError::AlreadyReviewed,
+            ReviewError::AlreadyVoted => HttpReviewError::AlreadyVoted,
+        }
+    }
+}
+
+impl From<AuthError> for HttpReviewError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpReviewError::InternalError,
+            _ => HttpReviewError::Unauthorized,
+        }
+    }
+}
+
+impl From<PaginationError> for HttpReviewError {
+    fn from(_: PaginationError) -> Self {
+        HttpReviewError::InvalidData
+    }
+}
+
+impl ResponseError for HttpReviewError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            HttpReviewError::InternalError => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+            HttpReviewError::NotFound => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpReviewError::InvalidData => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpReviewError::NotPurchased => actix_web::http::StatusCode::FORBIDDEN,
+            HttpReviewError::AlreadyReviewed => actix_web::http::StatusCode::CONFLICT,
+            HttpReviewError::AlreadyVoted => actix_web::http::StatusCode::CONFLICT,
+            HttpReviewError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .body(
+                serde_json::to_string(&ErrorMessage {
+                    error: self.to_string(),
+                })
+                .unwrap(),
+            )
+    }
+}
@@ -0,0 +1,76 @@
+use super::ErrorMessage;
+use actix_web::{HttpResponse, ResponseError, http::header::ContentType};
+use derive_more::derive::{Display, Error};
+use ecommercers::core::models::{auth::AuthError, subscription::SubscriptionError};
+
+#[derive(Debug, Display, Error)]
+pub enum HttpSubscriptionError {
+    #[display("internal error")]
+    InternalError,
+
+    #[display("subscription not found")]
+    NotFound,
+
+    #[display("invalid data")]
+    InvalidData,
+
+    #[display("invalid status transition")]
+    InvalidStatusTransition,
+
+    #[display("product unavailable")]
+    ProductUnavailable,
+
+    #[display("unauthorized")]
+    Unauthorized,
+}
+
+impl From<SubscriptionError> for HttpSubscriptionError {
+    fn from(value: SubscriptionError) -> Self {
+        match value {
+            SubscriptionError::NotFound => HttpSubscriptionError::NotFound,
+            SubscriptionError::InvalidData => HttpSubscriptionError::InvalidData,
+            SubscriptionError::InvalidStatusTransition => {
+                HttpSubscriptionError::InvalidStatusTransition
+            }
+            SubscriptionError::ProductUnavailable => HttpSubscriptionError::ProductUnavailable,
+            SubscriptionError::InternalError => HttpSubscriptionError::InternalError,
+        }
+    }
+}
+
+impl From<AuthError> for HttpSubscriptionError {
+    fn from(value: AuthError) -> Self {
+        match value {
+            AuthError::InternalError => HttpSubscriptionError::InternalError,
+            _ => HttpSubscriptionError::Unauthorized,
+        }
+    }
+}
+
+impl ResponseError for HttpSubscriptionError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        match self {
+            HttpSubscriptionError::InternalError => {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            }
+            HttpSubscriptionError::NotFound => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpSubscriptionError::InvalidData => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpSubscriptionError::InvalidStatusTransition => {
+                actix_web::http::StatusCode::BAD_REQUEST
+            }
+            HttpSubscriptionError::ProductUnavailable => actix_web::http::StatusCode::BAD_REQUEST,
+            HttpSubscriptionError::Unauthorized => actix_web::http::StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse<actix_web::body::BoxBody> {
+        HttpResponse::build(self.status_code())
+            .insert_header(ContentType::json())
+            .body(
+                serde_json::to_string(&ErrorMessage {
+                    error: self.to_string(),
+                })
+                .unwrap(),
+            )
+    }
+}
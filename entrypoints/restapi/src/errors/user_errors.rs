@@ -22,6 +22,9 @@ pub enum HttpAuthError {
 
     #[display("email not verified")]
     EmailNotVerified,
+
+    #[display("permission denied")]
+    PermissionDenied,
 }
 
 impl From<AuthError> for HttpAuthError {
@@ -33,6 +36,7 @@ impl From<AuthError> for HttpAuthError {
             AuthError::TokenExpired => HttpAuthError::TokenExpired,
             AuthError::EmailAlreadyExists => HttpAuthError::EmailAlreadyExists,
             AuthError::EmailNotVerified => HttpAuthError::EmailNotVerified,
+            AuthError::PermissionDenied => HttpAuthError::PermissionDenied,
         }
     }
 }
@@ -46,6 +50,7 @@ impl ResponseError for HttpAuthError {
             HttpAuthError::TokenExpired => actix_web::http::StatusCode::UNAUTHORIZED,
             HttpAuthError::EmailAlreadyExists => actix_web::http::StatusCode::BAD_REQUEST,
             HttpAuthError::EmailNotVerified => actix_web::http::StatusCode::UNAUTHORIZED,
+            HttpAuthError::PermissionDenied => actix_web::http::StatusCode::FORBIDDEN,
         }
     }
 
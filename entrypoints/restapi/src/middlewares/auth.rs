@@ -0,0 +1,66 @@
+use actix_web::{HttpRequest, web};
+use ecommercers::core::{
+    models::{
+        auth::AuthError,
+        user::{User, UserRole},
+        view::Viewer,
+    },
+    services::user_service::UserService,
+};
+use std::sync::{Arc, Mutex};
+
+/// Resolves the user behind the `Authorization: Bearer <access_token>` header.
+pub fn authenticate(
+    req: &HttpRequest,
+    user_service_guard: &web::Data<Arc<Mutex<UserService>>>,
+) -> Result<User, AuthError> {
+    let auth_str = req
+        .headers()
+        .get("Authorization")
+        .ok_or(AuthError::InvalidCredentials)?
+        .to_str()
+        .map_err(|_| AuthError::InvalidPayload)?;
+
+    let access_token = auth_str
+        .strip_prefix("Bearer ")
+        .ok_or(AuthError::InvalidPayload)?
+        .to_string();
+
+    let mut user_service = user_service_guard.lock().unwrap();
+    user_service.authorization(access_token)
+}
+
+/// Same as [`authenticate`] but additionally requires one of `roles`.
+pub fn authorize(
+    req: &HttpRequest,
+    user_service_guard: &web::Data<Arc<Mutex<UserService>>>,
+    roles: &[UserRole],
+) -> Result<User, AuthError> {
+    let user = authenticate(req, user_service_guard)?;
+    if !roles.contains(&user.user_role) {
+        return Err(AuthError::PermissionDenied);
+    }
+    Ok(user)
+}
+
+/// Header anonymous visitors send a token of their own in, to be told apart
+/// e.g. for their recently viewed products.
+pub const ANONYMOUS_TOKEN_HEADER: &str = "X-Anonymous-Token";
+
+/// Identifies who sent the request: the user behind a valid access token,
+/// otherwise the visitor behind a well-formed anonymous token, if any.
+pub fn identify_viewer(
+    req: &HttpRequest,
+    user_service_guard: &web::Data<Arc<Mutex<UserService>>>,
+) -> Option<Viewer> {
+    if let Ok(user) = authenticate(req, user_service_guard) {
+        return Some(Viewer::User(user.id));
+    }
+    req.headers()
+        .get(ANONYMOUS_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|token| Viewer::anonymous(token).ok())
+}
+
+/// Roles allowed to manage the catalog.
+pub const MANAGER_ROLES: &[UserRole] = &[UserRole::Manager, UserRole::Admin];
@@ -1,30 +1,146 @@
-use actix_web::{App, HttpServer, web};
+use actix_web::{App, HttpServer, rt, web};
 use controllers::{
-    category_controller::new_category_controller, product_controller::new_product_controller,
+    cart_controller::new_cart_controller, category_controller::new_category_controller,
+    download_controller::new_download_controller, media_controller::new_media_controller,
+    order_controller::new_order_controller, product_controller::new_product_controller,
+    question_controller::new_question_controller,
+    recommendation_controller::new_recommendation_controller,
+    review_controller::new_review_controller, subscription_controller::new_subscription_controller,
     user_controller::new_user_controller,
 };
+use ecommercers::core::services::{
+    product_service::ProductService, recommendation_service::RecommendationService,
+    subscription_service::SubscriptionService, view_service::ViewService,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 mod controllers;
 mod dto;
 mod errors;
 mod middlewares;
 
+/// Applies the publication schedules of products every `interval_seconds`.
+fn spawn_scheduler(product_service: Arc<Mutex<ProductService>>, interval_seconds: u64) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            let mut product_service = product_service.lock().unwrap();
+            if let Err(err) = product_service.apply_schedules() {
+                eprintln!("Failed to apply product schedules: {:?}", err);
+            }
+        }
+    });
+}
+
+/// Computes the related products again every `interval_seconds`.
+fn spawn_recommender(
+    recommendation_service: Arc<Mutex<RecommendationService>>,
+    interval_seconds: u64,
+) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            let mut recommendation_service = recommendation_service.lock().unwrap();
+            if let Err(err) = recommendation_service.refresh() {
+                eprintln!("Failed to compute product recommendations: {:?}", err);
+            }
+        }
+    });
+}
+
+/// Saves the product view counts kept in Redis every `interval_seconds`.
+fn spawn_view_flusher(view_service: Arc<Mutex<ViewService>>, interval_seconds: u64) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            let mut view_service = view_service.lock().unwrap();
+            if let Err(err) = view_service.flush() {
+                eprintln!("Failed to flush product views: {:?}", err);
+            }
+        }
+    });
+}
+
+/// Places the orders of the subscriptions due every `interval_seconds`.
+fn spawn_subscription_runner(
+    subscription_service: Arc<Mutex<SubscriptionService>>,
+    interval_seconds: u64,
+) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(Duration::from_secs(interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            let mut subscription_service = subscription_service.lock().unwrap();
+            if let Err(err) = subscription_service.run_due() {
+                eprintln!("Failed to run subscriptions: {:?}", err);
+            }
+        }
+    });
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     let services = ecommercers::bootstrap::bootstrap_services();
-    let cfg = services.cfg.lock().unwrap();
-
-    let endpoint_addr = format!("{}:{}", cfg.server.host, cfg.server.port);
+    let (
+        endpoint_addr,
+        scheduler_interval,
+        recommendations_interval,
+        views_interval,
+        subscriptions_interval,
+    ) = {
+        let cfg = services.cfg.lock().unwrap();
+        (
+            format!("{}:{}", cfg.server.host, cfg.server.port),
+            cfg.scheduler.interval_seconds,
+            cfg.recommendations.interval_seconds,
+            cfg.views.flush_interval_seconds,
+            cfg.subscriptions.interval_seconds,
+        )
+    };
     println!("# RestAPI Endpoint: {}", endpoint_addr.clone());
+    spawn_scheduler(services.product_service.clone(), scheduler_interval);
+    spawn_recommender(
+        services.recommendation_service.clone(),
+        recommendations_interval,
+    );
+    spawn_view_flusher(services.view_service.clone(), views_interval);
+    spawn_subscription_runner(
+        services.subscription_service.clone(),
+        subscriptions_interval,
+    );
 
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(services.user_service.clone()))
             .app_data(web::Data::new(services.product_service.clone()))
             .app_data(web::Data::new(services.category_service.clone()))
+            .app_data(web::Data::new(services.cart_service.clone()))
+            .app_data(web::Data::new(services.order_service.clone()))
+            .app_data(web::Data::new(services.import_service.clone()))
+            .app_data(web::Data::new(services.export_service.clone()))
+            .app_data(web::Data::new(services.review_service.clone()))
+            .app_data(web::Data::new(services.question_service.clone()))
+            .app_data(web::Data::new(services.recommendation_service.clone()))
+            .app_data(web::Data::new(services.view_service.clone()))
+            .app_data(web::Data::new(services.download_service.clone()))
+            .app_data(web::Data::new(services.subscription_service.clone()))
             .service(new_user_controller())
             .service(new_product_controller())
             .service(new_category_controller())
+            .service(new_cart_controller())
+            .service(new_order_controller())
+            .service(new_media_controller())
+            .service(new_download_controller())
+            .service(new_review_controller())
+            .service(new_question_controller())
+            .service(new_recommendation_controller())
+            .service(new_subscription_controller())
     })
     .bind(endpoint_addr)
     .unwrap()
@@ -0,0 +1,81 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::core::ports::blob_storage::{BlobStorage, BlobStorageError};
+
+/// Stores blobs as files under `root`; they are served back through `public_url`.
+pub struct LocalBlobStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+impl LocalBlobStorage {
+    pub fn new(root: String, public_url: String) -> Self {
+        LocalBlobStorage {
+            root: PathBuf::from(root),
+            public_url,
+        }
+    }
+
+    /// Resolves `key` under the storage root, rejecting keys that would escape it.
+    fn path(&self, key: &str) -> Result<PathBuf, BlobStorageError> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_safe {
+            return Err(BlobStorageError::InvalidKey);
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+impl BlobStorage for LocalBlobStorage {
+    fn put(
+        &mut self,
+        key: &str,
+        _content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<String, BlobStorageError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|_| BlobStorageError::InternalError)?;
+        }
+        fs::write(&path, data).map_err(|_| BlobStorageError::InternalError)?;
+        Ok(self.url(key))
+    }
+
+    fn get(&mut self, key: &str) -> Result<Vec<u8>, BlobStorageError> {
+        let path = self.path(key)?;
+        fs::read(path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => BlobStorageError::NotFound,
+            _ => BlobStorageError::InternalError,
+        })
+    }
+
+    fn open(&mut self, key: &str) -> Result<Box<dyn Read + Send>, BlobStorageError> {
+        let path = self.path(key)?;
+        let file = File::open(path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => BlobStorageError::NotFound,
+            _ => BlobStorageError::InternalError,
+        })?;
+        Ok(Box::new(file))
+    }
+
+    fn delete(&mut self, key: &str) -> Result<(), BlobStorageError> {
+        let path = self.path(key)?;
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(_) => Err(BlobStorageError::InternalError),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.public_url.trim_end_matches('/'), key)
+    }
+}
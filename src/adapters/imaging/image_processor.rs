@@ -0,0 +1,121 @@
+use std::{cell::OnceCell, io::Cursor};
+
+use image::{DynamicImage, ImageFormat, ImageReader, imageops::FilterType};
+
+use crate::core::{
+    models::product::ImageSize,
+    ports::image_processor::{ImageInfo, ImageProcessor, ImageProcessorError, RenderedImage},
+};
+
+/// Pure-Rust image processing backed by the `image` crate.
+pub struct ImageProcessorImpl;
+
+impl ImageProcessorImpl {
+    pub fn new() -> Self {
+        ImageProcessorImpl
+    }
+
+    fn reader(data: &[u8]) -> Result<ImageReader<Cursor<&[u8]>>, ImageProcessorError> {
+        let reader = ImageReader::new(Cursor::new(data))
+            .with_guessed_format()
+            .map_err(|_| ImageProcessorError::InvalidImage)?;
+
+        match reader.format() {
+            Some(ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP | ImageFormat::Gif) => {
+                Ok(reader)
+            }
+            _ => Err(ImageProcessorError::UnsupportedFormat),
+        }
+    }
+
+    fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, ImageProcessorError> {
+        // Not every encoder accepts every pixel layout (JPEG has no alpha,
+        // WebP and GIF want 8-bit channels).
+        let image = match format {
+            ImageFormat::Jpeg => DynamicImage::ImageRgb8(image.to_rgb8()),
+            _ => DynamicImage::ImageRgba8(image.to_rgba8()),
+        };
+
+        let mut data = Cursor::new(Vec::new());
+        image
+            .write_to(&mut data, format)
+            .map_err(|_| ImageProcessorError::InternalError)?;
+        Ok(data.into_inner())
+    }
+}
+
+impl Default for ImageProcessorImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageProcessor for ImageProcessorImpl {
+    fn inspect(&self, data: &[u8]) -> Result<ImageInfo, ImageProcessorError> {
+        let reader = Self::reader(data)?;
+        let format = reader
+            .format()
+            .ok_or(ImageProcessorError::UnsupportedFormat)?;
+        let (width, height) = reader
+            .into_dimensions()
+            .map_err(|_| ImageProcessorError::InvalidImage)?;
+
+        Ok(ImageInfo {
+            content_type: format.to_mime_type().to_string(),
+            width,
+            height,
+        })
+    }
+
+    fn render(
+        &self,
+        data: &[u8],
+        sizes: &[ImageSize],
+    ) -> Result<Vec<RenderedImage>, ImageProcessorError> {
+        let reader = Self::reader(data)?;
+        let format = reader
+            .format()
+            .ok_or(ImageProcessorError::UnsupportedFormat)?;
+        let image = reader
+            .decode()
+            .map_err(|_| ImageProcessorError::InvalidImage)?;
+
+        // Every size the image already fits in shares the full-size WebP.
+        let full_size_webp: OnceCell<Vec<u8>> = OnceCell::new();
+
+        sizes
+            .iter()
+            .map(|size| {
+                let resized = size
+                    .max_dimension()
+                    .filter(|max| image.width() > *max || image.height() > *max)
+                    .map(|max| image.resize(max, max, FilterType::CatmullRom));
+
+                let (rendered, data, webp_data) = match &resized {
+                    Some(resized) => (
+                        resized,
+                        Self::encode(resized, format)?,
+                        Self::encode(resized, ImageFormat::WebP)?,
+                    ),
+                    None => {
+                        let webp_data = match full_size_webp.get() {
+                            Some(webp_data) => webp_data.clone(),
+                            None if format == ImageFormat::WebP => data.to_vec(),
+                            None => Self::encode(&image, ImageFormat::WebP)?,
+                        };
+                        let _ = full_size_webp.set(webp_data.clone());
+                        (&image, data.to_vec(), webp_data)
+                    }
+                };
+
+                Ok(RenderedImage {
+                    size: *size,
+                    width: rendered.width(),
+                    height: rendered.height(),
+                    data,
+                    webp_data,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1 @@
+pub mod image_processor;
\ No newline at end of file
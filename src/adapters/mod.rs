@@ -1,2 +1,6 @@
 pub mod postgres;
-pub mod redis;
\ No newline at end of file
+pub mod redis;
+pub mod filesystem;
+pub mod s3;
+pub mod imaging;
+pub mod tantivy;
\ No newline at end of file
@@ -0,0 +1,94 @@
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::attribute::{Attribute, AttributeType, AttributeValue, ProductAttribute},
+};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, prelude::*};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Selectable, Queryable, QueryableByName)]
+#[diesel(table_name = attributes)]
+#[diesel(check_for_backend(Pg))]
+pub struct AttributeEntity {
+    pub id: i64,
+    pub category_id: i64,
+    pub name: String,
+    pub data_type: String,
+    pub unit: Option<String>,
+    pub is_filterable: bool,
+    pub options: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl AttributeEntity {
+    pub fn to_model(&self) -> Attribute {
+        Attribute {
+            id: self.id,
+            category_id: self.category_id,
+            name: self.name.clone(),
+            data_type: AttributeType::from_str(&self.data_type).unwrap_or_default(),
+            unit: self.unit.clone(),
+            is_filterable: self.is_filterable,
+            options: self.options.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = attributes)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewAttributeEntity {
+    pub category_id: i64,
+    pub name: String,
+    pub data_type: String,
+    pub unit: Option<String>,
+    pub is_filterable: bool,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable, Insertable)]
+#[diesel(table_name = product_attribute_values)]
+#[diesel(check_for_backend(Pg))]
+pub struct ProductAttributeValueEntity {
+    pub product_id: i64,
+    pub attribute_id: i64,
+    pub text_value: Option<String>,
+    pub number_value: Option<f64>,
+    pub bool_value: Option<bool>,
+}
+
+impl ProductAttributeValueEntity {
+    pub fn new(product_id: i64, attribute_id: i64, value: AttributeValue) -> Self {
+        let mut entity = ProductAttributeValueEntity {
+            product_id,
+            attribute_id,
+            text_value: None,
+            number_value: None,
+            bool_value: None,
+        };
+        match value {
+            AttributeValue::Text(text) => entity.text_value = Some(text),
+            AttributeValue::Number(number) => entity.number_value = Some(number),
+            AttributeValue::Bool(flag) => entity.bool_value = Some(flag),
+        }
+        entity
+    }
+
+    pub fn to_model(&self, attribute: &AttributeEntity) -> ProductAttribute {
+        let value = match (&self.text_value, self.number_value, self.bool_value) {
+            (_, Some(number), _) => AttributeValue::Number(number),
+            (_, _, Some(flag)) => AttributeValue::Bool(flag),
+            (text, _, _) => AttributeValue::Text(text.clone().unwrap_or_default()),
+        };
+        ProductAttribute {
+            attribute_id: attribute.id,
+            name: attribute.name.clone(),
+            data_type: AttributeType::from_str(&attribute.data_type).unwrap_or_default(),
+            unit: attribute.unit.clone(),
+            value,
+        }
+    }
+}
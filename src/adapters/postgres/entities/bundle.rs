@@ -0,0 +1,46 @@
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::bundle::{Bundle, BundleItem},
+};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, prelude::*};
+
+#[derive(Debug, Clone, Selectable, Queryable, Insertable)]
+#[diesel(table_name = product_bundles)]
+#[diesel(check_for_backend(Pg))]
+pub struct BundleEntity {
+    pub product_id: i64,
+    pub discount_percent: Option<f64>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl BundleEntity {
+    pub fn to_model(&self, items: Vec<BundleItem>) -> Bundle {
+        Bundle {
+            product_id: self.product_id,
+            discount_percent: self.discount_percent,
+            items,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Selectable, Queryable, Insertable)]
+#[diesel(table_name = product_bundle_items)]
+#[diesel(check_for_backend(Pg))]
+pub struct BundleItemEntity {
+    pub bundle_id: i64,
+    pub component_id: i64,
+    pub quantity: i32,
+}
+
+impl BundleItemEntity {
+    pub fn to_model(&self) -> BundleItem {
+        BundleItem {
+            component_id: self.component_id,
+            quantity: self.quantity,
+        }
+    }
+}
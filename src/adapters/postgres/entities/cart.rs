@@ -1,6 +1,10 @@
-use crate::adapters::postgres::schema::*;
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::cart::{Cart, CartItem},
+};
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use serde_json::Value;
 
 #[derive(Debug, Queryable, Insertable)]
 #[diesel(table_name = cart_items)]
@@ -12,6 +16,25 @@ pub struct CartItemEntity {
     pub quantity: i32,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub customizations: Value,
+    pub customization_price: f64,
+    pub variant_id: Option<i64>,
+}
+
+impl CartItemEntity {
+    pub fn to_model(&self) -> CartItem {
+        CartItem {
+            id: self.id,
+            cart_id: self.cart_id,
+            product_id: self.product_id,
+            variant_id: self.variant_id,
+            quantity: self.quantity,
+            customizations: self.customizations.clone(),
+            customization_price: self.customization_price,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
 }
 
 #[derive(Debug, Insertable, AsChangeset)]
@@ -20,6 +43,9 @@ pub struct NewCartItemEntity {
     pub cart_id: i64,
     pub product_id: i64,
     pub quantity: i32,
+    pub customizations: Value,
+    pub customization_price: f64,
+    pub variant_id: Option<i64>,
 }
 
 #[derive(Debug, Queryable, Insertable)]
@@ -30,10 +56,27 @@ pub struct CartEntity {
     pub user_id: i64,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub name: String,
+    pub is_active: bool,
+}
+
+impl CartEntity {
+    pub fn to_model(&self) -> Cart {
+        Cart {
+            id: self.id,
+            user_id: self.user_id,
+            name: self.name.clone(),
+            is_active: self.is_active,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
 }
 
 #[derive(Debug, Insertable, AsChangeset)]
 #[diesel(table_name = carts)]
 pub struct NewCartEntity {
     pub user_id: i64,
-}
\ No newline at end of file
+    pub name: String,
+    pub is_active: bool,
+}
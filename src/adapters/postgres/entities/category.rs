@@ -1,4 +1,7 @@
-use crate::{adapters::postgres::schema::*, core::models::category::Category};
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::{category::Category, seo::SeoMetadata},
+};
 use chrono::NaiveDateTime;
 use diesel::{pg::Pg, prelude::*};
 
@@ -12,6 +15,11 @@ pub struct CategoryEntity {
     pub parent_id: Option<i64>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub slug: String,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl CategoryEntity {
@@ -19,10 +27,17 @@ impl CategoryEntity {
         Category {
             id: self.id,
             name: self.name.clone(),
+            slug: self.slug.clone(),
             description: self.description.clone(),
             parent,
+            seo: SeoMetadata {
+                meta_title: self.meta_title.clone(),
+                meta_description: self.meta_description.clone(),
+                canonical_url: self.canonical_url.clone(),
+            },
             created_at: self.created_at,
             updated_at: self.updated_at,
+            deleted_at: self.deleted_at,
         }
     }
 }
@@ -32,6 +47,14 @@ impl CategoryEntity {
 #[diesel(check_for_backend(Pg))]
 pub struct NewCategoryEntity {
     pub name: String,
+    pub slug: String,
     pub description: String,
     pub parent_id: Option<i64>,
 }
+
+#[derive(Debug, QueryableByName)]
+#[diesel(check_for_backend(Pg))]
+pub struct CategoryIdEntity {
+    #[diesel(sql_type = diesel::sql_types::Int8)]
+    pub id: i64,
+}
@@ -0,0 +1,70 @@
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::download::{DownloadGrant, ProductFile},
+};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, prelude::*};
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_files)]
+#[diesel(check_for_backend(Pg))]
+pub struct ProductFileEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub storage_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProductFileEntity {
+    pub fn to_model(&self) -> ProductFile {
+        ProductFile {
+            id: self.id,
+            product_id: self.product_id,
+            storage_key: self.storage_key.clone(),
+            file_name: self.file_name.clone(),
+            content_type: self.content_type.clone(),
+            size_bytes: self.size_bytes,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_files)]
+pub struct NewProductFileEntity {
+    pub product_id: i64,
+    pub storage_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable, Insertable)]
+#[diesel(table_name = order_item_downloads)]
+#[diesel(check_for_backend(Pg))]
+pub struct DownloadGrantEntity {
+    pub order_item_id: i64,
+    pub max_downloads: i32,
+    pub download_count: i32,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl DownloadGrantEntity {
+    pub fn to_model(&self) -> DownloadGrant {
+        DownloadGrant {
+            order_item_id: self.order_item_id,
+            max_downloads: self.max_downloads,
+            download_count: self.download_count,
+            expires_at: self.expires_at,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
@@ -2,4 +2,12 @@ pub mod cart;
 pub mod order;
 pub mod category;
 pub mod product;
-pub mod user;
\ No newline at end of file
+pub mod user;
+pub mod product_category;
+pub mod review;
+pub mod question;
+pub mod recommendation;
+pub mod attribute;
+pub mod bundle;
+pub mod download;
+pub mod subscription;
\ No newline at end of file
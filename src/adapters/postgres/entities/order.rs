@@ -1,6 +1,12 @@
+use std::str::FromStr;
+
 use diesel::{prelude::*};
 use chrono::NaiveDateTime;
-use crate::adapters::postgres::schema::*;
+use serde_json::Value;
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::order::{Order, OrderItem, OrderStatus},
+};
 
 #[derive(Debug, Queryable, Insertable)]
 #[diesel(table_name = order_items)]
@@ -13,6 +19,35 @@ pub struct OrderItemEntity {
     pub price_at_time_of_order: f64,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub customizations: Value,
+    pub variant_id: Option<i64>,
+}
+
+impl OrderItemEntity {
+    pub fn to_model(&self) -> OrderItem {
+        OrderItem {
+            id: self.id,
+            order_id: self.order_id,
+            product_id: self.product_id,
+            variant_id: self.variant_id,
+            quantity: self.quantity,
+            price_at_time_of_order: self.price_at_time_of_order,
+            customizations: self.customizations.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = order_items)]
+pub struct NewOrderItemEntity {
+    pub order_id: i64,
+    pub product_id: i64,
+    pub quantity: i32,
+    pub price_at_time_of_order: f64,
+    pub customizations: Value,
+    pub variant_id: Option<i64>,
 }
 
 #[derive(Debug, Queryable, Insertable)]
@@ -25,4 +60,30 @@ pub struct OrderEntity {
     pub status: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
-}
\ No newline at end of file
+    pub requires_shipping: bool,
+    pub paid_at: Option<NaiveDateTime>,
+}
+
+impl OrderEntity {
+    pub fn to_model(&self) -> Order {
+        Order {
+            id: self.id,
+            user_id: self.user_id,
+            total_amount: self.total_amount,
+            status: OrderStatus::from_str(&self.status).unwrap_or(OrderStatus::Error),
+            requires_shipping: self.requires_shipping,
+            paid_at: self.paid_at,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = orders)]
+pub struct NewOrderEntity {
+    pub user_id: i64,
+    pub total_amount: f64,
+    pub status: String,
+    pub requires_shipping: bool,
+}
@@ -1,5 +1,21 @@
-use crate::{adapters::postgres::schema::*, core::models::{category::Category, product::Product}};
-use chrono::NaiveDateTime;
+use std::str::FromStr;
+
+use crate::{
+    adapters::postgres::schema::*,
+    core::{
+        models::{
+            category::Category,
+            customization::{CustomizationField, CustomizationFieldType},
+            product::{
+                Product, ProductImage, ProductStatus, ProductVariant, SearchSuggestion,
+                SuggestionKind, Variation, VariationOption,
+            },
+            seo::SeoMetadata,
+        },
+        ports::search_index::SearchHit,
+    },
+};
+use chrono::{NaiveDate, NaiveDateTime};
 use diesel::{pg::Pg, prelude::*};
 
 #[derive(Debug, Clone, Selectable, Queryable, Insertable)]
@@ -15,6 +31,18 @@ pub struct ProductEntity {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub category_id: Option<i64>,
+    pub slug: String,
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    pub canonical_url: Option<String>,
+    pub status: String,
+    pub publish_at: Option<NaiveDateTime>,
+    pub unpublish_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub sku: Option<String>,
+    pub rating_average: f64,
+    pub rating_count: i64,
+    pub is_digital: bool,
 }
 
 #[derive(Debug, Insertable, AsChangeset)]
@@ -27,20 +55,117 @@ pub struct NewProductEntity {
     pub stock: i32,
     pub product_image: Option<String>,
     pub category_id: Option<i64>,
+    pub slug: String,
+    pub status: String,
+    pub publish_at: Option<NaiveDateTime>,
+    pub unpublish_at: Option<NaiveDateTime>,
+    pub sku: Option<String>,
+}
+
+/// Columns an import row overwrites on an existing product; unset fields are
+/// left unchanged.
+#[derive(Debug, AsChangeset)]
+#[diesel(table_name = products)]
+#[diesel(check_for_backend(Pg))]
+pub struct ProductImportEntity {
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    pub stock: Option<i32>,
+    pub category_id: Option<i64>,
+    pub status: Option<String>,
+    pub sku: Option<String>,
+}
+
+/// A row of the search queries: the matching product, its rank and a snippet.
+#[derive(Debug, QueryableByName)]
+#[diesel(check_for_backend(Pg))]
+pub struct SearchHitEntity {
+    #[diesel(sql_type = diesel::sql_types::Int8)]
+    pub id: i64,
+    #[diesel(sql_type = diesel::sql_types::Float4)]
+    pub rank: f32,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub snippet: String,
+}
+
+impl SearchHitEntity {
+    pub fn to_model(&self) -> SearchHit {
+        SearchHit {
+            product_id: self.id,
+            rank: self.rank,
+            snippet: self.snippet.clone(),
+        }
+    }
+}
+
+#[derive(Debug, QueryableByName)]
+#[diesel(check_for_backend(Pg))]
+pub struct SearchSuggestionEntity {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub kind: String,
+    #[diesel(sql_type = diesel::sql_types::Int8)]
+    pub id: i64,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub name: String,
+}
+
+impl SearchSuggestionEntity {
+    pub fn to_model(&self) -> SearchSuggestion {
+        SearchSuggestion {
+            kind: if self.kind == "category" {
+                SuggestionKind::Category
+            } else {
+                SuggestionKind::Product
+            },
+            id: self.id,
+            name: self.name.clone(),
+        }
+    }
 }
 
 impl ProductEntity {
-    pub fn to_model(&self, category: Option<Category>) -> Product {
+    pub fn to_model(
+        &self,
+        category: Option<Category>,
+        categories: Vec<Category>,
+        customization_fields: Vec<CustomizationField>,
+    ) -> Product {
         Product {
             id: self.id,
             name: self.name.clone(),
+            slug: self.slug.clone(),
+            sku: self.sku.clone(),
             description: self.description.clone(),
             price: self.price,
             stock: self.stock,
             product_image: self.product_image.clone(),
+            is_digital: self.is_digital,
+            seo: SeoMetadata {
+                meta_title: self.meta_title.clone(),
+                meta_description: self.meta_description.clone(),
+                canonical_url: self.canonical_url.clone(),
+            },
+            status: ProductStatus::from_str(&self.status).unwrap_or_default(),
+            publish_at: self.publish_at,
+            unpublish_at: self.unpublish_at,
             category,
+            categories,
+            customization_fields,
+            attributes: vec![],
+            bundle: None,
+            variations: vec![],
+            variation_options: vec![],
+            variants: vec![],
+            images: vec![],
+            files: vec![],
+            rating_average: self.rating_average,
+            rating_count: self.rating_count,
+            questions: vec![],
+            related_products: vec![],
             created_at: self.created_at,
             updated_at: self.updated_at,
+            deleted_at: self.deleted_at,
         }
     }
 }
@@ -48,17 +173,207 @@ impl ProductEntity {
 #[derive(Debug, Identifiable, Selectable, Queryable, Insertable)]
 #[diesel(table_name = variations)]
 #[diesel(check_for_backend(Pg))]
-pub struct Variation {
+pub struct VariationEntity {
     pub id: i64,
     pub category_id: i64,
     pub name: String,
 }
 
+impl VariationEntity {
+    pub fn to_model(&self) -> Variation {
+        Variation {
+            id: self.id,
+            category_id: self.category_id,
+            name: self.name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = variations)]
+pub struct NewVariationEntity {
+    pub category_id: i64,
+    pub name: String,
+}
+
 #[derive(Debug, Identifiable, Selectable, Queryable, Insertable)]
 #[diesel(table_name = variation_options)]
 #[diesel(check_for_backend(Pg))]
-pub struct VariationOption {
+pub struct VariationOptionEntity {
     pub id: i64,
     pub variation_id: i64,
     pub value: String,
 }
+
+impl VariationOptionEntity {
+    pub fn to_model(&self) -> VariationOption {
+        VariationOption {
+            id: self.id,
+            variation_id: self.variation_id,
+            value: self.value.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = variation_options)]
+pub struct NewVariationOptionEntity {
+    pub variation_id: i64,
+    pub value: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_variation_options)]
+pub struct ProductVariationOptionEntity {
+    pub product_id: i64,
+    pub variation_option_id: i64,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_customization_fields)]
+#[diesel(check_for_backend(Pg))]
+pub struct CustomizationFieldEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub name: String,
+    pub field_type: String,
+    pub required: bool,
+    pub max_length: Option<i32>,
+    pub extra_price: f64,
+}
+
+impl CustomizationFieldEntity {
+    pub fn to_model(&self) -> CustomizationField {
+        CustomizationField {
+            id: self.id,
+            product_id: self.product_id,
+            name: self.name.clone(),
+            field_type: CustomizationFieldType::from_str(&self.field_type)
+                .unwrap_or(CustomizationFieldType::Text),
+            required: self.required,
+            max_length: self.max_length,
+            extra_price: self.extra_price,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_customization_fields)]
+pub struct NewCustomizationFieldEntity {
+    pub product_id: i64,
+    pub name: String,
+    pub field_type: String,
+    pub required: bool,
+    pub max_length: Option<i32>,
+    pub extra_price: f64,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_variants)]
+#[diesel(check_for_backend(Pg))]
+pub struct ProductVariantEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub sku: String,
+    pub price: Option<f64>,
+    pub stock: i32,
+    pub image: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProductVariantEntity {
+    pub fn to_model(&self, options: Vec<VariationOption>) -> ProductVariant {
+        ProductVariant {
+            id: self.id,
+            product_id: self.product_id,
+            sku: self.sku.clone(),
+            price: self.price,
+            stock: self.stock,
+            image: self.image.clone(),
+            options,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable, AsChangeset)]
+#[diesel(table_name = product_variants)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewProductVariantEntity {
+    pub product_id: i64,
+    pub sku: String,
+    pub price: Option<f64>,
+    pub stock: i32,
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_variant_options)]
+pub struct ProductVariantOptionEntity {
+    pub variant_id: i64,
+    pub variation_option_id: i64,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_images)]
+#[diesel(check_for_backend(Pg))]
+pub struct ProductImageEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub storage_key: String,
+    pub url: String,
+    pub content_type: String,
+    pub alt_text: Option<String>,
+    pub position: i32,
+    pub is_primary: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub renditions: serde_json::Value,
+}
+
+impl ProductImageEntity {
+    pub fn to_model(&self) -> ProductImage {
+        ProductImage {
+            id: self.id,
+            product_id: self.product_id,
+            storage_key: self.storage_key.clone(),
+            url: self.url.clone(),
+            content_type: self.content_type.clone(),
+            width: self.width,
+            height: self.height,
+            alt_text: self.alt_text.clone(),
+            position: self.position,
+            is_primary: self.is_primary,
+            renditions: serde_json::from_value(self.renditions.clone()).unwrap_or_default(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_images)]
+pub struct NewProductImageEntity {
+    pub product_id: i64,
+    pub storage_key: String,
+    pub url: String,
+    pub content_type: String,
+    pub alt_text: Option<String>,
+    pub position: i32,
+    pub is_primary: bool,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub renditions: serde_json::Value,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_view_counts)]
+pub struct ProductViewCountEntity {
+    pub product_id: i64,
+    pub viewed_on: NaiveDate,
+    pub views: i64,
+}
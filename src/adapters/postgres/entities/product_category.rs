@@ -0,0 +1,37 @@
+use crate::{adapters::postgres::schema::*, core::models::product_category::ProductCategory};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, prelude::*};
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_categories)]
+#[diesel(check_for_backend(Pg))]
+pub struct ProductCategoryEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub category_id: i64,
+    pub is_primary: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProductCategoryEntity {
+    pub fn to_model(&self) -> ProductCategory {
+        ProductCategory {
+            id: self.id,
+            product_id: self.product_id,
+            category_id: self.category_id,
+            is_primary: self.is_primary,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_categories)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewProductCategoryEntity {
+    pub product_id: i64,
+    pub category_id: i64,
+    pub is_primary: bool,
+}
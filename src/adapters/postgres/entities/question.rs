@@ -0,0 +1,93 @@
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::{
+        moderation::ModerationStatus,
+        question::{ProductAnswer, ProductQuestion},
+    },
+};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, prelude::*};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_questions)]
+#[diesel(check_for_backend(Pg))]
+pub struct QuestionEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub user_id: i64,
+    pub body: String,
+    pub status: String,
+    pub answer_count: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl QuestionEntity {
+    pub fn to_model(&self, answers: Vec<ProductAnswer>) -> ProductQuestion {
+        ProductQuestion {
+            id: self.id,
+            product_id: self.product_id,
+            user_id: self.user_id,
+            body: self.body.clone(),
+            status: ModerationStatus::from_str(&self.status).unwrap_or_default(),
+            answer_count: self.answer_count,
+            answers,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_questions)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewQuestionEntity {
+    pub product_id: i64,
+    pub user_id: i64,
+    pub body: String,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_answers)]
+#[diesel(check_for_backend(Pg))]
+pub struct AnswerEntity {
+    pub id: i64,
+    pub question_id: i64,
+    pub user_id: i64,
+    pub body: String,
+    pub status: String,
+    pub is_staff: bool,
+    pub is_verified_buyer: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl AnswerEntity {
+    pub fn to_model(&self) -> ProductAnswer {
+        ProductAnswer {
+            id: self.id,
+            question_id: self.question_id,
+            user_id: self.user_id,
+            body: self.body.clone(),
+            status: ModerationStatus::from_str(&self.status).unwrap_or_default(),
+            is_staff: self.is_staff,
+            is_verified_buyer: self.is_verified_buyer,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_answers)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewAnswerEntity {
+    pub question_id: i64,
+    pub user_id: i64,
+    pub body: String,
+    pub status: String,
+    pub is_staff: bool,
+    pub is_verified_buyer: bool,
+}
@@ -0,0 +1,23 @@
+use crate::{adapters::postgres::schema::*, core::models::recommendation::ProductRecommendation};
+use diesel::{pg::Pg, prelude::*};
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_recommendations)]
+#[diesel(check_for_backend(Pg))]
+pub struct RecommendationEntity {
+    pub product_id: i64,
+    pub related_product_id: i64,
+    pub score: f64,
+    pub position: i32,
+}
+
+impl RecommendationEntity {
+    pub fn to_model(&self) -> ProductRecommendation {
+        ProductRecommendation {
+            product_id: self.product_id,
+            related_product_id: self.related_product_id,
+            score: self.score,
+            position: self.position,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::{moderation::ModerationStatus, review::Review},
+};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, prelude::*};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = product_reviews)]
+#[diesel(check_for_backend(Pg))]
+pub struct ReviewEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub user_id: i64,
+    pub rating: i32,
+    pub body: String,
+    pub status: String,
+    pub response: Option<String>,
+    pub responded_at: Option<NaiveDateTime>,
+    pub helpful_count: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ReviewEntity {
+    pub fn to_model(&self) -> Review {
+        Review {
+            id: self.id,
+            product_id: self.product_id,
+            user_id: self.user_id,
+            rating: self.rating,
+            body: self.body.clone(),
+            status: ModerationStatus::from_str(&self.status).unwrap_or_default(),
+            response: self.response.clone(),
+            responded_at: self.responded_at,
+            helpful_count: self.helpful_count,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_reviews)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewReviewEntity {
+    pub product_id: i64,
+    pub user_id: i64,
+    pub rating: i32,
+    pub body: String,
+    pub status: String,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = product_review_votes)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewReviewVoteEntity {
+    pub review_id: i64,
+    pub user_id: i64,
+}
@@ -0,0 +1,88 @@
+use crate::{
+    adapters::postgres::schema::*,
+    core::models::subscription::{Subscription, SubscriptionPlan, SubscriptionStatus},
+};
+use chrono::NaiveDateTime;
+use diesel::{pg::Pg, prelude::*};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = subscription_plans)]
+#[diesel(check_for_backend(Pg))]
+pub struct SubscriptionPlanEntity {
+    pub id: i64,
+    pub product_id: i64,
+    pub interval_days: i32,
+    pub discount_percent: f64,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl SubscriptionPlanEntity {
+    pub fn to_model(&self) -> SubscriptionPlan {
+        SubscriptionPlan {
+            id: self.id,
+            product_id: self.product_id,
+            interval_days: self.interval_days,
+            discount_percent: self.discount_percent,
+            is_active: self.is_active,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = subscription_plans)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewSubscriptionPlanEntity {
+    pub product_id: i64,
+    pub interval_days: i32,
+    pub discount_percent: f64,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = subscriptions)]
+#[diesel(check_for_backend(Pg))]
+pub struct SubscriptionEntity {
+    pub id: i64,
+    pub user_id: i64,
+    pub variant_id: Option<i64>,
+    pub quantity: i32,
+    pub status: String,
+    pub next_run_at: NaiveDateTime,
+    pub last_order_id: Option<i64>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl SubscriptionEntity {
+    pub fn to_model(&self, plan: &SubscriptionPlanEntity) -> Subscription {
+        Subscription {
+            id: self.id,
+            user_id: self.user_id,
+            plan: plan.to_model(),
+            variant_id: self.variant_id,
+            quantity: self.quantity,
+            status: SubscriptionStatus::from_str(&self.status).unwrap_or_default(),
+            next_run_at: self.next_run_at,
+            last_order_id: self.last_order_id,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = subscriptions)]
+#[diesel(check_for_backend(Pg))]
+pub struct NewSubscriptionEntity {
+    pub user_id: i64,
+    pub plan_id: i64,
+    pub variant_id: Option<i64>,
+    pub quantity: i32,
+    pub status: String,
+    pub next_run_at: NaiveDateTime,
+}
@@ -1,11 +1,121 @@
-use crate::core::models::product::ProductError;
+use crate::core::{
+    models::{
+        product::ProductError, product_category::ProductCategoryError, question::QuestionError,
+        recommendation::RecommendationError, review::ReviewError, subscription::SubscriptionError,
+    },
+    ports::cart_repository::CartError,
+};
+use diesel::result::DatabaseErrorKind;
 
 impl From<diesel::result::Error> for ProductError {
     fn from(error: diesel::result::Error) -> Self {
         match error {
             diesel::result::Error::NotFound => ProductError::NotFound,
             diesel::result::Error::InvalidCString(_) => ProductError::InvalidData,
-            _ => ProductError::InternalError, 
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                match info.constraint_name() {
+                    Some("uq_products_slug") => ProductError::SlugTaken,
+                    Some("uq_products_sku") => ProductError::SkuTaken,
+                    _ => ProductError::InvalidData,
+                }
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                ProductError::InvalidCategory
+            }
+            _ => ProductError::InternalError,
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<diesel::result::Error> for CartError {
+    fn from(error: diesel::result::Error) -> Self {
+        match error {
+            diesel::result::Error::NotFound => CartError::NotFound,
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                CartError::Conflict
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                CartError::InvalidData
+            }
+            _ => CartError::DatabaseError,
+        }
+    }
+}
+
+impl From<diesel::result::Error> for ProductCategoryError {
+    fn from(error: diesel::result::Error) -> Self {
+        match error {
+            diesel::result::Error::NotFound => ProductCategoryError::NotFound,
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                ProductCategoryError::Conflict
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => {
+                match info.constraint_name() {
+                    Some("fk_product") => ProductCategoryError::ProductNotFound,
+                    _ => ProductCategoryError::CategoryNotFound,
+                }
+            }
+            _ => ProductCategoryError::DatabaseError,
+        }
+    }
+}
+
+impl From<diesel::result::Error> for ReviewError {
+    fn from(error: diesel::result::Error) -> Self {
+        match error {
+            diesel::result::Error::NotFound => ReviewError::NotFound,
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                match info.constraint_name() {
+                    Some("pk_product_review_votes") => ReviewError::AlreadyVoted,
+                    _ => ReviewError::AlreadyReviewed,
+                }
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                ReviewError::NotFound
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::CheckViolation, _) => {
+                ReviewError::InvalidData
+            }
+            _ => ReviewError::DatabaseError,
+        }
+    }
+}
+
+impl From<diesel::result::Error> for QuestionError {
+    fn from(error: diesel::result::Error) -> Self {
+        match error {
+            diesel::result::Error::NotFound => QuestionError::NotFound,
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                QuestionError::NotFound
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::CheckViolation, _) => {
+                QuestionError::InvalidData
+            }
+            _ => QuestionError::DatabaseError,
+        }
+    }
+}
+
+impl From<diesel::result::Error> for RecommendationError {
+    fn from(_: diesel::result::Error) -> Self {
+        RecommendationError::DatabaseError
+    }
+}
+
+impl From<diesel::result::Error> for SubscriptionError {
+    fn from(error: diesel::result::Error) -> Self {
+        match error {
+            diesel::result::Error::NotFound => SubscriptionError::NotFound,
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                SubscriptionError::InvalidData
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => {
+                SubscriptionError::NotFound
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::CheckViolation, _) => {
+                SubscriptionError::InvalidData
+            }
+            _ => SubscriptionError::InternalError,
+        }
+    }
+}
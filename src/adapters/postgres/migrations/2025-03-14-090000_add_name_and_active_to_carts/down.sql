@@ -0,0 +1,6 @@
+DROP INDEX uq_carts_user_active;
+
+ALTER TABLE carts
+DROP CONSTRAINT uq_carts_user_name,
+DROP COLUMN name,
+DROP COLUMN is_active;
@@ -0,0 +1,17 @@
+ALTER TABLE carts
+ADD COLUMN name TEXT NOT NULL DEFAULT 'Default',
+ADD COLUMN is_active BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE carts SET is_active = TRUE
+WHERE id IN (
+    SELECT DISTINCT ON (user_id) id
+    FROM carts
+    ORDER BY user_id, updated_at DESC, id DESC
+);
+
+UPDATE carts SET name = 'Cart ' || id WHERE NOT is_active;
+
+ALTER TABLE carts
+ADD CONSTRAINT uq_carts_user_name UNIQUE (user_id, name);
+
+CREATE UNIQUE INDEX uq_carts_user_active ON carts (user_id) WHERE is_active;
@@ -0,0 +1,8 @@
+ALTER TABLE order_items
+DROP COLUMN customizations;
+
+ALTER TABLE cart_items
+DROP COLUMN customizations,
+DROP COLUMN customization_price;
+
+DROP TABLE product_customization_fields;
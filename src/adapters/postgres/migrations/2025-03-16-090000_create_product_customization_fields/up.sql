@@ -0,0 +1,21 @@
+CREATE TABLE product_customization_fields (
+    id BIGSERIAL PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    field_type TEXT NOT NULL,
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    max_length INTEGER,
+    extra_price DOUBLE PRECISION NOT NULL DEFAULT 0,
+    CONSTRAINT fk_product
+        FOREIGN KEY (product_id)
+        REFERENCES products(id)
+        ON DELETE CASCADE,
+    CONSTRAINT uq_product_customization_fields_name UNIQUE (product_id, name)
+);
+
+ALTER TABLE cart_items
+ADD COLUMN customizations JSONB NOT NULL DEFAULT '{}',
+ADD COLUMN customization_price DOUBLE PRECISION NOT NULL DEFAULT 0;
+
+ALTER TABLE order_items
+ADD COLUMN customizations JSONB NOT NULL DEFAULT '{}';
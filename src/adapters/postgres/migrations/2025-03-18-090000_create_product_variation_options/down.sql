@@ -0,0 +1,7 @@
+DROP TABLE product_variation_options;
+
+ALTER TABLE variation_options
+DROP CONSTRAINT uq_variation_options_variation_value;
+
+ALTER TABLE variations
+DROP CONSTRAINT uq_variations_category_name;
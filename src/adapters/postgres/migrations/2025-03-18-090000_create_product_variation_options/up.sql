@@ -0,0 +1,13 @@
+ALTER TABLE variations
+ADD CONSTRAINT uq_variations_category_name UNIQUE (category_id, name);
+
+ALTER TABLE variation_options
+ADD CONSTRAINT uq_variation_options_variation_value UNIQUE (variation_id, value);
+
+CREATE TABLE product_variation_options (
+    product_id BIGINT NOT NULL,
+    variation_option_id BIGINT NOT NULL,
+    PRIMARY KEY (product_id, variation_option_id),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT fk_variation_option FOREIGN KEY (variation_option_id) REFERENCES variation_options(id) ON DELETE CASCADE
+);
@@ -0,0 +1,8 @@
+ALTER TABLE order_items
+DROP COLUMN variant_id;
+
+ALTER TABLE cart_items
+DROP COLUMN variant_id;
+
+DROP TABLE product_variant_options;
+DROP TABLE product_variants;
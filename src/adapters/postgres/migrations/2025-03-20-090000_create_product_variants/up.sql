@@ -0,0 +1,25 @@
+CREATE TABLE product_variants (
+    id BIGSERIAL PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    sku TEXT NOT NULL UNIQUE,
+    price DOUBLE PRECISION,
+    stock INTEGER NOT NULL DEFAULT 0,
+    image TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE TABLE product_variant_options (
+    variant_id BIGINT NOT NULL,
+    variation_option_id BIGINT NOT NULL,
+    PRIMARY KEY (variant_id, variation_option_id),
+    CONSTRAINT fk_variant FOREIGN KEY (variant_id) REFERENCES product_variants(id) ON DELETE CASCADE,
+    CONSTRAINT fk_variation_option FOREIGN KEY (variation_option_id) REFERENCES variation_options(id) ON DELETE CASCADE
+);
+
+ALTER TABLE cart_items
+ADD COLUMN variant_id BIGINT REFERENCES product_variants(id) ON DELETE CASCADE;
+
+ALTER TABLE order_items
+ADD COLUMN variant_id BIGINT REFERENCES product_variants(id) ON DELETE SET NULL;
@@ -0,0 +1,20 @@
+CREATE TABLE product_categories (
+    id BIGSERIAL PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    category_id BIGINT NOT NULL,
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT fk_category FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+    CONSTRAINT uq_product_categories_product_category UNIQUE (product_id, category_id)
+);
+
+CREATE UNIQUE INDEX uq_product_categories_primary ON product_categories(product_id) WHERE is_primary;
+CREATE INDEX idx_product_categories_category_id ON product_categories(category_id);
+
+-- products.category_id is kept as the primary category of the product.
+INSERT INTO product_categories (product_id, category_id, is_primary)
+SELECT id, category_id, TRUE
+FROM products
+WHERE category_id IS NOT NULL;
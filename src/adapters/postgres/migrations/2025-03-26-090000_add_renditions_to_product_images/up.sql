@@ -0,0 +1,4 @@
+ALTER TABLE product_images
+ADD COLUMN width INTEGER,
+ADD COLUMN height INTEGER,
+ADD COLUMN renditions JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,14 @@
+DROP TRIGGER trg_categories_search_document ON categories;
+DROP FUNCTION categories_refresh_search_document();
+
+DROP TRIGGER trg_product_categories_search_document ON product_categories;
+DROP FUNCTION product_categories_refresh_search_document();
+
+DROP TRIGGER trg_products_search_document ON products;
+DROP FUNCTION products_refresh_search_document();
+
+DROP INDEX idx_products_search_document;
+ALTER TABLE products DROP COLUMN search_document;
+
+DROP FUNCTION product_search_document(BIGINT, TEXT, TEXT);
+DROP TABLE search_settings;
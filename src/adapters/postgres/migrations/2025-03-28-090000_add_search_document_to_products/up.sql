@@ -0,0 +1,72 @@
+-- Text search configuration used to build search documents. The application
+-- keeps it in sync with the `search.language` setting and reindexes on change.
+CREATE TABLE search_settings (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    language REGCONFIG NOT NULL DEFAULT 'english'
+);
+
+INSERT INTO search_settings DEFAULT VALUES;
+
+-- Name weighs most, then category names, then the description.
+CREATE FUNCTION product_search_document(product_id BIGINT, name TEXT, description TEXT)
+RETURNS TSVECTOR AS $$
+    SELECT setweight(to_tsvector(settings.language, coalesce(name, '')), 'A')
+        || setweight(to_tsvector(settings.language, coalesce((
+            SELECT string_agg(categories.name, ' ')
+            FROM product_categories
+            JOIN categories ON categories.id = product_categories.category_id
+            WHERE product_categories.product_id = product_search_document.product_id
+        ), '')), 'B')
+        || setweight(to_tsvector(settings.language, coalesce(description, '')), 'C')
+    FROM search_settings AS settings
+$$ LANGUAGE SQL STABLE;
+
+ALTER TABLE products ADD COLUMN search_document TSVECTOR NOT NULL DEFAULT '';
+
+UPDATE products SET search_document = product_search_document(id, name, description);
+
+CREATE INDEX idx_products_search_document ON products USING GIN (search_document);
+
+CREATE FUNCTION products_refresh_search_document() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.search_document := product_search_document(NEW.id, NEW.name, NEW.description);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_products_search_document
+BEFORE INSERT OR UPDATE OF name, description ON products
+FOR EACH ROW EXECUTE FUNCTION products_refresh_search_document();
+
+-- Category names are part of the document, so assigning, unassigning or
+-- renaming a category refreshes the products it belongs to.
+CREATE FUNCTION product_categories_refresh_search_document() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE products
+    SET search_document = product_search_document(id, name, description)
+    WHERE id IN (
+        CASE WHEN TG_OP <> 'DELETE' THEN NEW.product_id END,
+        CASE WHEN TG_OP <> 'INSERT' THEN OLD.product_id END
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_product_categories_search_document
+AFTER INSERT OR UPDATE OF product_id, category_id OR DELETE ON product_categories
+FOR EACH ROW EXECUTE FUNCTION product_categories_refresh_search_document();
+
+CREATE FUNCTION categories_refresh_search_document() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE products
+    SET search_document = product_search_document(id, name, description)
+    WHERE id IN (
+        SELECT product_id FROM product_categories WHERE category_id = NEW.id
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_categories_search_document
+AFTER UPDATE OF name ON categories
+FOR EACH ROW EXECUTE FUNCTION categories_refresh_search_document();
@@ -0,0 +1,2 @@
+DROP INDEX idx_categories_name_trgm;
+DROP INDEX idx_products_name_trgm;
@@ -0,0 +1,13 @@
+DROP TRIGGER trg_orders_units_sold ON orders;
+DROP FUNCTION orders_refresh_units_sold();
+
+DROP TRIGGER trg_order_items_units_sold ON order_items;
+DROP FUNCTION order_items_refresh_units_sold();
+
+DROP INDEX idx_products_rating_average;
+ALTER TABLE products DROP COLUMN rating_average;
+
+DROP INDEX idx_products_units_sold;
+ALTER TABLE products DROP COLUMN units_sold;
+
+DROP FUNCTION product_units_sold(BIGINT);
@@ -0,0 +1,50 @@
+-- Units ordered, for sorting by popularity. Cancelled and failed orders do not count.
+CREATE FUNCTION product_units_sold(product_id BIGINT) RETURNS BIGINT AS $$
+    SELECT coalesce(sum(order_items.quantity), 0)
+    FROM order_items
+    JOIN orders ON orders.id = order_items.order_id
+    WHERE order_items.product_id = product_units_sold.product_id
+      AND orders.status NOT IN ('Cancelled', 'Error')
+$$ LANGUAGE SQL STABLE;
+
+ALTER TABLE products ADD COLUMN units_sold BIGINT NOT NULL DEFAULT 0;
+
+UPDATE products SET units_sold = product_units_sold(id);
+
+CREATE INDEX idx_products_units_sold ON products(units_sold DESC, id);
+
+-- Average star rating, for filtering by rating; 0 until the product is rated.
+ALTER TABLE products ADD COLUMN rating_average DOUBLE PRECISION NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_products_rating_average ON products(rating_average DESC, id);
+
+CREATE FUNCTION order_items_refresh_units_sold() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE products
+    SET units_sold = product_units_sold(id)
+    WHERE id IN (
+        CASE WHEN TG_OP <> 'DELETE' THEN NEW.product_id END,
+        CASE WHEN TG_OP <> 'INSERT' THEN OLD.product_id END
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_order_items_units_sold
+AFTER INSERT OR UPDATE OF product_id, quantity OR DELETE ON order_items
+FOR EACH ROW EXECUTE FUNCTION order_items_refresh_units_sold();
+
+CREATE FUNCTION orders_refresh_units_sold() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE products
+    SET units_sold = product_units_sold(id)
+    WHERE id IN (SELECT product_id FROM order_items WHERE order_id = NEW.id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_orders_units_sold
+AFTER UPDATE OF status ON orders
+FOR EACH ROW
+WHEN (OLD.status IS DISTINCT FROM NEW.status)
+EXECUTE FUNCTION orders_refresh_units_sold();
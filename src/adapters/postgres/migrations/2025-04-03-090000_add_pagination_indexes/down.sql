@@ -0,0 +1,3 @@
+DROP INDEX idx_orders_user_id_created_at_id;
+DROP INDEX idx_categories_created_at_id;
+DROP INDEX idx_products_created_at_id;
@@ -0,0 +1,4 @@
+-- Keyset pagination walks lists in (created_at, id) order.
+CREATE INDEX idx_products_created_at_id ON products(created_at, id);
+CREATE INDEX idx_categories_created_at_id ON categories(created_at, id);
+CREATE INDEX idx_orders_user_id_created_at_id ON orders(user_id, created_at, id);
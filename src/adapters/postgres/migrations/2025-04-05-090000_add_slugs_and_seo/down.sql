@@ -0,0 +1,14 @@
+DROP TABLE category_slug_redirects;
+DROP TABLE product_slug_redirects;
+
+ALTER TABLE categories
+DROP COLUMN canonical_url,
+DROP COLUMN meta_description,
+DROP COLUMN meta_title,
+DROP COLUMN slug;
+
+ALTER TABLE products
+DROP COLUMN canonical_url,
+DROP COLUMN meta_description,
+DROP COLUMN meta_title,
+DROP COLUMN slug;
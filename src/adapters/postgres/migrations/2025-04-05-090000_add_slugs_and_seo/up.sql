@@ -0,0 +1,52 @@
+-- Backfills slugs from names: lowercase ASCII words joined by dashes, suffixed
+-- with the id when the name does not produce a unique one.
+CREATE FUNCTION slugify(value TEXT) RETURNS TEXT AS $$
+    SELECT left(trim(BOTH '-' FROM regexp_replace(lower(value), '[^a-z0-9]+', '-', 'g')), 100)
+$$ LANGUAGE SQL IMMUTABLE;
+
+ALTER TABLE products
+ADD COLUMN slug TEXT,
+ADD COLUMN meta_title TEXT,
+ADD COLUMN meta_description TEXT,
+ADD COLUMN canonical_url TEXT;
+
+UPDATE products SET slug = slugify(name);
+UPDATE products SET slug = trim(BOTH '-' FROM slug || '-' || id)
+WHERE slug = '' OR slug IN (SELECT slug FROM products GROUP BY slug HAVING count(*) > 1);
+
+ALTER TABLE products ALTER COLUMN slug SET NOT NULL;
+ALTER TABLE products ADD CONSTRAINT uq_products_slug UNIQUE (slug);
+
+ALTER TABLE categories
+ADD COLUMN slug TEXT,
+ADD COLUMN meta_title TEXT,
+ADD COLUMN meta_description TEXT,
+ADD COLUMN canonical_url TEXT;
+
+UPDATE categories SET slug = slugify(name);
+UPDATE categories SET slug = trim(BOTH '-' FROM slug || '-' || id)
+WHERE slug = '' OR slug IN (SELECT slug FROM categories GROUP BY slug HAVING count(*) > 1);
+
+ALTER TABLE categories ALTER COLUMN slug SET NOT NULL;
+ALTER TABLE categories ADD CONSTRAINT uq_categories_slug UNIQUE (slug);
+
+DROP FUNCTION slugify(TEXT);
+
+-- Slugs a product or category had before, so that old URLs keep resolving.
+CREATE TABLE product_slug_redirects (
+    slug TEXT PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_product_slug_redirects_product ON product_slug_redirects(product_id);
+
+CREATE TABLE category_slug_redirects (
+    slug TEXT PRIMARY KEY,
+    category_id BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_category FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_category_slug_redirects_category ON category_slug_redirects(category_id);
@@ -0,0 +1,8 @@
+DROP INDEX idx_products_unpublish_at;
+DROP INDEX idx_products_publish_at;
+
+ALTER TABLE products
+DROP CONSTRAINT chk_products_status,
+DROP COLUMN unpublish_at,
+DROP COLUMN publish_at,
+DROP COLUMN status;
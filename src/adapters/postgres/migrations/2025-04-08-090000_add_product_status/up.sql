@@ -0,0 +1,11 @@
+-- Existing products stay live; products created from now on start as drafts.
+ALTER TABLE products
+ADD COLUMN status TEXT NOT NULL DEFAULT 'published',
+ADD COLUMN publish_at TIMESTAMP,
+ADD COLUMN unpublish_at TIMESTAMP,
+ADD CONSTRAINT chk_products_status CHECK (status IN ('draft', 'published', 'archived'));
+
+ALTER TABLE products ALTER COLUMN status SET DEFAULT 'draft';
+
+CREATE INDEX idx_products_publish_at ON products(publish_at) WHERE publish_at IS NOT NULL;
+CREATE INDEX idx_products_unpublish_at ON products(unpublish_at) WHERE unpublish_at IS NOT NULL;
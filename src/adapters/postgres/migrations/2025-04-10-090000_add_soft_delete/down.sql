@@ -0,0 +1,9 @@
+ALTER TABLE order_items
+DROP CONSTRAINT fk_product,
+ADD CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE;
+
+DROP INDEX idx_categories_deleted_at;
+DROP INDEX idx_products_deleted_at;
+
+ALTER TABLE categories DROP COLUMN deleted_at;
+ALTER TABLE products DROP COLUMN deleted_at;
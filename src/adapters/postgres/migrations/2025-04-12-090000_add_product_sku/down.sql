@@ -0,0 +1,2 @@
+ALTER TABLE products DROP CONSTRAINT uq_products_sku;
+ALTER TABLE products DROP COLUMN sku;
@@ -0,0 +1,3 @@
+-- Stock keeping unit merchandisers identify products by in their catalogs.
+ALTER TABLE products ADD COLUMN sku TEXT;
+ALTER TABLE products ADD CONSTRAINT uq_products_sku UNIQUE (sku);
@@ -0,0 +1,10 @@
+DROP TRIGGER trg_product_review_votes_helpful_count ON product_review_votes;
+DROP FUNCTION product_review_votes_refresh_helpful_count();
+
+DROP TRIGGER trg_product_reviews_rating ON product_reviews;
+DROP FUNCTION product_reviews_refresh_rating();
+
+ALTER TABLE products DROP COLUMN rating_count;
+
+DROP TABLE product_review_votes;
+DROP TABLE product_reviews;
@@ -0,0 +1,78 @@
+CREATE TABLE product_reviews (
+    id BIGSERIAL PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    rating INTEGER NOT NULL,
+    body TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    response TEXT,
+    responded_at TIMESTAMP,
+    helpful_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT uq_product_reviews_product_user UNIQUE (product_id, user_id),
+    CONSTRAINT chk_product_reviews_rating CHECK (rating BETWEEN 1 AND 5),
+    CONSTRAINT chk_product_reviews_status CHECK (status IN ('pending', 'approved', 'rejected'))
+);
+
+CREATE INDEX idx_product_reviews_product_created_at ON product_reviews(product_id, created_at DESC, id DESC);
+CREATE INDEX idx_product_reviews_status_created_at ON product_reviews(status, created_at, id);
+
+CREATE TABLE product_review_votes (
+    review_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT pk_product_review_votes PRIMARY KEY (review_id, user_id),
+    CONSTRAINT fk_review FOREIGN KEY (review_id) REFERENCES product_reviews(id) ON DELETE CASCADE,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Number of the approved reviews; rating_average becomes their average.
+ALTER TABLE products ADD COLUMN rating_count BIGINT NOT NULL DEFAULT 0;
+
+CREATE FUNCTION product_reviews_refresh_rating() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE products
+    SET rating_average = ratings.average,
+        rating_count = ratings.count
+    FROM (
+        SELECT products.id,
+               coalesce(avg(product_reviews.rating), 0)::DOUBLE PRECISION AS average,
+               count(product_reviews.id) AS count
+        FROM products
+        LEFT JOIN product_reviews
+            ON product_reviews.product_id = products.id AND product_reviews.status = 'approved'
+        WHERE products.id IN (
+            CASE WHEN TG_OP <> 'DELETE' THEN NEW.product_id END,
+            CASE WHEN TG_OP <> 'INSERT' THEN OLD.product_id END
+        )
+        GROUP BY products.id
+    ) AS ratings
+    WHERE products.id = ratings.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_product_reviews_rating
+AFTER INSERT OR UPDATE OF product_id, rating, status OR DELETE ON product_reviews
+FOR EACH ROW EXECUTE FUNCTION product_reviews_refresh_rating();
+
+CREATE FUNCTION product_review_votes_refresh_helpful_count() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE product_reviews
+    SET helpful_count = (
+        SELECT count(*) FROM product_review_votes WHERE review_id = product_reviews.id
+    )
+    WHERE id IN (
+        CASE WHEN TG_OP <> 'DELETE' THEN NEW.review_id END,
+        CASE WHEN TG_OP <> 'INSERT' THEN OLD.review_id END
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_product_review_votes_helpful_count
+AFTER INSERT OR DELETE ON product_review_votes
+FOR EACH ROW EXECUTE FUNCTION product_review_votes_refresh_helpful_count();
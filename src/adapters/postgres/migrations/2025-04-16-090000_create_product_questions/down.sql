@@ -0,0 +1,5 @@
+DROP TRIGGER trg_product_answers_answer_count ON product_answers;
+DROP FUNCTION product_answers_refresh_answer_count();
+
+DROP TABLE product_answers;
+DROP TABLE product_questions;
@@ -0,0 +1,55 @@
+CREATE TABLE product_questions (
+    id BIGSERIAL PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    body TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    answer_count BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT chk_product_questions_status CHECK (status IN ('pending', 'approved', 'rejected'))
+);
+
+CREATE INDEX idx_product_questions_product_created_at ON product_questions(product_id, created_at DESC, id DESC);
+CREATE INDEX idx_product_questions_status_created_at ON product_questions(status, created_at, id);
+
+CREATE TABLE product_answers (
+    id BIGSERIAL PRIMARY KEY,
+    question_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    body TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    is_staff BOOLEAN NOT NULL DEFAULT FALSE,
+    is_verified_buyer BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_question FOREIGN KEY (question_id) REFERENCES product_questions(id) ON DELETE CASCADE,
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT chk_product_answers_status CHECK (status IN ('pending', 'approved', 'rejected'))
+);
+
+CREATE INDEX idx_product_answers_question_created_at ON product_answers(question_id, created_at, id);
+CREATE INDEX idx_product_answers_status_created_at ON product_answers(status, created_at, id);
+
+-- Approved answers, for showing the most answered questions first.
+CREATE FUNCTION product_answers_refresh_answer_count() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE product_questions
+    SET answer_count = (
+        SELECT count(*)
+        FROM product_answers
+        WHERE question_id = product_questions.id AND status = 'approved'
+    )
+    WHERE id IN (
+        CASE WHEN TG_OP <> 'DELETE' THEN NEW.question_id END,
+        CASE WHEN TG_OP <> 'INSERT' THEN OLD.question_id END
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_product_answers_answer_count
+AFTER INSERT OR UPDATE OF question_id, status OR DELETE ON product_answers
+FOR EACH ROW EXECUTE FUNCTION product_answers_refresh_answer_count();
@@ -0,0 +1,14 @@
+-- Products related to each product, best first. Computing the recommendations
+-- upserts the pairs still related and deletes the ones that no longer are.
+CREATE TABLE product_recommendations (
+    product_id BIGINT NOT NULL,
+    related_product_id BIGINT NOT NULL,
+    score DOUBLE PRECISION NOT NULL,
+    position INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT pk_product_recommendations PRIMARY KEY (product_id, related_product_id),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT fk_related_product FOREIGN KEY (related_product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_product_recommendations_product_position ON product_recommendations(product_id, position);
@@ -0,0 +1,4 @@
+DROP INDEX idx_products_trending_views;
+ALTER TABLE products DROP COLUMN trending_views;
+
+DROP TABLE product_view_counts;
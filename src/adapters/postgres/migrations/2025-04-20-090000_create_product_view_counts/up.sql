@@ -0,0 +1,15 @@
+-- Views per product and day, flushed periodically from Redis.
+CREATE TABLE product_view_counts (
+    product_id BIGINT NOT NULL,
+    viewed_on DATE NOT NULL,
+    views BIGINT NOT NULL DEFAULT 0,
+    CONSTRAINT pk_product_view_counts PRIMARY KEY (product_id, viewed_on),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_product_view_counts_viewed_on ON product_view_counts(viewed_on);
+
+-- Views of the last days, for sorting by trending products.
+ALTER TABLE products ADD COLUMN trending_views BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_products_trending_views ON products(trending_views DESC, id);
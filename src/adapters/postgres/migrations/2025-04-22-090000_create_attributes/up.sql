@@ -0,0 +1,31 @@
+CREATE TABLE attributes (
+    id BIGSERIAL PRIMARY KEY,
+    category_id BIGINT NOT NULL,
+    name TEXT NOT NULL,
+    data_type TEXT NOT NULL,
+    unit TEXT,
+    is_filterable BOOLEAN NOT NULL DEFAULT FALSE,
+    options TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_category FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE,
+    CONSTRAINT uq_attributes_category_name UNIQUE (category_id, name),
+    CONSTRAINT chk_attributes_data_type CHECK (data_type IN ('text', 'number', 'bool', 'enum'))
+);
+
+-- Exactly one of the value columns is set, the one matching the data type of
+-- the attribute; enum values are stored as text.
+CREATE TABLE product_attribute_values (
+    product_id BIGINT NOT NULL,
+    attribute_id BIGINT NOT NULL,
+    text_value TEXT,
+    number_value DOUBLE PRECISION,
+    bool_value BOOLEAN,
+    PRIMARY KEY (product_id, attribute_id),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT fk_attribute FOREIGN KEY (attribute_id) REFERENCES attributes(id) ON DELETE CASCADE,
+    CONSTRAINT chk_product_attribute_values_single CHECK (num_nonnulls(text_value, number_value, bool_value) = 1)
+);
+
+CREATE INDEX idx_product_attribute_values_text ON product_attribute_values(attribute_id, text_value);
+CREATE INDEX idx_product_attribute_values_number ON product_attribute_values(attribute_id, number_value);
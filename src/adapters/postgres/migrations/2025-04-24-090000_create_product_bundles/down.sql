@@ -0,0 +1,10 @@
+DROP TRIGGER trg_product_bundles_bundles ON product_bundles;
+DROP FUNCTION product_bundles_refresh_bundles();
+DROP TRIGGER trg_product_bundle_items_bundles ON product_bundle_items;
+DROP FUNCTION product_bundle_items_refresh_bundles();
+DROP TRIGGER trg_products_bundles ON products;
+DROP FUNCTION products_refresh_bundles();
+DROP FUNCTION refresh_product_bundles(BIGINT[]);
+
+DROP TABLE product_bundle_items;
+DROP TABLE product_bundles;
@@ -0,0 +1,107 @@
+-- A product sold as a kit of other products. Without a discount the bundle
+-- keeps the price set on the product.
+CREATE TABLE product_bundles (
+    product_id BIGINT PRIMARY KEY,
+    discount_percent DOUBLE PRECISION,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT chk_product_bundles_discount_percent CHECK (discount_percent >= 0 AND discount_percent < 100)
+);
+
+CREATE TABLE product_bundle_items (
+    bundle_id BIGINT NOT NULL,
+    component_id BIGINT NOT NULL,
+    quantity INTEGER NOT NULL,
+    CONSTRAINT pk_product_bundle_items PRIMARY KEY (bundle_id, component_id),
+    CONSTRAINT fk_bundle FOREIGN KEY (bundle_id) REFERENCES product_bundles(product_id) ON DELETE CASCADE,
+    CONSTRAINT fk_component FOREIGN KEY (component_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT chk_product_bundle_items_quantity CHECK (quantity > 0)
+);
+
+CREATE INDEX idx_product_bundle_items_component_id ON product_bundle_items(component_id);
+
+-- Sets the stock of the bundles to the number of complete kits their components
+-- make up, and the price of discounted bundles to the discounted total of their
+-- components. Components in the trash count as out of stock.
+CREATE FUNCTION refresh_product_bundles(bundle_ids BIGINT[]) RETURNS VOID AS $$
+    UPDATE products
+    SET stock = bundles.stock,
+        price = bundles.price
+    FROM (
+        SELECT product_bundles.product_id,
+               coalesce(totals.stock, 0) AS stock,
+               CASE
+                   WHEN product_bundles.discount_percent IS NULL THEN bundle_products.price
+                   ELSE round(
+                       (coalesce(totals.price, 0) * (100 - product_bundles.discount_percent) / 100)::NUMERIC,
+                       2
+                   )::DOUBLE PRECISION
+               END AS price
+        FROM product_bundles
+        JOIN products AS bundle_products ON bundle_products.id = product_bundles.product_id
+        LEFT JOIN (
+            SELECT product_bundle_items.bundle_id,
+                   min(
+                       CASE WHEN components.deleted_at IS NULL THEN greatest(components.stock, 0) ELSE 0 END
+                       / product_bundle_items.quantity
+                   ) AS stock,
+                   sum(components.price * product_bundle_items.quantity) AS price
+            FROM product_bundle_items
+            JOIN products AS components ON components.id = product_bundle_items.component_id
+            GROUP BY product_bundle_items.bundle_id
+        ) AS totals ON totals.bundle_id = product_bundles.product_id
+        WHERE product_bundles.product_id = ANY(bundle_ids)
+    ) AS bundles
+    WHERE products.id = bundles.product_id
+      AND (products.stock <> bundles.stock OR products.price <> bundles.price);
+$$ LANGUAGE sql;
+
+-- A change to a component updates the bundles it belongs to; a change to the
+-- stock or price of a bundle itself is derived again.
+CREATE FUNCTION products_refresh_bundles() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_product_bundles(
+        array_append(
+            ARRAY(SELECT bundle_id FROM product_bundle_items WHERE component_id = NEW.id),
+            NEW.id
+        )
+    );
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_products_bundles
+AFTER UPDATE OF price, stock, deleted_at ON products
+FOR EACH ROW
+WHEN (
+    OLD.price IS DISTINCT FROM NEW.price
+    OR OLD.stock IS DISTINCT FROM NEW.stock
+    OR OLD.deleted_at IS DISTINCT FROM NEW.deleted_at
+)
+EXECUTE FUNCTION products_refresh_bundles();
+
+CREATE FUNCTION product_bundle_items_refresh_bundles() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_product_bundles(ARRAY[
+        CASE WHEN TG_OP <> 'DELETE' THEN NEW.bundle_id END,
+        CASE WHEN TG_OP <> 'INSERT' THEN OLD.bundle_id END
+    ]);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_product_bundle_items_bundles
+AFTER INSERT OR UPDATE OR DELETE ON product_bundle_items
+FOR EACH ROW EXECUTE FUNCTION product_bundle_items_refresh_bundles();
+
+CREATE FUNCTION product_bundles_refresh_bundles() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_product_bundles(ARRAY[NEW.product_id]);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_product_bundles_bundles
+AFTER INSERT OR UPDATE OF discount_percent ON product_bundles
+FOR EACH ROW EXECUTE FUNCTION product_bundles_refresh_bundles();
@@ -0,0 +1,8 @@
+DROP TABLE order_item_downloads;
+DROP TABLE product_files;
+
+ALTER TABLE orders
+DROP COLUMN paid_at,
+DROP COLUMN requires_shipping;
+
+ALTER TABLE products DROP COLUMN is_digital;
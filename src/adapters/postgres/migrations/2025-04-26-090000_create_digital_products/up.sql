@@ -0,0 +1,35 @@
+-- Digital products are delivered as downloadable files rather than shipped.
+ALTER TABLE products ADD COLUMN is_digital BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Orders made of digital products only have nothing to ship.
+ALTER TABLE orders
+ADD COLUMN requires_shipping BOOLEAN NOT NULL DEFAULT TRUE,
+ADD COLUMN paid_at TIMESTAMP;
+
+CREATE TABLE product_files (
+    id BIGSERIAL PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    file_name TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_product_files_product_id ON product_files(product_id);
+
+-- Downloads of the files of a digital product an order item gives access to,
+-- granted once the order is paid.
+CREATE TABLE order_item_downloads (
+    order_item_id BIGINT PRIMARY KEY,
+    max_downloads INTEGER NOT NULL,
+    download_count INTEGER NOT NULL DEFAULT 0,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_order_item FOREIGN KEY (order_item_id) REFERENCES order_items(id) ON DELETE CASCADE,
+    CONSTRAINT chk_order_item_downloads_max_downloads CHECK (max_downloads > 0),
+    CONSTRAINT chk_order_item_downloads_download_count CHECK (download_count >= 0 AND download_count <= max_downloads)
+);
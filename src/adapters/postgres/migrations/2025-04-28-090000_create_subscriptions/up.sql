@@ -0,0 +1,39 @@
+-- Schedules a product can be subscribed to, with the discount subscribers get.
+CREATE TABLE subscription_plans (
+    id BIGSERIAL PRIMARY KEY,
+    product_id BIGINT NOT NULL,
+    interval_days INTEGER NOT NULL,
+    discount_percent DOUBLE PRECISION NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_product FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+    CONSTRAINT uq_subscription_plans_product_interval UNIQUE (product_id, interval_days),
+    CONSTRAINT chk_subscription_plans_interval_days CHECK (interval_days > 0),
+    CONSTRAINT chk_subscription_plans_discount_percent CHECK (discount_percent >= 0 AND discount_percent < 100)
+);
+
+-- Products users get ordered for them on the schedule of a plan. Plans with
+-- subscribers cannot be deleted, only deactivated.
+CREATE TABLE subscriptions (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL,
+    plan_id BIGINT NOT NULL,
+    variant_id BIGINT,
+    quantity INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'active',
+    next_run_at TIMESTAMP NOT NULL,
+    last_order_id BIGINT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT fk_plan FOREIGN KEY (plan_id) REFERENCES subscription_plans(id),
+    CONSTRAINT fk_variant FOREIGN KEY (variant_id) REFERENCES product_variants(id) ON DELETE CASCADE,
+    CONSTRAINT fk_last_order FOREIGN KEY (last_order_id) REFERENCES orders(id) ON DELETE SET NULL,
+    CONSTRAINT chk_subscriptions_quantity CHECK (quantity > 0),
+    CONSTRAINT chk_subscriptions_status CHECK (status IN ('active', 'paused', 'cancelled'))
+);
+
+CREATE INDEX idx_subscriptions_user_id ON subscriptions(user_id);
+CREATE INDEX idx_subscriptions_plan_id ON subscriptions(plan_id);
+CREATE INDEX idx_subscriptions_due ON subscriptions(next_run_at) WHERE status = 'active';
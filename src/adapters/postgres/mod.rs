@@ -1,4 +1,5 @@
 mod entities;
 pub mod repos;
 pub mod schema;
-mod errors;
\ No newline at end of file
+mod errors;
+pub mod search_index;
\ No newline at end of file
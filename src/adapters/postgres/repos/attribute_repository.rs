@@ -0,0 +1,252 @@
+use std::ops::DerefMut;
+
+use chrono::Utc;
+use diesel::{
+    Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+    dsl::count_star,
+    r2d2::{ConnectionManager, Pool},
+    result::Error as DieselError,
+    sql_types::{Array, Int8},
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::attribute::{AttributeEntity, NewAttributeEntity, ProductAttributeValueEntity},
+        schema::{attributes, product_attribute_values, products},
+    },
+    core::{
+        models::{
+            attribute::{Attribute, AttributeType, AttributeValue, ProductAttribute},
+            product::ProductError,
+        },
+        ports::attribute_repository::AttributeRepository,
+    },
+};
+
+/// Deletes the attribute values of `product_ids` whose attribute is no longer
+/// defined on any category of the product or on their ancestors, after the
+/// product lost a category or one of its categories moved.
+pub(crate) fn delete_stale_attribute_values(
+    conn: &mut PgConnection,
+    product_ids: &[i64],
+) -> Result<usize, DieselError> {
+    diesel::sql_query(
+        "DELETE FROM product_attribute_values \
+         WHERE product_attribute_values.product_id = ANY($1) \
+             AND NOT EXISTS ( \
+                 WITH RECURSIVE lineage (id) AS ( \
+                     SELECT category_id FROM product_categories \
+                     WHERE product_categories.product_id = product_attribute_values.product_id \
+                     UNION \
+                     SELECT categories.parent_id FROM categories \
+                     JOIN lineage ON lineage.id = categories.id \
+                     WHERE categories.parent_id IS NOT NULL \
+                 ) \
+                 SELECT 1 FROM attributes \
+                 JOIN lineage ON lineage.id = attributes.category_id \
+                 WHERE attributes.id = product_attribute_values.attribute_id \
+             )",
+    )
+    .bind::<Array<Int8>, _>(product_ids)
+    .execute(conn)
+}
+
+pub struct AttributeRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl AttributeRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        AttributeRepositoryImpl { conn }
+    }
+
+    fn load_product_attributes(
+        conn: &mut PgConnection,
+        product_id: i64,
+    ) -> Result<Vec<ProductAttribute>, DieselError> {
+        let records = product_attribute_values::table
+            .inner_join(attributes::table)
+            .filter(product_attribute_values::product_id.eq(product_id))
+            .order(attributes::id.asc())
+            .select((
+                ProductAttributeValueEntity::as_select(),
+                AttributeEntity::as_select(),
+            ))
+            .load::<(ProductAttributeValueEntity, AttributeEntity)>(conn)?;
+
+        Ok(records
+            .iter()
+            .map(|(value, attribute)| value.to_model(attribute))
+            .collect())
+    }
+}
+
+impl AttributeRepository for AttributeRepositoryImpl {
+    fn create_attribute(&mut self, attribute: Attribute) -> Result<Attribute, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(attributes::table)
+            .values(NewAttributeEntity {
+                category_id: attribute.category_id,
+                name: attribute.name,
+                data_type: attribute.data_type.to_string(),
+                unit: attribute.unit,
+                is_filterable: attribute.is_filterable,
+                options: attribute.options,
+            })
+            .get_result::<AttributeEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_attribute_by_id(&mut self, id: i64) -> Result<Attribute, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        attributes::table
+            .filter(attributes::id.eq(id))
+            .first::<AttributeEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_inherited_attributes(
+        &mut self,
+        category_ids: &[i64],
+    ) -> Result<Vec<Attribute>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        // The categories first, then their parents, grandparents and so on.
+        // The path stops the walk should parents ever form a cycle.
+        diesel::sql_query(
+            "WITH RECURSIVE lineage (id, depth, path) AS ( \
+                 SELECT id, 0, ARRAY[id] FROM categories WHERE id = ANY($1) \
+                 UNION ALL \
+                 SELECT categories.parent_id, lineage.depth + 1, \
+                     lineage.path || categories.parent_id \
+                 FROM categories \
+                 JOIN lineage ON lineage.id = categories.id \
+                 WHERE categories.parent_id IS NOT NULL \
+                     AND NOT categories.parent_id = ANY(lineage.path) \
+             ) \
+             SELECT attributes.* FROM attributes \
+             JOIN (SELECT id, min(depth) AS depth FROM lineage GROUP BY id) AS closest \
+                 ON closest.id = attributes.category_id \
+             ORDER BY closest.depth, array_position($1, attributes.category_id), attributes.id",
+        )
+        .bind::<Array<Int8>, _>(category_ids)
+        .load::<AttributeEntity>(conn.deref_mut())
+        .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+        .map_err(Into::into)
+    }
+
+    fn update_attribute(&mut self, attribute: Attribute) -> Result<Attribute, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<AttributeEntity, DieselError, _>(|conn| {
+                let record =
+                    diesel::update(attributes::table.filter(attributes::id.eq(attribute.id)))
+                        .set((
+                            attributes::name.eq(attribute.name),
+                            attributes::unit.eq(attribute.unit),
+                            attributes::is_filterable.eq(attribute.is_filterable),
+                            attributes::options.eq(&attribute.options),
+                            attributes::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .get_result::<AttributeEntity>(conn)?;
+
+                if record.data_type == AttributeType::Enum.to_string() {
+                    let orphaned = product_attribute_values::table
+                        .filter(product_attribute_values::attribute_id.eq(record.id))
+                        .filter(diesel::dsl::not(
+                            product_attribute_values::text_value.eq_any(&attribute.options),
+                        ))
+                        .select(count_star())
+                        .first::<i64>(conn)?;
+                    if orphaned > 0 {
+                        return Err(DieselError::RollbackTransaction);
+                    }
+                }
+                Ok(record)
+            })
+            .map(|record| record.to_model())
+            .map_err(|err| match err {
+                // Products still use an option being removed.
+                DieselError::RollbackTransaction => ProductError::InvalidAttribute,
+                err => err.into(),
+            })
+    }
+
+    fn delete_attribute(&mut self, id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(attributes::table.filter(attributes::id.eq(id)))
+            .execute(conn.deref_mut())
+            .map(|affected_rows| {
+                if affected_rows == 0 {
+                    Err(ProductError::NotFound)
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|_| ProductError::InternalError)?
+    }
+
+    fn find_product_attributes(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductAttribute>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        Self::load_product_attributes(conn.deref_mut(), product_id).map_err(Into::into)
+    }
+
+    fn delete_stale_product_attributes(&mut self, product_id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        delete_stale_attribute_values(conn.deref_mut(), &[product_id])
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    fn set_product_attributes(
+        &mut self,
+        product_id: i64,
+        values: Vec<(i64, AttributeValue)>,
+    ) -> Result<Vec<ProductAttribute>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<Vec<ProductAttribute>, DieselError, _>(|conn| {
+                products::table
+                    .filter(products::id.eq(product_id))
+                    .select(products::id)
+                    .first::<i64>(conn)?;
+
+                diesel::delete(
+                    product_attribute_values::table
+                        .filter(product_attribute_values::product_id.eq(product_id)),
+                )
+                .execute(conn)?;
+
+                let new_values: Vec<ProductAttributeValueEntity> = values
+                    .into_iter()
+                    .map(|(attribute_id, value)| {
+                        ProductAttributeValueEntity::new(product_id, attribute_id, value)
+                    })
+                    .collect();
+                diesel::insert_into(product_attribute_values::table)
+                    .values(&new_values)
+                    .execute(conn)?;
+
+                Self::load_product_attributes(conn, product_id)
+            })
+            .map_err(|err| match err {
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => ProductError::InvalidAttribute,
+                err => err.into(),
+            })
+    }
+}
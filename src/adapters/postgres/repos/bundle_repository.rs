@@ -0,0 +1,270 @@
+use std::ops::DerefMut;
+
+use diesel::{
+    Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+    r2d2::{ConnectionManager, Pool},
+    result::Error as DieselError,
+    upsert::excluded,
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::bundle::{BundleEntity, BundleItemEntity},
+        schema::{product_bundle_items, product_bundles},
+    },
+    core::{
+        models::{bundle::Bundle, product::ProductError},
+        ports::bundle_repository::BundleRepository,
+    },
+};
+
+pub struct BundleRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl BundleRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        BundleRepositoryImpl { conn }
+    }
+
+    fn load_bundle(conn: &mut PgConnection, product_id: i64) -> Result<Bundle, DieselError> {
+        let record = product_bundles::table
+            .filter(product_bundles::product_id.eq(product_id))
+            .first::<BundleEntity>(conn)?;
+        let items = product_bundle_items::table
+            .filter(product_bundle_items::bundle_id.eq(product_id))
+            .order(product_bundle_items::component_id.asc())
+            .load::<BundleItemEntity>(conn)?;
+
+        Ok(record.to_model(items.iter().map(|item| item.to_model()).collect()))
+    }
+}
+
+impl BundleRepository for BundleRepositoryImpl {
+    fn find_bundle_by_product_id(&mut self, product_id: i64) -> Result<Bundle, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        Self::load_bundle(conn.deref_mut(), product_id).map_err(Into::into)
+    }
+
+    fn find_bundle_ids_by_component_id(
+        &mut self,
+        component_id: i64,
+    ) -> Result<Vec<i64>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_bundle_items::table
+            .filter(product_bundle_items::component_id.eq(component_id))
+            .order(product_bundle_items::bundle_id.asc())
+            .select(product_bundle_items::bundle_id)
+            .load::<i64>(conn.deref_mut())
+            .map_err(Into::into)
+    }
+
+    fn save_bundle(&mut self, bundle: Bundle) -> Result<Bundle, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<Bundle, DieselError, _>(|conn| {
+                diesel::insert_into(product_bundles::table)
+                    .values(BundleEntity {
+                        product_id: bundle.product_id,
+                        discount_percent: bundle.discount_percent,
+                        created_at: bundle.created_at,
+                        updated_at: bundle.updated_at,
+                    })
+                    .on_conflict(product_bundles::product_id)
+                    .do_update()
+                    .set((
+                        product_bundles::discount_percent
+                            .eq(excluded(product_bundles::discount_percent)),
+                        product_bundles::updated_at.eq(excluded(product_bundles::updated_at)),
+                    ))
+                    .execute(conn)?;
+
+                diesel::delete(
+                    product_bundle_items::table
+                        .filter(product_bundle_items::bundle_id.eq(bundle.product_id)),
+                )
+                .execute(conn)?;
+
+                let items: Vec<BundleItemEntity> = bundle
+                    .items
+                    .iter()
+                    .map(|item| BundleItemEntity {
+                        bundle_id: bundle.product_id,
+                        component_id: item.component_id,
+                        quantity: item.quantity,
+                    })
+                    .collect();
+                diesel::insert_into(product_bundle_items::table)
+                    .values(&items)
+                    .execute(conn)?;
+
+                Self::load_bundle(conn, bundle.product_id)
+            })
+            .map_err(|err| match err {
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => ProductError::NotFound,
+                err => err.into(),
+            })
+    }
+
+    fn delete_bundle(&mut self, product_id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(product_bundles::table.filter(product_bundles::product_id.eq(product_id)))
+            .execute(conn.deref_mut())
+            .map(|affected_rows| {
+                if affected_rows == 0 {
+                    Err(ProductError::NotFound)
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|_| ProductError::InternalError)?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use diesel::{Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+
+    use crate::adapters::postgres::schema::{product_bundle_items, product_bundles, products};
+
+    /// Connection to the migrated database at `POSTGRES_TEST_URL`, inside a
+    /// transaction that is never committed. Tests needing one are skipped when
+    /// the variable is unset.
+    fn connection() -> Option<PgConnection> {
+        let Ok(url) = std::env::var("POSTGRES_TEST_URL") else {
+            eprintln!("POSTGRES_TEST_URL is not set, skipping");
+            return None;
+        };
+        let mut conn = PgConnection::establish(&url).unwrap();
+        conn.begin_test_transaction().unwrap();
+        Some(conn)
+    }
+
+    fn create_product(conn: &mut PgConnection, slug: &str, price: f64, stock: i32) -> i64 {
+        diesel::insert_into(products::table)
+            .values((
+                products::name.eq(slug),
+                products::description.eq(""),
+                products::price.eq(price),
+                products::stock.eq(stock),
+                products::slug.eq(slug),
+            ))
+            .returning(products::id)
+            .get_result(conn)
+            .unwrap()
+    }
+
+    fn create_bundle(
+        conn: &mut PgConnection,
+        product_id: i64,
+        discount_percent: Option<f64>,
+        items: &[(i64, i32)],
+    ) {
+        diesel::insert_into(product_bundles::table)
+            .values((
+                product_bundles::product_id.eq(product_id),
+                product_bundles::discount_percent.eq(discount_percent),
+            ))
+            .execute(conn)
+            .unwrap();
+        for (component_id, quantity) in items {
+            diesel::insert_into(product_bundle_items::table)
+                .values((
+                    product_bundle_items::bundle_id.eq(product_id),
+                    product_bundle_items::component_id.eq(component_id),
+                    product_bundle_items::quantity.eq(quantity),
+                ))
+                .execute(conn)
+                .unwrap();
+        }
+    }
+
+    fn stock_and_price(conn: &mut PgConnection, product_id: i64) -> (i32, f64) {
+        products::table
+            .filter(products::id.eq(product_id))
+            .select((products::stock, products::price))
+            .first(conn)
+            .unwrap()
+    }
+
+    #[test]
+    fn bundle_holds_complete_kits_at_the_discounted_total() {
+        let Some(mut conn) = connection() else {
+            return;
+        };
+        let frame = create_product(&mut conn, "bundle-test-kits-frame", 10.0, 7);
+        let wheel = create_product(&mut conn, "bundle-test-kits-wheel", 5.0, 9);
+        let bike = create_product(&mut conn, "bundle-test-kits-bike", 99.0, 0);
+        create_bundle(&mut conn, bike, Some(10.0), &[(frame, 2), (wheel, 3)]);
+
+        assert_eq!(stock_and_price(&mut conn, bike), (3, 31.5));
+    }
+
+    #[test]
+    fn bundle_without_discount_keeps_its_own_price() {
+        let Some(mut conn) = connection() else {
+            return;
+        };
+        let frame = create_product(&mut conn, "bundle-test-price-frame", 10.0, 7);
+        let bike = create_product(&mut conn, "bundle-test-price-bike", 99.0, 0);
+        create_bundle(&mut conn, bike, None, &[(frame, 2)]);
+
+        assert_eq!(stock_and_price(&mut conn, bike), (3, 99.0));
+    }
+
+    #[test]
+    fn component_changes_refresh_their_bundles() {
+        let Some(mut conn) = connection() else {
+            return;
+        };
+        let frame = create_product(&mut conn, "bundle-test-changes-frame", 10.0, 7);
+        let wheel = create_product(&mut conn, "bundle-test-changes-wheel", 5.0, 9);
+        let bike = create_product(&mut conn, "bundle-test-changes-bike", 99.0, 0);
+        create_bundle(&mut conn, bike, Some(50.0), &[(frame, 1), (wheel, 2)]);
+        assert_eq!(stock_and_price(&mut conn, bike), (4, 10.0));
+
+        diesel::update(products::table.filter(products::id.eq(frame)))
+            .set((products::stock.eq(2), products::price.eq(20.0)))
+            .execute(&mut conn)
+            .unwrap();
+        assert_eq!(stock_and_price(&mut conn, bike), (2, 15.0));
+
+        // Components in the trash count as out of stock.
+        diesel::update(products::table.filter(products::id.eq(wheel)))
+            .set(products::deleted_at.eq(Some(Utc::now().naive_utc())))
+            .execute(&mut conn)
+            .unwrap();
+        assert_eq!(stock_and_price(&mut conn, bike), (0, 15.0));
+    }
+
+    #[test]
+    fn bundle_stock_and_price_are_derived_again_when_set() {
+        let Some(mut conn) = connection() else {
+            return;
+        };
+        let frame = create_product(&mut conn, "bundle-test-derived-frame", 10.0, 7);
+        let bike = create_product(&mut conn, "bundle-test-derived-bike", 99.0, 0);
+        create_bundle(&mut conn, bike, Some(0.0), &[(frame, 1)]);
+
+        diesel::update(products::table.filter(products::id.eq(bike)))
+            .set((products::stock.eq(100), products::price.eq(1.0)))
+            .execute(&mut conn)
+            .unwrap();
+        assert_eq!(stock_and_price(&mut conn, bike), (7, 10.0));
+
+        diesel::delete(
+            product_bundle_items::table.filter(product_bundle_items::bundle_id.eq(bike)),
+        )
+        .execute(&mut conn)
+        .unwrap();
+        assert_eq!(stock_and_price(&mut conn, bike), (0, 0.0));
+    }
+}
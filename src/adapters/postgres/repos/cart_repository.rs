@@ -9,9 +9,10 @@ use crate::{
     },
 };
 use diesel::{
-    ExpressionMethods, PgConnection, RunQueryDsl,
-    query_dsl::methods::FilterDsl,
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, PgConnection,
+    PgExpressionMethods, QueryDsl, RunQueryDsl,
     r2d2::{ConnectionManager, Pool},
+    result::Error as DieselError,
 };
 use std::ops::DerefMut;
 
@@ -26,21 +27,25 @@ impl CartRepositoryImpl {
 }
 
 impl CartRepository for CartRepositoryImpl {
-    fn create_cart(&mut self, user_id: i64) -> Result<Cart, CartError> {
+    fn create_cart(
+        &mut self,
+        user_id: i64,
+        name: String,
+        is_active: bool,
+    ) -> Result<Cart, CartError> {
         let mut conn = self.conn.get().unwrap();
 
-        let new_cart = NewCartEntity { user_id };
+        let new_cart = NewCartEntity {
+            user_id,
+            name,
+            is_active,
+        };
 
         diesel::insert_into(carts::table)
             .values(&new_cart)
             .get_result::<CartEntity>(conn.deref_mut())
-            .map(|entity| Cart {
-                id: entity.id,
-                user_id: entity.user_id,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
-            .map_err(|_| CartError::DatabaseError)
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
     }
 
     fn find_cart_by_id(&mut self, id: i64) -> Result<Cart, CartError> {
@@ -49,30 +54,68 @@ impl CartRepository for CartRepositoryImpl {
         carts::table
             .filter(carts::id.eq(id))
             .first::<CartEntity>(conn.deref_mut())
-            .map(|entity| Cart {
-                id: entity.id,
-                user_id: entity.user_id,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| CartError::NotFound)
     }
 
-    fn find_carts_by_user_id(&mut self, user_id: i64) -> Result<Cart, CartError> {
+    fn find_carts_by_user_id(&mut self, user_id: i64) -> Result<Vec<Cart>, CartError> {
         let mut conn = self.conn.get().unwrap();
 
         carts::table
             .filter(carts::user_id.eq(user_id))
+            .order((carts::is_active.desc(), carts::created_at.asc()))
+            .load::<CartEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(|_| CartError::DatabaseError)
+    }
+
+    fn find_active_cart_by_user_id(&mut self, user_id: i64) -> Result<Cart, CartError> {
+        let mut conn = self.conn.get().unwrap();
+
+        carts::table
+            .filter(carts::user_id.eq(user_id).and(carts::is_active.eq(true)))
             .first::<CartEntity>(conn.deref_mut())
-            .map(|entity| Cart {
-                id: entity.id,
-                user_id: entity.user_id,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| CartError::NotFound)
     }
 
+    fn rename_cart(&mut self, id: i64, new_name: String) -> Result<Cart, CartError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(carts::table.filter(carts::id.eq(id)))
+            .set((
+                carts::name.eq(new_name),
+                carts::updated_at.eq(diesel::dsl::now),
+            ))
+            .get_result::<CartEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn set_active_cart(&mut self, user_id: i64, cart_id: i64) -> Result<Cart, CartError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<CartEntity, DieselError, _>(|conn| {
+                diesel::update(
+                    carts::table.filter(carts::user_id.eq(user_id).and(carts::is_active.eq(true))),
+                )
+                .set(carts::is_active.eq(false))
+                .execute(conn)?;
+
+                diesel::update(
+                    carts::table.filter(carts::id.eq(cart_id).and(carts::user_id.eq(user_id))),
+                )
+                .set((
+                    carts::is_active.eq(true),
+                    carts::updated_at.eq(diesel::dsl::now),
+                ))
+                .get_result::<CartEntity>(conn)
+            })
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
     fn delete_cart(&mut self, id: i64) -> Result<(), CartError> {
         let mut conn = self.conn.get().unwrap();
 
@@ -88,6 +131,27 @@ impl CartRepository for CartRepositoryImpl {
             .map_err(|_| CartError::DatabaseError)?
     }
 
+    fn find_cart_item_by_id(&mut self, cart_item_id: i64) -> Result<CartItem, CartError> {
+        let mut conn = self.conn.get().unwrap();
+
+        cart_items::table
+            .filter(cart_items::id.eq(cart_item_id))
+            .first::<CartItemEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(|_| CartError::NotFound)
+    }
+
+    fn find_cart_items_by_cart_id(&mut self, cart_id: i64) -> Result<Vec<CartItem>, CartError> {
+        let mut conn = self.conn.get().unwrap();
+
+        cart_items::table
+            .filter(cart_items::cart_id.eq(cart_id))
+            .order(cart_items::created_at.asc())
+            .load::<CartItemEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(|_| CartError::DatabaseError)
+    }
+
     fn add_cart_item(&mut self, cart_item: CartItem) -> Result<CartItem, CartError> {
         let mut conn = self.conn.get().unwrap();
 
@@ -95,20 +159,16 @@ impl CartRepository for CartRepositoryImpl {
             cart_id: cart_item.cart_id,
             product_id: cart_item.product_id,
             quantity: cart_item.quantity,
+            customizations: cart_item.customizations,
+            customization_price: cart_item.customization_price,
+            variant_id: cart_item.variant_id,
         };
 
         diesel::insert_into(cart_items::table)
             .values(&new_cart_item)
             .get_result::<CartItemEntity>(conn.deref_mut())
-            .map(|entity| CartItem {
-                id: entity.id,
-                cart_id: entity.cart_id,
-                product_id: entity.product_id,
-                quantity: entity.quantity,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
-            .map_err(|_| CartError::DatabaseError)
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
     }
 
     fn update_cart_item_quantity(
@@ -119,19 +179,63 @@ impl CartRepository for CartRepositoryImpl {
         let mut conn = self.conn.get().unwrap();
 
         diesel::update(cart_items::table.filter(cart_items::id.eq(cart_item_id)))
-            .set(cart_items::quantity.eq(new_quantity))
+            .set((
+                cart_items::quantity.eq(new_quantity),
+                cart_items::updated_at.eq(diesel::dsl::now),
+            ))
             .get_result::<CartItemEntity>(conn.deref_mut())
-            .map(|entity| CartItem {
-                id: entity.id,
-                cart_id: entity.cart_id,
-                product_id: entity.product_id,
-                quantity: entity.quantity,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| CartError::NotFound)
     }
 
+    fn move_cart_item(
+        &mut self,
+        cart_item_id: i64,
+        target_cart_id: i64,
+    ) -> Result<CartItem, CartError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<CartItemEntity, DieselError, _>(|conn| {
+                let item = cart_items::table
+                    .filter(cart_items::id.eq(cart_item_id))
+                    .first::<CartItemEntity>(conn)?;
+
+                let existing = cart_items::table
+                    .filter(
+                        cart_items::cart_id
+                            .eq(target_cart_id)
+                            .and(cart_items::product_id.eq(item.product_id))
+                            .and(cart_items::variant_id.is_not_distinct_from(item.variant_id))
+                            .and(cart_items::customizations.eq(&item.customizations)),
+                    )
+                    .first::<CartItemEntity>(conn)
+                    .optional()?;
+
+                match existing {
+                    Some(existing) => {
+                        diesel::delete(cart_items::table.filter(cart_items::id.eq(item.id)))
+                            .execute(conn)?;
+
+                        diesel::update(cart_items::table.filter(cart_items::id.eq(existing.id)))
+                            .set((
+                                cart_items::quantity.eq(existing.quantity + item.quantity),
+                                cart_items::updated_at.eq(diesel::dsl::now),
+                            ))
+                            .get_result::<CartItemEntity>(conn)
+                    }
+                    None => diesel::update(cart_items::table.filter(cart_items::id.eq(item.id)))
+                        .set((
+                            cart_items::cart_id.eq(target_cart_id),
+                            cart_items::updated_at.eq(diesel::dsl::now),
+                        ))
+                        .get_result::<CartItemEntity>(conn),
+                }
+            })
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
     fn remove_cart_item(&mut self, cart_item_id: i64) -> Result<(), CartError> {
         let mut conn = self.conn.get().unwrap();
 
@@ -1,11 +1,25 @@
 use crate::adapters::postgres::entities::category::{CategoryEntity, NewCategoryEntity};
+use crate::adapters::postgres::repos::attribute_repository::delete_stale_attribute_values;
 use crate::core::ports::category_repository::CategoryRepository;
 use crate::{
-    adapters::postgres::schema::categories,
-    core::models::category::{Category, CategoryError},
+    adapters::postgres::schema::{
+        attributes, categories, category_slug_redirects, product_attribute_values,
+        product_categories, product_variation_options, products, variation_options, variations,
+    },
+    core::models::{
+        category::{Category, CategoryError},
+        pagination::{Cursor, Page, Pagination},
+        seo::{SeoMetadata, SlugLookup},
+    },
 };
+use chrono::{NaiveDateTime, Utc};
+use diesel::pg::Pg;
 use diesel::r2d2::{ConnectionManager, Pool};
-use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, PgConnection,
+    QueryDsl, RunQueryDsl,
+};
 use std::ops::DerefMut;
 pub struct CategoryRepositoryImpl {
     conn: Pool<ConnectionManager<PgConnection>>,
@@ -15,18 +29,115 @@ impl CategoryRepositoryImpl {
     pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
         CategoryRepositoryImpl { conn }
     }
+
+    fn load_all_categories(&mut self) -> Result<Vec<Category>, CategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let records = categories::table
+            .filter(categories::deleted_at.is_null())
+            .load::<CategoryEntity>(conn.deref_mut())
+            .map_err(|_| CategoryError::InternalError)?;
+
+        let categories: Vec<Category> = records
+            .clone()
+            .into_iter()
+            .map(|entity| {
+                let mut parent: Option<Box<Category>> = None;
+                if let Some(parent_id) = entity.parent_id {
+                    let record = records.iter().find(|&key| key.id == parent_id);
+                    if let Some(record) = record {
+                        parent = Some(Box::new(record.to_model(None)));
+                    }
+                }
+
+                entity.to_model(parent)
+            })
+            .collect();
+
+        Ok(categories)
+    }
+
+    /// Returns a page of the categories in the trash or out of it, oldest first.
+    fn load_page(
+        &mut self,
+        in_trash: bool,
+        pagination: Pagination,
+    ) -> Result<Page<Category>, CategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let mut query = filter_trash(categories::table.into_boxed(), in_trash)
+            .order((categories::created_at.asc(), categories::id.asc()))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                categories::created_at
+                    .gt(after.created_at)
+                    .or(categories::created_at
+                        .eq(after.created_at)
+                        .and(categories::id.gt(after.id))),
+            );
+        }
+        let records = query
+            .load::<CategoryEntity>(conn.deref_mut())
+            .map_err(|_| CategoryError::InternalError)?;
+
+        let parent_ids: Vec<i64> = records
+            .iter()
+            .filter_map(|record| record.parent_id)
+            .collect();
+        let parents = categories::table
+            .filter(categories::id.eq_any(parent_ids))
+            .filter(categories::deleted_at.is_null())
+            .load::<CategoryEntity>(conn.deref_mut())
+            .map_err(|_| CategoryError::InternalError)?;
+
+        let categories: Vec<Category> = records
+            .iter()
+            .map(|entity| {
+                let parent = entity.parent_id.and_then(|parent_id| {
+                    parents
+                        .iter()
+                        .find(|parent| parent.id == parent_id)
+                        .map(|parent| Box::new(parent.to_model(None)))
+                });
+                entity.to_model(parent)
+            })
+            .collect();
+
+        let total = if pagination.with_total {
+            let total = filter_trash(categories::table.into_boxed(), in_trash)
+                .count()
+                .get_result::<i64>(conn.deref_mut())
+                .map_err(|_| CategoryError::InternalError)?;
+            Some(total)
+        } else {
+            None
+        };
+
+        Ok(Page::new(
+            categories,
+            &pagination,
+            total,
+            |category: &Category| Cursor {
+                created_at: category.created_at,
+                id: category.id,
+                key: None,
+            },
+        ))
+    }
 }
 
 impl CategoryRepository for CategoryRepositoryImpl {
     fn create_category(
         &mut self,
         name: String,
+        slug: String,
         description: String,
         parent_id: Option<i64>,
     ) -> Result<Category, CategoryError> {
         let mut parent: Option<Box<Category>> = None;
         if let Some(parent_id) = parent_id {
-            let records = self.find_all_categories()?;
+            let records = self.load_all_categories()?;
             let record = records.iter().find(|&key| key.id == parent_id);
             if let Some(record) = record {
                 parent = Some(Box::new(record.clone()));
@@ -34,51 +145,150 @@ impl CategoryRepository for CategoryRepositoryImpl {
         }
 
         let mut conn = self.conn.get().unwrap();
-        Ok(diesel::insert_into(categories::table)
+        diesel::insert_into(categories::table)
             .values(NewCategoryEntity {
                 name,
+                slug,
                 description,
                 parent_id,
             })
             .get_result::<CategoryEntity>(conn.deref_mut())
             .map(|entity| entity.to_model(parent))
-            .map_err(|_| CategoryError::InternalError)?)
+            .map_err(map_slug_error)
     }
 
     fn find_category_by_id(&mut self, id: i64) -> Result<Category, CategoryError> {
-        let records: Vec<Category> = self.find_all_categories()?;
+        let mut conn = self.conn.get().unwrap();
 
-        if let Some(category) = records.iter().find(|&key| key.id == id) {
-            Ok(category.clone())
-        } else {
-            Err(CategoryError::NotFound)
+        let mut find_entity = |id: i64| {
+            categories::table
+                .find(id)
+                .filter(categories::deleted_at.is_null())
+                .first::<CategoryEntity>(conn.deref_mut())
+                .optional()
+                .map_err(|_| CategoryError::InternalError)
+        };
+        let entity = find_entity(id)?.ok_or(CategoryError::NotFound)?;
+        let parent = match entity.parent_id {
+            Some(parent_id) => {
+                find_entity(parent_id)?.map(|parent| Box::new(parent.to_model(None)))
+            }
+            None => None,
+        };
+
+        Ok(entity.to_model(parent))
+    }
+
+    fn resolve_category_slug(&mut self, slug: &str) -> Result<SlugLookup<i64>, CategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let id = categories::table
+            .filter(categories::slug.eq(slug))
+            .filter(categories::deleted_at.is_null())
+            .select(categories::id)
+            .first::<i64>(conn.deref_mut())
+            .optional()
+            .map_err(|_| CategoryError::InternalError)?;
+        if let Some(id) = id {
+            return Ok(SlugLookup::Found(id));
         }
+
+        category_slug_redirects::table
+            .inner_join(categories::table)
+            .filter(category_slug_redirects::slug.eq(slug))
+            .filter(categories::deleted_at.is_null())
+            .select(categories::slug)
+            .first::<String>(conn.deref_mut())
+            .map(SlugLookup::Moved)
+            .map_err(|err| match err {
+                DieselError::NotFound => CategoryError::NotFound,
+                _ => CategoryError::InternalError,
+            })
     }
 
-    fn find_all_categories(&mut self) -> Result<Vec<Category>, CategoryError> {
+    fn is_category_slug_taken(
+        &mut self,
+        slug: &str,
+        except_category_id: Option<i64>,
+    ) -> Result<bool, CategoryError> {
         let mut conn = self.conn.get().unwrap();
 
-        let records = categories::table
-            .load::<CategoryEntity>(conn.deref_mut())
+        let mut current = categories::table
+            .filter(categories::slug.eq(slug))
+            .into_boxed();
+        let mut former = category_slug_redirects::table
+            .filter(category_slug_redirects::slug.eq(slug))
+            .into_boxed();
+        if let Some(category_id) = except_category_id {
+            current = current.filter(categories::id.ne(category_id));
+            former = former.filter(category_slug_redirects::category_id.ne(category_id));
+        }
+
+        let current = current
+            .count()
+            .get_result::<i64>(conn.deref_mut())
+            .map_err(|_| CategoryError::InternalError)?;
+        let former = former
+            .count()
+            .get_result::<i64>(conn.deref_mut())
             .map_err(|_| CategoryError::InternalError)?;
+        Ok(current + former > 0)
+    }
 
-        let categories: Vec<Category> = records
-            .clone()
-            .into_iter()
-            .map(|entity| {
-                let mut parent: Option<Box<Category>> = None;
-                if let Some(parent_id) = entity.parent_id.clone() {
-                    let record = records.iter().find(|&key| key.id == parent_id);
-                    if let Some(record) = record {
-                        parent = Some(Box::new(record.to_model(None)));
+    fn update_category_seo(
+        &mut self,
+        category_id: i64,
+        new_slug: String,
+        new_seo: SeoMetadata,
+    ) -> Result<Category, CategoryError> {
+        let record = {
+            let mut conn = self.conn.get().unwrap();
+            conn.deref_mut()
+                .transaction::<CategoryEntity, DieselError, _>(|conn| {
+                    let previous = categories::table
+                        .filter(categories::id.eq(category_id))
+                        .filter(categories::deleted_at.is_null())
+                        .first::<CategoryEntity>(conn)?;
+
+                    if previous.slug != new_slug {
+                        // The new slug may be one the category was renamed from.
+                        diesel::delete(
+                            category_slug_redirects::table
+                                .filter(category_slug_redirects::slug.eq(&new_slug))
+                                .filter(category_slug_redirects::category_id.eq(category_id)),
+                        )
+                        .execute(conn)?;
+                        diesel::insert_into(category_slug_redirects::table)
+                            .values((
+                                category_slug_redirects::slug.eq(&previous.slug),
+                                category_slug_redirects::category_id.eq(category_id),
+                            ))
+                            .on_conflict(category_slug_redirects::slug)
+                            .do_update()
+                            .set(category_slug_redirects::category_id.eq(category_id))
+                            .execute(conn)?;
                     }
-                }
 
-                entity.to_model(parent)
-            })
-            .collect();
+                    diesel::update(categories::table.filter(categories::id.eq(category_id)))
+                        .set((
+                            categories::slug.eq(&new_slug),
+                            categories::meta_title.eq(new_seo.meta_title),
+                            categories::meta_description.eq(new_seo.meta_description),
+                            categories::canonical_url.eq(new_seo.canonical_url),
+                        ))
+                        .get_result::<CategoryEntity>(conn)
+                })
+                .map_err(map_slug_error)?
+        };
 
-        Ok(categories)
+        self.find_category_by_id(record.id)
+    }
+
+    fn find_all_categories(
+        &mut self,
+        pagination: Pagination,
+    ) -> Result<Page<Category>, CategoryError> {
+        self.load_page(false, pagination)
     }
 
     fn update_category(
@@ -90,13 +300,44 @@ impl CategoryRepository for CategoryRepositoryImpl {
     ) -> Result<Category, CategoryError> {
         let record = {
             let mut conn = self.conn.get().unwrap();
-            diesel::update(categories::table.filter(categories::id.eq(category_id)))
-                .set((
-                    categories::name.eq(new_name),
-                    categories::description.eq(new_description),
-                    categories::parent_id.eq(new_parent_id),
-                ))
-                .get_result::<CategoryEntity>(conn.deref_mut())
+            conn.deref_mut()
+                .transaction::<CategoryEntity, DieselError, _>(|conn| {
+                    let parent_id = categories::table
+                        .filter(categories::id.eq(category_id))
+                        .filter(categories::deleted_at.is_null())
+                        .select(categories::parent_id)
+                        .first::<Option<i64>>(conn)?;
+                    let record =
+                        diesel::update(categories::table.filter(categories::id.eq(category_id)))
+                            .set((
+                                categories::name.eq(new_name),
+                                categories::description.eq(new_description),
+                                categories::parent_id.eq(new_parent_id),
+                            ))
+                            .get_result::<CategoryEntity>(conn)?;
+
+                    // Products of the moved categories lose the attributes of
+                    // their former ancestors.
+                    if parent_id != new_parent_id {
+                        let mut tree = vec![category_id];
+                        let mut level = vec![category_id];
+                        while !level.is_empty() {
+                            level = categories::table
+                                .filter(categories::parent_id.eq_any(&level))
+                                .filter(diesel::dsl::not(categories::id.eq_any(&tree)))
+                                .select(categories::id)
+                                .load::<i64>(conn)?;
+                            tree.extend(&level);
+                        }
+                        let product_ids = product_categories::table
+                            .filter(product_categories::category_id.eq_any(&tree))
+                            .select(product_categories::product_id)
+                            .distinct()
+                            .load::<i64>(conn)?;
+                        delete_stale_attribute_values(conn, &product_ids)?;
+                    }
+                    Ok(record)
+                })
                 .map_err(|_| CategoryError::NotFound)?
         };
 
@@ -106,15 +347,126 @@ impl CategoryRepository for CategoryRepositoryImpl {
     fn delete_category(&mut self, id: i64) -> Result<(), CategoryError> {
         let mut conn = self.conn.get().unwrap();
 
-        diesel::delete(categories::table.filter(categories::id.eq(id)))
-            .execute(conn.deref_mut())
-            .map(|affected_rows| {
-                if affected_rows == 0 {
-                    Err(CategoryError::NotFound)
-                } else {
-                    Ok(())
-                }
+        diesel::update(
+            categories::table
+                .filter(categories::id.eq(id))
+                .filter(categories::deleted_at.is_null()),
+        )
+        .set(categories::deleted_at.eq(Utc::now().naive_utc()))
+        .execute(conn.deref_mut())
+        .map(|affected_rows| {
+            if affected_rows == 0 {
+                Err(CategoryError::NotFound)
+            } else {
+                Ok(())
+            }
+        })
+        .map_err(|_| CategoryError::InternalError)?
+    }
+
+    fn restore_category(&mut self, category_id: i64) -> Result<Category, CategoryError> {
+        let record = {
+            let mut conn = self.conn.get().unwrap();
+            diesel::update(
+                categories::table
+                    .filter(categories::id.eq(category_id))
+                    .filter(categories::deleted_at.is_not_null()),
+            )
+            .set(categories::deleted_at.eq(None::<NaiveDateTime>))
+            .get_result::<CategoryEntity>(conn.deref_mut())
+            .map_err(|err| match err {
+                DieselError::NotFound => CategoryError::NotFound,
+                _ => CategoryError::InternalError,
+            })?
+        };
+
+        self.find_category_by_id(record.id)
+    }
+
+    fn find_deleted_categories(
+        &mut self,
+        pagination: Pagination,
+    ) -> Result<Page<Category>, CategoryError> {
+        self.load_page(true, pagination)
+    }
+
+    fn purge_deleted_categories(
+        &mut self,
+        deleted_before: NaiveDateTime,
+    ) -> Result<usize, CategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<usize, DieselError, _>(|conn| {
+                let mut referenced = products::table
+                    .filter(products::category_id.is_not_null())
+                    .select(products::category_id)
+                    .distinct()
+                    .load::<Option<i64>>(conn)?;
+                referenced.extend(
+                    categories::table
+                        .filter(categories::parent_id.is_not_null())
+                        .select(categories::parent_id)
+                        .distinct()
+                        .load::<Option<i64>>(conn)?,
+                );
+                let mut referenced: Vec<i64> = referenced.into_iter().flatten().collect();
+                // Deleting a category cascades to its product links, variations
+                // and attributes, which products may still use.
+                referenced.extend(
+                    product_categories::table
+                        .select(product_categories::category_id)
+                        .distinct()
+                        .load::<i64>(conn)?,
+                );
+                referenced.extend(
+                    variations::table
+                        .inner_join(
+                            variation_options::table.inner_join(product_variation_options::table),
+                        )
+                        .select(variations::category_id)
+                        .distinct()
+                        .load::<i64>(conn)?,
+                );
+                referenced.extend(
+                    attributes::table
+                        .inner_join(product_attribute_values::table)
+                        .select(attributes::category_id)
+                        .distinct()
+                        .load::<i64>(conn)?,
+                );
+
+                diesel::delete(
+                    categories::table
+                        .filter(categories::deleted_at.lt(deleted_before))
+                        .filter(diesel::dsl::not(categories::id.eq_any(referenced))),
+                )
+                .execute(conn)
             })
-            .map_err(|_| CategoryError::InternalError)?
+            .map_err(|_| CategoryError::InternalError)
+    }
+}
+
+/// Narrows `query` down to the categories in the trash or out of it.
+fn filter_trash(
+    query: categories::BoxedQuery<'static, Pg>,
+    in_trash: bool,
+) -> categories::BoxedQuery<'static, Pg> {
+    if in_trash {
+        query.filter(categories::deleted_at.is_not_null())
+    } else {
+        query.filter(categories::deleted_at.is_null())
+    }
+}
+
+fn map_slug_error(error: DieselError) -> CategoryError {
+    match error {
+        DieselError::NotFound => CategoryError::NotFound,
+        DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info)
+            if info.constraint_name() == Some("uq_categories_slug") =>
+        {
+            CategoryError::SlugTaken
+        }
+        _ => CategoryError::InternalError,
     }
 }
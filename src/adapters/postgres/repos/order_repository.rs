@@ -1,13 +1,26 @@
-use crate::adapters::postgres::entities::order::{OrderEntity, OrderItemEntity};
+use crate::adapters::postgres::entities::{
+    download::DownloadGrantEntity,
+    order::{NewOrderEntity, NewOrderItemEntity, OrderEntity, OrderItemEntity},
+};
 use crate::core::ports::order_repository::OrderRepository;
 use crate::{
-    adapters::postgres::schema::{order_items, orders},
-    core::models::order::{Order, OrderError, OrderItem, OrderStatus},
+    adapters::postgres::schema::{
+        cart_items, order_item_downloads, order_items, orders, product_variants, products,
+    },
+    core::models::{
+        download::DownloadGrant,
+        order::{Order, OrderError, OrderItem, OrderStatus, StockReservation},
+        pagination::{Cursor, Page, Pagination},
+    },
 };
+use chrono::NaiveDateTime;
 use diesel::r2d2::{ConnectionManager, Pool};
-use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use diesel::result::Error as DieselError;
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, PgConnection,
+    QueryDsl, RunQueryDsl, SelectableHelper,
+};
 use std::ops::DerefMut;
-use std::str::FromStr;
 
 pub struct OrderRepositoryImpl {
     conn: Pool<ConnectionManager<PgConnection>>,
@@ -24,65 +37,149 @@ impl OrderRepository for OrderRepositoryImpl {
         let mut conn = self.conn.get().unwrap();
 
         diesel::insert_into(orders::table)
-            .values(OrderEntity {
-                id: order.id,
+            .values(NewOrderEntity {
                 user_id: order.user_id,
                 total_amount: order.total_amount,
                 status: order.status.to_string(),
-                created_at: order.created_at,
-                updated_at: order.updated_at,
+                requires_shipping: order.requires_shipping,
             })
             .get_result::<OrderEntity>(conn.deref_mut())
-            .map(|entity| Order {
-                id: entity.id,
-                user_id: entity.user_id,
-                total_amount: entity.total_amount,
-                status: order.status,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| OrderError::DatabaseError)
     }
 
+    fn place_order(
+        &mut self,
+        order: Order,
+        items: Vec<OrderItem>,
+        reservations: Vec<StockReservation>,
+        cart_item_ids: Vec<i64>,
+    ) -> Result<(Order, Vec<OrderItem>), OrderError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<(OrderEntity, Vec<OrderItemEntity>), DieselError, _>(|conn| {
+                for reservation in reservations {
+                    let affected_rows = match reservation {
+                        StockReservation::Product(product_id, quantity) => diesel::update(
+                            products::table
+                                .filter(products::id.eq(product_id))
+                                .filter(products::stock.ge(quantity)),
+                        )
+                        .set(products::stock.eq(products::stock - quantity))
+                        .execute(conn)?,
+                        StockReservation::Variant(variant_id, quantity) => diesel::update(
+                            product_variants::table
+                                .filter(product_variants::id.eq(variant_id))
+                                .filter(product_variants::stock.ge(quantity)),
+                        )
+                        .set(product_variants::stock.eq(product_variants::stock - quantity))
+                        .execute(conn)?,
+                    };
+                    if affected_rows == 0 {
+                        return Err(DieselError::RollbackTransaction);
+                    }
+                }
+
+                let record = diesel::insert_into(orders::table)
+                    .values(NewOrderEntity {
+                        user_id: order.user_id,
+                        total_amount: order.total_amount,
+                        status: order.status.to_string(),
+                        requires_shipping: order.requires_shipping,
+                    })
+                    .get_result::<OrderEntity>(conn)?;
+
+                let new_items: Vec<NewOrderItemEntity> = items
+                    .into_iter()
+                    .map(|item| NewOrderItemEntity {
+                        order_id: record.id,
+                        product_id: item.product_id,
+                        quantity: item.quantity,
+                        price_at_time_of_order: item.price_at_time_of_order,
+                        customizations: item.customizations,
+                        variant_id: item.variant_id,
+                    })
+                    .collect();
+                let item_records = diesel::insert_into(order_items::table)
+                    .values(&new_items)
+                    .get_results::<OrderItemEntity>(conn)?;
+
+                diesel::delete(cart_items::table.filter(cart_items::id.eq_any(cart_item_ids)))
+                    .execute(conn)?;
+
+                Ok((record, item_records))
+            })
+            .map(|(record, item_records)| {
+                (
+                    record.to_model(),
+                    item_records
+                        .iter()
+                        .map(|entity| entity.to_model())
+                        .collect(),
+                )
+            })
+            .map_err(|err| match err {
+                // A stock would drop below zero, or the product or variant is gone.
+                DieselError::RollbackTransaction => OrderError::OutOfStock,
+                _ => OrderError::DatabaseError,
+            })
+    }
+
     fn find_order_by_id(&mut self, id: i64) -> Result<Order, OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
 
         orders::table
             .filter(orders::id.eq(id))
             .first::<OrderEntity>(conn.deref_mut())
-            .map(|entity| Order {
-                id: entity.id,
-                user_id: entity.user_id,
-                total_amount: entity.total_amount,
-                status: OrderStatus::from_str(&entity.status).unwrap_or(OrderStatus::Error),
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| OrderError::NotFound)
     }
 
-    fn find_orders_by_user_id(&mut self, user_id: i64) -> Result<Vec<Order>, OrderError> {
+    fn find_orders_by_user_id(
+        &mut self,
+        user_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<Order>, OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
 
-        orders::table
+        let mut query = orders::table
             .filter(orders::user_id.eq(user_id))
+            .order((orders::created_at.desc(), orders::id.desc()))
+            .limit(pagination.fetch_limit())
+            .into_boxed();
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                orders::created_at
+                    .lt(after.created_at)
+                    .or(orders::created_at
+                        .eq(after.created_at)
+                        .and(orders::id.lt(after.id))),
+            );
+        }
+        let orders = query
             .load::<OrderEntity>(conn.deref_mut())
-            .map(|entities| {
-                entities
-                    .into_iter()
-                    .map(|entity| Order {
-                        id: entity.id,
-                        user_id: entity.user_id,
-                        total_amount: entity.total_amount,
-                        status: OrderStatus::from_str(&entity.status).unwrap_or(OrderStatus::Error),
-                        created_at: entity.created_at,
-                        updated_at: entity.updated_at,
-                    })
-                    .collect()
-            })
-            .map_err(|_| OrderError::DatabaseError)
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(|_| OrderError::DatabaseError)?;
+
+        let total = if pagination.with_total {
+            let total = orders::table
+                .filter(orders::user_id.eq(user_id))
+                .count()
+                .get_result::<i64>(conn.deref_mut())
+                .map_err(|_| OrderError::DatabaseError)?;
+            Some(total)
+        } else {
+            None
+        };
+
+        Ok(Page::new(orders, &pagination, total, |order: &Order| {
+            Cursor {
+                created_at: order.created_at,
+                id: order.id,
+                key: None,
+            }
+        }))
     }
 
     fn update_order_status(
@@ -91,25 +188,67 @@ impl OrderRepository for OrderRepositoryImpl {
         new_status: OrderStatus,
     ) -> Result<Order, OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
 
         diesel::update(orders::table.filter(orders::id.eq(id)))
             .set(orders::status.eq(new_status.to_string()))
             .get_result::<OrderEntity>(conn.deref_mut())
-            .map(|entity| Order {
-                id: entity.id,
-                user_id: entity.user_id,
-                total_amount: entity.total_amount,
-                status: OrderStatus::from_str(&entity.status).unwrap_or(OrderStatus::Error),
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| OrderError::NotFound)
     }
 
+    fn mark_order_paid(
+        &mut self,
+        id: i64,
+        paid_at: NaiveDateTime,
+        new_status: OrderStatus,
+        grants: Vec<DownloadGrant>,
+    ) -> Result<Order, OrderError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<OrderEntity, DieselError, _>(|conn| {
+                let record = diesel::update(
+                    orders::table
+                        .filter(orders::id.eq(id))
+                        .filter(orders::status.eq(OrderStatus::Pending.to_string())),
+                )
+                .set((
+                    orders::status.eq(new_status.to_string()),
+                    orders::paid_at.eq(Some(paid_at)),
+                    orders::updated_at.eq(paid_at),
+                ))
+                .get_result::<OrderEntity>(conn)
+                .optional()?
+                .ok_or(DieselError::RollbackTransaction)?;
+
+                let grants: Vec<DownloadGrantEntity> = grants
+                    .into_iter()
+                    .map(|grant| DownloadGrantEntity {
+                        order_item_id: grant.order_item_id,
+                        max_downloads: grant.max_downloads,
+                        download_count: grant.download_count,
+                        expires_at: grant.expires_at,
+                        created_at: paid_at,
+                        updated_at: paid_at,
+                    })
+                    .collect();
+                diesel::insert_into(order_item_downloads::table)
+                    .values(&grants)
+                    .execute(conn)?;
+
+                Ok(record)
+            })
+            .map(|entity| entity.to_model())
+            .map_err(|err| match err {
+                // Paid, cancelled or missing in the meantime.
+                DieselError::RollbackTransaction => OrderError::InvalidStatusTransition,
+                _ => OrderError::DatabaseError,
+            })
+    }
+
     fn delete_order(&mut self, id: i64) -> Result<(), OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
+
         diesel::delete(orders::table.filter(orders::id.eq(id)))
             .execute(conn.deref_mut())
             .map(|affected_rows| {
@@ -124,27 +263,18 @@ impl OrderRepository for OrderRepositoryImpl {
 
     fn add_order_item(&mut self, order_item: OrderItem) -> Result<OrderItem, OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
+
         diesel::insert_into(order_items::table)
-            .values(&OrderItemEntity {
-                id: order_item.id,
+            .values(&NewOrderItemEntity {
                 order_id: order_item.order_id,
                 product_id: order_item.product_id,
                 quantity: order_item.quantity,
                 price_at_time_of_order: order_item.price_at_time_of_order,
-                created_at: order_item.created_at,
-                updated_at: order_item.updated_at,
+                customizations: order_item.customizations,
+                variant_id: order_item.variant_id,
             })
             .get_result::<OrderItemEntity>(conn.deref_mut())
-            .map(|entity| OrderItem {
-                id: entity.id,
-                order_id: entity.order_id,
-                product_id: entity.product_id,
-                quantity: entity.quantity,
-                price_at_time_of_order: entity.price_at_time_of_order,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| OrderError::DatabaseError)
     }
 
@@ -153,52 +283,41 @@ impl OrderRepository for OrderRepositoryImpl {
         order_id: i64,
     ) -> Result<Vec<OrderItem>, OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
+
         order_items::table
             .filter(order_items::order_id.eq(order_id))
             .load::<OrderItemEntity>(conn.deref_mut())
-            .map(|entities| {
-                entities
-                    .into_iter()
-                    .map(|entity| OrderItem {
-                        id: entity.id,
-                        order_id: entity.order_id,
-                        product_id: entity.product_id,
-                        quantity: entity.quantity,
-                        price_at_time_of_order: entity.price_at_time_of_order,
-                        created_at: entity.created_at,
-                        updated_at: entity.updated_at,
-                    })
-                    .collect()
-            })
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
             .map_err(|_| OrderError::DatabaseError)
     }
 
+    fn find_order_item_by_id(&mut self, order_item_id: i64) -> Result<OrderItem, OrderError> {
+        let mut conn = self.conn.get().unwrap();
+
+        order_items::table
+            .filter(order_items::id.eq(order_item_id))
+            .first::<OrderItemEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(|_| OrderError::NotFound)
+    }
+
     fn update_order_item_quantity(
         &mut self,
         order_item_id: i64,
         new_quantity: i32,
     ) -> Result<OrderItem, OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
+
         diesel::update(order_items::table.filter(order_items::id.eq(order_item_id)))
             .set(order_items::quantity.eq(new_quantity))
             .get_result::<OrderItemEntity>(conn.deref_mut())
-            .map(|entity| OrderItem {
-                id: entity.id,
-                order_id: entity.order_id,
-                product_id: entity.product_id,
-                quantity: entity.quantity,
-                price_at_time_of_order: entity.price_at_time_of_order,
-                created_at: entity.created_at,
-                updated_at: entity.updated_at,
-            })
+            .map(|entity| entity.to_model())
             .map_err(|_| OrderError::NotFound)
     }
 
     fn remove_order_item(&mut self, order_item_id: i64) -> Result<(), OrderError> {
         let mut conn = self.conn.get().unwrap();
-        
+
         diesel::delete(order_items::table.filter(order_items::id.eq(order_item_id)))
             .execute(conn.deref_mut())
             .map(|affected_rows| {
@@ -210,4 +329,72 @@ impl OrderRepository for OrderRepositoryImpl {
             })
             .map_err(|_| OrderError::DatabaseError)?
     }
+
+    fn has_delivered_product(&mut self, user_id: i64, product_id: i64) -> Result<bool, OrderError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::select(diesel::dsl::exists(
+            order_items::table
+                .inner_join(orders::table)
+                .filter(orders::user_id.eq(user_id))
+                .filter(orders::status.eq(OrderStatus::Delivered.to_string()))
+                .filter(order_items::product_id.eq(product_id)),
+        ))
+        .get_result::<bool>(conn.deref_mut())
+        .map_err(|_| OrderError::DatabaseError)
+    }
+
+    fn find_download_grants_by_order_id(
+        &mut self,
+        order_id: i64,
+    ) -> Result<Vec<DownloadGrant>, OrderError> {
+        let mut conn = self.conn.get().unwrap();
+
+        order_item_downloads::table
+            .inner_join(order_items::table)
+            .filter(order_items::order_id.eq(order_id))
+            .order(order_item_downloads::order_item_id.asc())
+            .select(DownloadGrantEntity::as_select())
+            .load::<DownloadGrantEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(|_| OrderError::DatabaseError)
+    }
+
+    fn record_download(
+        &mut self,
+        order_item_id: i64,
+        now: NaiveDateTime,
+    ) -> Result<DownloadGrant, OrderError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let record = diesel::update(
+            order_item_downloads::table
+                .filter(order_item_downloads::order_item_id.eq(order_item_id))
+                .filter(order_item_downloads::expires_at.gt(now))
+                .filter(
+                    order_item_downloads::download_count.lt(order_item_downloads::max_downloads),
+                ),
+        )
+        .set((
+            order_item_downloads::download_count.eq(order_item_downloads::download_count + 1),
+            order_item_downloads::updated_at.eq(now),
+        ))
+        .get_result::<DownloadGrantEntity>(conn.deref_mut())
+        .optional()
+        .map_err(|_| OrderError::DatabaseError)?;
+        if let Some(record) = record {
+            return Ok(record.to_model());
+        }
+
+        // Tell why the download was refused.
+        let grant = order_item_downloads::table
+            .filter(order_item_downloads::order_item_id.eq(order_item_id))
+            .first::<DownloadGrantEntity>(conn.deref_mut())
+            .map_err(|_| OrderError::NotFound)?;
+        if grant.expires_at <= now {
+            Err(OrderError::DownloadExpired)
+        } else {
+            Err(OrderError::DownloadLimitReached)
+        }
+    }
 }
@@ -0,0 +1,230 @@
+use std::ops::DerefMut;
+
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+    r2d2::{ConnectionManager, Pool},
+    result::Error as DieselError,
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::product_category::{NewProductCategoryEntity, ProductCategoryEntity},
+        schema::{product_categories, products},
+    },
+    core::{
+        models::product_category::{ProductCategory, ProductCategoryError},
+        ports::product_repository::ProductCategoryRepository,
+    },
+};
+
+pub struct ProductCategoryRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ProductCategoryRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        ProductCategoryRepositoryImpl { conn }
+    }
+
+    /// Clears the current primary assignment of a product.
+    fn unset_primary(conn: &mut PgConnection, product_id: i64) -> Result<(), DieselError> {
+        diesel::update(
+            product_categories::table.filter(
+                product_categories::product_id
+                    .eq(product_id)
+                    .and(product_categories::is_primary.eq(true)),
+            ),
+        )
+        .set((
+            product_categories::is_primary.eq(false),
+            product_categories::updated_at.eq(diesel::dsl::now),
+        ))
+        .execute(conn)?;
+        Ok(())
+    }
+
+    /// Mirrors the primary category into `products.category_id`.
+    fn sync_product_category(
+        conn: &mut PgConnection,
+        product_id: i64,
+        category_id: Option<i64>,
+    ) -> Result<(), DieselError> {
+        diesel::update(products::table.filter(products::id.eq(product_id)))
+            .set((
+                products::category_id.eq(category_id),
+                products::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+        Ok(())
+    }
+}
+
+impl ProductCategoryRepository for ProductCategoryRepositoryImpl {
+    fn create_product_category(
+        &self,
+        product_category: ProductCategory,
+    ) -> Result<ProductCategory, ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<ProductCategoryEntity, DieselError, _>(|conn| {
+                if product_category.is_primary {
+                    Self::unset_primary(conn, product_category.product_id)?;
+                }
+
+                let record = diesel::insert_into(product_categories::table)
+                    .values(NewProductCategoryEntity {
+                        product_id: product_category.product_id,
+                        category_id: product_category.category_id,
+                        is_primary: product_category.is_primary,
+                    })
+                    .get_result::<ProductCategoryEntity>(conn)?;
+
+                if record.is_primary {
+                    Self::sync_product_category(conn, record.product_id, Some(record.category_id))?;
+                }
+                Ok(record)
+            })
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_product_category_by_id(
+        &self,
+        id: i64,
+    ) -> Result<ProductCategory, ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_categories::table
+            .filter(product_categories::id.eq(id))
+            .first::<ProductCategoryEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_categories_by_product_id(
+        &self,
+        product_id: i64,
+    ) -> Result<Vec<ProductCategory>, ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_categories::table
+            .filter(product_categories::product_id.eq(product_id))
+            .order((
+                product_categories::is_primary.desc(),
+                product_categories::created_at.asc(),
+            ))
+            .load::<ProductCategoryEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn find_products_by_category_id(
+        &self,
+        category_id: i64,
+    ) -> Result<Vec<ProductCategory>, ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_categories::table
+            .filter(product_categories::category_id.eq(category_id))
+            .order(product_categories::created_at.asc())
+            .load::<ProductCategoryEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn set_primary_product_category(
+        &self,
+        product_id: i64,
+        category_id: i64,
+    ) -> Result<ProductCategory, ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<ProductCategoryEntity, DieselError, _>(|conn| {
+                Self::unset_primary(conn, product_id)?;
+
+                let record = diesel::insert_into(product_categories::table)
+                    .values(NewProductCategoryEntity {
+                        product_id,
+                        category_id,
+                        is_primary: true,
+                    })
+                    .on_conflict((
+                        product_categories::product_id,
+                        product_categories::category_id,
+                    ))
+                    .do_update()
+                    .set((
+                        product_categories::is_primary.eq(true),
+                        product_categories::updated_at.eq(diesel::dsl::now),
+                    ))
+                    .get_result::<ProductCategoryEntity>(conn)?;
+
+                Self::sync_product_category(conn, product_id, Some(category_id))?;
+                Ok(record)
+            })
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn delete_product_category(&self, id: i64) -> Result<(), ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<(), DieselError, _>(|conn| {
+                let record =
+                    diesel::delete(product_categories::table.filter(product_categories::id.eq(id)))
+                        .get_result::<ProductCategoryEntity>(conn)?;
+
+                if record.is_primary {
+                    Self::sync_product_category(conn, record.product_id, None)?;
+                }
+                Ok(())
+            })
+            .map_err(Into::into)
+    }
+
+    fn delete_product_categories_by_product_id(
+        &self,
+        product_id: i64,
+    ) -> Result<(), ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<(), DieselError, _>(|conn| {
+                diesel::delete(
+                    product_categories::table.filter(product_categories::product_id.eq(product_id)),
+                )
+                .execute(conn)?;
+
+                Self::sync_product_category(conn, product_id, None)
+            })
+            .map_err(Into::into)
+    }
+
+    fn delete_product_categories_by_category_id(
+        &self,
+        category_id: i64,
+    ) -> Result<(), ProductCategoryError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<(), DieselError, _>(|conn| {
+                diesel::delete(
+                    product_categories::table
+                        .filter(product_categories::category_id.eq(category_id)),
+                )
+                .execute(conn)?;
+
+                diesel::update(products::table.filter(products::category_id.eq(category_id)))
+                    .set((
+                        products::category_id.eq(None::<i64>),
+                        products::updated_at.eq(diesel::dsl::now),
+                    ))
+                    .execute(conn)?;
+                Ok(())
+            })
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,84 @@
+use std::ops::DerefMut;
+
+use diesel::{
+    ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::download::{NewProductFileEntity, ProductFileEntity},
+        schema::product_files,
+    },
+    core::{
+        models::{download::ProductFile, product::ProductError},
+        ports::product_file_repository::ProductFileRepository,
+    },
+};
+
+pub struct ProductFileRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ProductFileRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        ProductFileRepositoryImpl { conn }
+    }
+}
+
+impl ProductFileRepository for ProductFileRepositoryImpl {
+    fn create_file(&mut self, file: ProductFile) -> Result<ProductFile, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(product_files::table)
+            .values(NewProductFileEntity {
+                product_id: file.product_id,
+                storage_key: file.storage_key,
+                file_name: file.file_name,
+                content_type: file.content_type,
+                size_bytes: file.size_bytes,
+            })
+            .get_result::<ProductFileEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(|err| match err {
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => ProductError::NotFound,
+                err => err.into(),
+            })
+    }
+
+    fn find_file_by_id(&mut self, id: i64) -> Result<ProductFile, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_files::table
+            .filter(product_files::id.eq(id))
+            .first::<ProductFileEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_files_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductFile>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_files::table
+            .filter(product_files::product_id.eq(product_id))
+            .order(product_files::id.asc())
+            .load::<ProductFileEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn delete_file(&mut self, id: i64) -> Result<ProductFile, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(product_files::table.filter(product_files::id.eq(id)))
+            .get_result::<ProductFileEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,255 @@
+use std::ops::DerefMut;
+
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, PgConnection,
+    QueryDsl, RunQueryDsl,
+    r2d2::{ConnectionManager, Pool},
+    result::Error as DieselError,
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::product::{NewProductImageEntity, ProductImageEntity},
+        schema::{product_images, products},
+    },
+    core::{
+        models::product::{ProductError, ProductImage},
+        ports::product_image_repository::ProductImageRepository,
+    },
+};
+
+pub struct ProductImageRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ProductImageRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        ProductImageRepositoryImpl { conn }
+    }
+
+    /// Marks `image` as the primary image of its product and mirrors its URL
+    /// into `products.product_image`.
+    fn make_primary(
+        conn: &mut PgConnection,
+        image: &ProductImageEntity,
+    ) -> Result<ProductImageEntity, DieselError> {
+        diesel::update(
+            product_images::table.filter(
+                product_images::product_id
+                    .eq(image.product_id)
+                    .and(product_images::is_primary.eq(true)),
+            ),
+        )
+        .set((
+            product_images::is_primary.eq(false),
+            product_images::updated_at.eq(diesel::dsl::now),
+        ))
+        .execute(conn)?;
+
+        diesel::update(products::table.filter(products::id.eq(image.product_id)))
+            .set((
+                products::product_image.eq(Some(&image.url)),
+                products::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(conn)?;
+
+        diesel::update(product_images::table.filter(product_images::id.eq(image.id)))
+            .set((
+                product_images::is_primary.eq(true),
+                product_images::updated_at.eq(diesel::dsl::now),
+            ))
+            .get_result::<ProductImageEntity>(conn)
+    }
+}
+
+impl ProductImageRepository for ProductImageRepositoryImpl {
+    fn create_image(&mut self, image: ProductImage) -> Result<ProductImage, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+        let product_id = image.product_id;
+
+        conn.deref_mut()
+            .transaction::<ProductImageEntity, DieselError, _>(|conn| {
+                let last_position = product_images::table
+                    .filter(product_images::product_id.eq(product_id))
+                    .select(diesel::dsl::max(product_images::position))
+                    .first::<Option<i32>>(conn)?;
+
+                let record = diesel::insert_into(product_images::table)
+                    .values(NewProductImageEntity {
+                        product_id,
+                        storage_key: image.storage_key,
+                        url: image.url,
+                        content_type: image.content_type,
+                        alt_text: image.alt_text,
+                        position: last_position.map_or(0, |position| position + 1),
+                        is_primary: false,
+                        width: image.width,
+                        height: image.height,
+                        renditions: serde_json::to_value(&image.renditions).unwrap_or_default(),
+                    })
+                    .get_result::<ProductImageEntity>(conn)?;
+
+                match last_position {
+                    Some(_) => Ok(record),
+                    None => Self::make_primary(conn, &record),
+                }
+            })
+            .map(|entity| entity.to_model())
+            .map_err(|err| match err {
+                DieselError::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => ProductError::NotFound,
+                err => err.into(),
+            })
+    }
+
+    fn find_image_by_id(&mut self, id: i64) -> Result<ProductImage, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_images::table
+            .filter(product_images::id.eq(id))
+            .first::<ProductImageEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_images_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductImage>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_images::table
+            .filter(product_images::product_id.eq(product_id))
+            .order((product_images::position.asc(), product_images::id.asc()))
+            .load::<ProductImageEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn find_images_by_product_ids(
+        &mut self,
+        product_ids: &[i64],
+    ) -> Result<Vec<ProductImage>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_images::table
+            .filter(product_images::product_id.eq_any(product_ids))
+            .order((
+                product_images::product_id.asc(),
+                product_images::position.asc(),
+                product_images::id.asc(),
+            ))
+            .load::<ProductImageEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn update_image_alt_text(
+        &mut self,
+        id: i64,
+        new_alt_text: Option<String>,
+    ) -> Result<ProductImage, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(product_images::table.filter(product_images::id.eq(id)))
+            .set((
+                product_images::alt_text.eq(new_alt_text),
+                product_images::updated_at.eq(diesel::dsl::now),
+            ))
+            .get_result::<ProductImageEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn reorder_images(
+        &mut self,
+        product_id: i64,
+        image_ids: Vec<i64>,
+    ) -> Result<Vec<ProductImage>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<Vec<ProductImageEntity>, ProductError, _>(|conn| {
+                let mut current_ids = product_images::table
+                    .filter(product_images::product_id.eq(product_id))
+                    .select(product_images::id)
+                    .load::<i64>(conn)?;
+                current_ids.sort_unstable();
+
+                let mut requested_ids = image_ids.clone();
+                requested_ids.sort_unstable();
+                if current_ids != requested_ids {
+                    return Err(ProductError::InvalidData);
+                }
+
+                for (position, image_id) in image_ids.iter().enumerate() {
+                    diesel::update(product_images::table.filter(product_images::id.eq(image_id)))
+                        .set((
+                            product_images::position.eq(position as i32),
+                            product_images::updated_at.eq(diesel::dsl::now),
+                        ))
+                        .execute(conn)?;
+                }
+
+                Ok(product_images::table
+                    .filter(product_images::product_id.eq(product_id))
+                    .order(product_images::position.asc())
+                    .load::<ProductImageEntity>(conn)?)
+            })
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+    }
+
+    fn set_primary_image(&mut self, id: i64) -> Result<ProductImage, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<ProductImageEntity, DieselError, _>(|conn| {
+                let record = product_images::table
+                    .filter(product_images::id.eq(id))
+                    .first::<ProductImageEntity>(conn)?;
+                Self::make_primary(conn, &record)
+            })
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn delete_image(&mut self, id: i64) -> Result<ProductImage, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<ProductImageEntity, DieselError, _>(|conn| {
+                let record =
+                    diesel::delete(product_images::table.filter(product_images::id.eq(id)))
+                        .get_result::<ProductImageEntity>(conn)?;
+
+                if record.is_primary {
+                    let next = product_images::table
+                        .filter(product_images::product_id.eq(record.product_id))
+                        .order((product_images::position.asc(), product_images::id.asc()))
+                        .first::<ProductImageEntity>(conn)
+                        .optional()?;
+
+                    match next {
+                        Some(next) => {
+                            Self::make_primary(conn, &next)?;
+                        }
+                        None => {
+                            diesel::update(
+                                products::table.filter(products::id.eq(record.product_id)),
+                            )
+                            .set((
+                                products::product_image.eq(None::<String>),
+                                products::updated_at.eq(diesel::dsl::now),
+                            ))
+                            .execute(conn)?;
+                        }
+                    }
+                }
+                Ok(record)
+            })
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+}
@@ -1,42 +1,593 @@
 use std::{
+    collections::{BTreeMap, HashMap, HashSet},
     ops::DerefMut,
     sync::{Arc, Mutex},
 };
 
+use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
 use diesel::{
-    ExpressionMethods, PgConnection, RunQueryDsl, TextExpressionMethods,
-    query_dsl::methods::FilterDsl,
+    BoolExpressionMethods, Connection, ExpressionMethods, OptionalExtension, PgConnection,
+    QueryDsl, RunQueryDsl, SelectableHelper,
+    dsl::count_star,
+    pg::Pg,
     r2d2::{ConnectionManager, Pool},
+    result::Error as DieselError,
+    sql_types::{BigInt, Text, Timestamp},
+    upsert::excluded,
 };
 
 use crate::{
     adapters::postgres::{
-        entities::product::{NewProductEntity, ProductEntity},
-        schema::products,
+        entities::{
+            attribute::AttributeEntity,
+            category::{CategoryEntity, CategoryIdEntity},
+            product::{
+                CustomizationFieldEntity, NewCustomizationFieldEntity, NewProductEntity,
+                ProductEntity, ProductImportEntity, ProductViewCountEntity, SearchSuggestionEntity,
+            },
+            product_category::NewProductCategoryEntity,
+        },
+        schema::{
+            attributes, categories, order_items, product_attribute_values, product_categories,
+            product_customization_fields, product_slug_redirects, product_variants,
+            product_variation_options, product_view_counts, products, subscription_plans,
+            subscriptions, variation_options, variations,
+        },
+        search_index::set_similarity_threshold,
     },
     core::{
         models::{
-            category::Category,
-            product::{Product, ProductError},
+            attribute::{AttributeType, AttributeValue},
+            category::{Category, CategoryError},
+            customization::CustomizationField,
+            import::{ImportAction, ProductImport},
+            pagination::{Cursor, Page, Pagination},
+            product::{
+                CategoryFacet, PRICE_BUCKETS, PriceFacet, Product, ProductError, ProductFacets,
+                ProductFilter, ProductSort, ProductStatus, ProductVisibility, SearchSuggestion,
+                VariationOptionFacet,
+            },
+            seo::{SeoMetadata, SlugLookup, slugify, unique_slug},
         },
         ports::{category_repository::CategoryRepository, product_repository::ProductRepository},
     },
 };
 
+/// View counts inserted per statement, well below the bind parameter limit.
+const VIEW_COUNTS_BATCH_SIZE: usize = 1000;
+
 pub struct ProductRepositoryImpl {
     conn: Pool<ConnectionManager<PgConnection>>,
     category_repo: Arc<Mutex<dyn CategoryRepository>>,
+    search_language: String,
 }
 
 impl ProductRepositoryImpl {
     pub fn new(
         conn: Pool<ConnectionManager<PgConnection>>,
         category_repo: Arc<Mutex<dyn CategoryRepository>>,
+        search_language: String,
     ) -> Self {
         ProductRepositoryImpl {
             conn,
             category_repo,
+            search_language,
+        }
+    }
+
+    /// Stores the configured search language in the database, where the search
+    /// document triggers read it, and rebuilds every document when it changed.
+    pub fn sync_search_language(&self) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<(), DieselError, _>(|conn| {
+                let changed = diesel::sql_query(
+                    "UPDATE search_settings SET language = $1::regconfig \
+                     WHERE language <> $1::regconfig",
+                )
+                .bind::<Text, _>(&self.search_language)
+                .execute(conn)?;
+
+                if changed > 0 {
+                    diesel::sql_query(
+                        "UPDATE products \
+                         SET search_document = product_search_document(id, name, description)",
+                    )
+                    .execute(conn)?;
+                }
+                Ok(())
+            })
+            .map_err(Into::into)
+    }
+
+    fn load_customization_fields(
+        conn: &mut PgConnection,
+        product_ids: Vec<i64>,
+    ) -> Result<HashMap<i64, Vec<CustomizationField>>, ProductError> {
+        let records = product_customization_fields::table
+            .filter(product_customization_fields::product_id.eq_any(product_ids))
+            .order(product_customization_fields::id.asc())
+            .load::<CustomizationFieldEntity>(conn)?;
+
+        let mut fields: HashMap<i64, Vec<CustomizationField>> = HashMap::new();
+        for record in records {
+            fields
+                .entry(record.product_id)
+                .or_default()
+                .push(record.to_model());
+        }
+        Ok(fields)
+    }
+
+    /// Loads the categories assigned to each product, primary first.
+    fn load_categories(
+        conn: &mut PgConnection,
+        product_ids: Vec<i64>,
+    ) -> Result<HashMap<i64, Vec<Category>>, ProductError> {
+        let records = product_categories::table
+            .inner_join(categories::table)
+            .filter(product_categories::product_id.eq_any(product_ids))
+            .filter(categories::deleted_at.is_null())
+            .order((
+                product_categories::is_primary.desc(),
+                product_categories::created_at.asc(),
+            ))
+            .select((product_categories::product_id, CategoryEntity::as_select()))
+            .load::<(i64, CategoryEntity)>(conn)?;
+
+        let mut categories: HashMap<i64, Vec<Category>> = HashMap::new();
+        for (product_id, record) in records {
+            categories
+                .entry(product_id)
+                .or_default()
+                .push(record.to_model(None));
         }
+        Ok(categories)
+    }
+
+    /// Narrows `query` down to the products customers currently see, unless
+    /// every product is visible. Products in the trash are never visible.
+    fn filter_visible(
+        query: products::BoxedQuery<'static, Pg>,
+        visibility: ProductVisibility,
+    ) -> products::BoxedQuery<'static, Pg> {
+        let query = query.filter(products::deleted_at.is_null());
+        match visibility {
+            ProductVisibility::All => query,
+            ProductVisibility::Published => query
+                .filter(products::status.eq(ProductStatus::Published.to_string()))
+                .filter(
+                    products::unpublish_at
+                        .is_null()
+                        .or(products::unpublish_at.gt(Utc::now().naive_utc())),
+                ),
+        }
+    }
+
+    /// Builds the query selecting the visible products that match `filter`.
+    fn filter_products(
+        conn: &mut PgConnection,
+        filter: &ProductFilter,
+        visibility: ProductVisibility,
+    ) -> Result<products::BoxedQuery<'static, Pg>, ProductError> {
+        let mut query = Self::filter_visible(products::table.into_boxed(), visibility);
+
+        if let Some(category_id) = filter.category_id {
+            let category_ids = Self::load_category_tree(conn, category_id)?;
+            query = query.filter(
+                products::id.eq_any(
+                    product_categories::table
+                        .filter(product_categories::category_id.eq_any(category_ids))
+                        .select(product_categories::product_id),
+                ),
+            );
+        }
+        if let Some(min_price) = filter.min_price {
+            query = query.filter(products::price.ge(min_price));
+        }
+        if let Some(max_price) = filter.max_price {
+            query = query.filter(products::price.le(max_price));
+        }
+        if let Some(min_rating) = filter.min_rating {
+            query = query.filter(products::rating_average.ge(min_rating));
+        }
+        if filter.in_stock {
+            query = query.filter(
+                products::stock.gt(0).or(products::id.eq_any(
+                    product_variants::table
+                        .filter(product_variants::stock.gt(0))
+                        .select(product_variants::product_id),
+                )),
+            );
+        }
+
+        if !filter.variation_option_ids.is_empty() {
+            let option_ids: HashSet<i64> = filter.variation_option_ids.iter().copied().collect();
+            let options = variation_options::table
+                .filter(
+                    variation_options::id.eq_any(option_ids.iter().copied().collect::<Vec<_>>()),
+                )
+                .select((variation_options::variation_id, variation_options::id))
+                .load::<(i64, i64)>(conn)?;
+            if options.len() != option_ids.len() {
+                return Err(ProductError::InvalidVariationOption);
+            }
+
+            let mut groups: BTreeMap<i64, Vec<i64>> = BTreeMap::new();
+            for (variation_id, option_id) in options {
+                groups.entry(variation_id).or_default().push(option_id);
+            }
+            for option_ids in groups.into_values() {
+                query = query.filter(
+                    products::id.eq_any(
+                        product_variation_options::table
+                            .filter(
+                                product_variation_options::variation_option_id.eq_any(option_ids),
+                            )
+                            .select(product_variation_options::product_id),
+                    ),
+                );
+            }
+        }
+
+        for attribute_filter in &filter.attributes {
+            let attribute = attributes::table
+                .filter(attributes::id.eq(attribute_filter.attribute_id))
+                .first::<AttributeEntity>(conn)
+                .optional()?
+                .map(|entity| entity.to_model())
+                .filter(|attribute| attribute.is_filterable)
+                .ok_or(ProductError::InvalidAttribute)?;
+            let is_number = attribute.data_type == AttributeType::Number;
+            if !is_number && (attribute_filter.min.is_some() || attribute_filter.max.is_some()) {
+                return Err(ProductError::InvalidAttribute);
+            }
+
+            let mut matches = product_attribute_values::table
+                .select(product_attribute_values::product_id)
+                .filter(product_attribute_values::attribute_id.eq(attribute.id))
+                .into_boxed();
+            if !attribute_filter.values.is_empty() {
+                let mut texts = vec![];
+                let mut numbers = vec![];
+                let mut flags = vec![];
+                for value in &attribute_filter.values {
+                    match attribute.validate_value(value.clone()) {
+                        Some(AttributeValue::Text(text)) => texts.push(text),
+                        Some(AttributeValue::Number(number)) => numbers.push(number),
+                        Some(AttributeValue::Bool(flag)) => flags.push(flag),
+                        None => return Err(ProductError::InvalidAttribute),
+                    }
+                }
+                matches = match attribute.data_type {
+                    AttributeType::Text | AttributeType::Enum => {
+                        matches.filter(product_attribute_values::text_value.eq_any(texts))
+                    }
+                    AttributeType::Number => {
+                        matches.filter(product_attribute_values::number_value.eq_any(numbers))
+                    }
+                    AttributeType::Bool => {
+                        matches.filter(product_attribute_values::bool_value.eq_any(flags))
+                    }
+                };
+            }
+            if let Some(min) = attribute_filter.min {
+                matches = matches.filter(product_attribute_values::number_value.ge(min));
+            }
+            if let Some(max) = attribute_filter.max {
+                matches = matches.filter(product_attribute_values::number_value.le(max));
+            }
+            query = query.filter(products::id.eq_any(matches));
+        }
+
+        Ok(query)
+    }
+
+    /// Narrows `query` down to the products that come after `after` in `sort`
+    /// order. Sorts other than the newest compare against the sort key stored
+    /// in the cursor, so the page boundary stays put when the cursor product
+    /// changes in between.
+    fn filter_after(
+        query: products::BoxedQuery<'static, Pg>,
+        sort: ProductSort,
+        after: &Cursor,
+    ) -> Result<products::BoxedQuery<'static, Pg>, ProductError> {
+        if sort == ProductSort::Newest {
+            return Ok(query.filter(
+                products::created_at
+                    .lt(after.created_at)
+                    .or(products::created_at
+                        .eq(after.created_at)
+                        .and(products::id.lt(after.id))),
+            ));
+        }
+
+        let key = after.key.clone().ok_or(ProductError::InvalidData)?;
+        let after_id = products::id.gt(after.id);
+        let float_key = || key.parse::<f64>().map_err(|_| ProductError::InvalidData);
+        let int_key = || key.parse::<i64>().map_err(|_| ProductError::InvalidData);
+
+        Ok(match sort {
+            ProductSort::PriceAsc => {
+                let price = float_key()?;
+                query.filter(
+                    products::price
+                        .gt(price)
+                        .or(products::price.eq(price).and(after_id)),
+                )
+            }
+            ProductSort::PriceDesc => {
+                let price = float_key()?;
+                query.filter(
+                    products::price
+                        .lt(price)
+                        .or(products::price.eq(price).and(after_id)),
+                )
+            }
+            ProductSort::NameAsc => query.filter(
+                products::name
+                    .gt(key.clone())
+                    .or(products::name.eq(key).and(after_id)),
+            ),
+            ProductSort::NameDesc => query.filter(
+                products::name
+                    .lt(key.clone())
+                    .or(products::name.eq(key).and(after_id)),
+            ),
+            ProductSort::Popularity | ProductSort::Newest => {
+                let units_sold = int_key()?;
+                query.filter(
+                    products::units_sold
+                        .lt(units_sold)
+                        .or(products::units_sold.eq(units_sold).and(after_id)),
+                )
+            }
+            ProductSort::Rating => {
+                let rating_average = float_key()?;
+                query.filter(
+                    products::rating_average
+                        .lt(rating_average)
+                        .or(products::rating_average.eq(rating_average).and(after_id)),
+                )
+            }
+            ProductSort::Trending => {
+                let trending_views = int_key()?;
+                query.filter(
+                    products::trending_views
+                        .lt(trending_views)
+                        .or(products::trending_views.eq(trending_views).and(after_id)),
+                )
+            }
+        })
+    }
+
+    /// Value of the `sort` key of a product, as stored in page cursors.
+    fn sort_key(
+        entity: &ProductEntity,
+        sort: ProductSort,
+        units_sold: i64,
+        trending_views: i64,
+    ) -> Option<String> {
+        match sort {
+            ProductSort::Newest => None,
+            ProductSort::PriceAsc | ProductSort::PriceDesc => Some(entity.price.to_string()),
+            ProductSort::NameAsc | ProductSort::NameDesc => Some(entity.name.clone()),
+            ProductSort::Popularity => Some(units_sold.to_string()),
+            ProductSort::Rating => Some(entity.rating_average.to_string()),
+            ProductSort::Trending => Some(trending_views.to_string()),
+        }
+    }
+
+    /// Returns `category_id` along with the ids of all its descendants.
+    fn load_category_tree(
+        conn: &mut PgConnection,
+        category_id: i64,
+    ) -> Result<Vec<i64>, ProductError> {
+        // UNION drops the rows already walked, which ends the walk should
+        // parents ever form a cycle.
+        let records = diesel::sql_query(
+            "WITH RECURSIVE tree (id) AS ( \
+                 SELECT $1::BIGINT \
+                 UNION \
+                 SELECT categories.id FROM categories \
+                 JOIN tree ON categories.parent_id = tree.id \
+             ) \
+             SELECT id FROM tree",
+        )
+        .bind::<BigInt, _>(category_id)
+        .load::<CategoryIdEntity>(conn)?;
+
+        Ok(records.into_iter().map(|record| record.id).collect())
+    }
+
+    fn to_page(
+        conn: &mut PgConnection,
+        entities: Vec<ProductEntity>,
+        pagination: &Pagination,
+        total: Option<i64>,
+        sort_key: impl Fn(&ProductEntity) -> Option<String>,
+    ) -> Result<Page<Product>, ProductError> {
+        let page = Page::new(entities, pagination, total, |entity: &ProductEntity| {
+            Cursor {
+                created_at: entity.created_at,
+                id: entity.id,
+                key: sort_key(entity),
+            }
+        });
+
+        Ok(Page {
+            items: Self::to_models(conn, page.items)?,
+            next_cursor: page.next_cursor,
+            total: page.total,
+        })
+    }
+
+    fn to_models(
+        conn: &mut PgConnection,
+        entities: Vec<ProductEntity>,
+    ) -> Result<Vec<Product>, ProductError> {
+        let product_ids: Vec<i64> = entities.iter().map(|entity| entity.id).collect();
+        let mut fields = Self::load_customization_fields(conn, product_ids.clone())?;
+        let mut categories = Self::load_categories(conn, product_ids)?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                let customization_fields = fields.remove(&entity.id).unwrap_or_default();
+                let categories = categories.remove(&entity.id).unwrap_or_default();
+                let category = entity.category_id.and_then(|category_id| {
+                    categories
+                        .iter()
+                        .find(|category| category.id == category_id)
+                        .cloned()
+                });
+                entity.to_model(category, categories, customization_fields)
+            })
+            .collect())
+    }
+
+    fn is_slug_taken(
+        conn: &mut PgConnection,
+        slug: &str,
+        except_product_id: Option<i64>,
+    ) -> Result<bool, DieselError> {
+        let mut current = products::table.filter(products::slug.eq(slug)).into_boxed();
+        let mut former = product_slug_redirects::table
+            .filter(product_slug_redirects::slug.eq(slug))
+            .into_boxed();
+        if let Some(product_id) = except_product_id {
+            current = current.filter(products::id.ne(product_id));
+            former = former.filter(product_slug_redirects::product_id.ne(product_id));
+        }
+
+        let current = current.count().get_result::<i64>(conn)?;
+        let former = former.count().get_result::<i64>(conn)?;
+        Ok(current + former > 0)
+    }
+
+    /// Creates or updates the product of an import row. Products in the trash
+    /// keep their SKU and slug, so rows matching them are rejected.
+    fn import_product(
+        conn: &mut PgConnection,
+        row: &ProductImport,
+    ) -> Result<ImportAction, ProductError> {
+        let find = |conn: &mut PgConnection, query: products::BoxedQuery<'static, Pg>| {
+            query
+                .select((products::id, products::category_id, products::deleted_at))
+                .first::<(i64, Option<i64>, Option<NaiveDateTime>)>(conn)
+                .optional()
+        };
+        let mut existing = None;
+        if let Some(sku) = &row.sku {
+            existing = find(
+                conn,
+                products::table
+                    .filter(products::sku.eq(sku.clone()))
+                    .into_boxed(),
+            )?;
+            if existing.is_some_and(|(_, _, deleted_at)| deleted_at.is_some()) {
+                return Err(ProductError::SkuTaken);
+            }
+        }
+        if let (None, Some(slug)) = (existing, &row.slug) {
+            existing = find(
+                conn,
+                products::table
+                    .filter(products::slug.eq(slug.clone()))
+                    .into_boxed(),
+            )?;
+            if existing.is_some_and(|(_, _, deleted_at)| deleted_at.is_some()) {
+                return Err(ProductError::SlugTaken);
+            }
+        }
+
+        if let Some((id, previous_category_id, _)) = existing {
+            diesel::update(products::table.filter(products::id.eq(id)))
+                .set(ProductImportEntity {
+                    name: row.name.clone(),
+                    description: row.description.clone(),
+                    price: row.price,
+                    stock: row.stock,
+                    category_id: row.category_id,
+                    status: row.status.map(|status| status.to_string()),
+                    sku: row.sku.clone(),
+                })
+                .execute(conn)?;
+            if row.category_id.is_some() && row.category_id != previous_category_id {
+                Self::replace_primary_category(conn, id, row.category_id)?;
+            }
+            return Ok(ImportAction::Updated(id));
+        }
+
+        let slug = match &row.slug {
+            Some(slug) if Self::is_slug_taken(conn, slug, None)? => {
+                return Err(ProductError::SlugTaken);
+            }
+            Some(slug) => slug.clone(),
+            None => unique_slug(slugify(&row.name), "product", |slug| {
+                Self::is_slug_taken(conn, slug, None)
+            })?,
+        };
+        let id = diesel::insert_into(products::table)
+            .values(NewProductEntity {
+                name: row.name.clone(),
+                description: row.description.clone().unwrap_or_default(),
+                price: row.price,
+                stock: row.stock.unwrap_or_default(),
+                product_image: None,
+                category_id: row.category_id,
+                slug,
+                status: row.status.unwrap_or_default().to_string(),
+                publish_at: None,
+                unpublish_at: None,
+                sku: row.sku.clone(),
+            })
+            .returning(products::id)
+            .get_result::<i64>(conn)?;
+        Self::replace_primary_category(conn, id, row.category_id)?;
+        Ok(ImportAction::Created(id))
+    }
+
+    /// Points the primary category link of a product at `category_id`, keeping
+    /// the previous primary category as a secondary one.
+    fn replace_primary_category(
+        conn: &mut PgConnection,
+        product_id: i64,
+        category_id: Option<i64>,
+    ) -> Result<(), DieselError> {
+        diesel::update(
+            product_categories::table.filter(
+                product_categories::product_id
+                    .eq(product_id)
+                    .and(product_categories::is_primary.eq(true)),
+            ),
+        )
+        .set((
+            product_categories::is_primary.eq(false),
+            product_categories::updated_at.eq(diesel::dsl::now),
+        ))
+        .execute(conn)?;
+
+        if let Some(category_id) = category_id {
+            diesel::insert_into(product_categories::table)
+                .values(NewProductCategoryEntity {
+                    product_id,
+                    category_id,
+                    is_primary: true,
+                })
+                .on_conflict((
+                    product_categories::product_id,
+                    product_categories::category_id,
+                ))
+                .do_update()
+                .set((
+                    product_categories::is_primary.eq(true),
+                    product_categories::updated_at.eq(diesel::dsl::now),
+                ))
+                .execute(conn)?;
+        }
+        Ok(())
     }
 }
 
@@ -44,11 +595,13 @@ impl ProductRepository for ProductRepositoryImpl {
     fn create_product(
         &mut self,
         name: String,
+        slug: String,
         description: String,
         price: f64,
         stock: i32,
         product_image: Option<String>,
         category_id: Option<i64>,
+        status: ProductStatus,
     ) -> Result<Product, ProductError> {
         let mut conn = self.conn.get().unwrap();
 
@@ -59,6 +612,11 @@ impl ProductRepository for ProductRepositoryImpl {
             stock,
             product_image,
             category_id,
+            slug,
+            status: status.to_string(),
+            publish_at: None,
+            unpublish_at: None,
+            sku: None,
         };
 
         let mut category: Option<Category> = None;
@@ -71,10 +629,19 @@ impl ProductRepository for ProductRepositoryImpl {
             );
         }
 
-        diesel::insert_into(products::table)
-            .values(&entity)
-            .get_result::<ProductEntity>(conn.deref_mut())
-            .map(|entity| entity.to_model(category))
+        conn.deref_mut()
+            .transaction::<ProductEntity, DieselError, _>(|conn| {
+                let record = diesel::insert_into(products::table)
+                    .values(&entity)
+                    .returning(ProductEntity::as_returning())
+                    .get_result(conn)?;
+                Self::replace_primary_category(conn, record.id, record.category_id)?;
+                Ok(record)
+            })
+            .map(|record| {
+                let categories = category.iter().cloned().collect();
+                record.to_model(category, categories, vec![])
+            })
             .map_err(Into::into)
     }
 
@@ -83,64 +650,512 @@ impl ProductRepository for ProductRepositoryImpl {
 
         let record = products::table
             .filter(products::id.eq(id))
-            .first::<ProductEntity>(conn.deref_mut())
+            .filter(products::deleted_at.is_null())
+            .select(ProductEntity::as_select())
+            .first(conn.deref_mut())
             .map_err(ProductError::from)?;
 
-        let mut category: Option<Category> = None;
-        if let Some(category_id) = record.category_id {
+        let category = {
             let mut category_repo = self.category_repo.lock().unwrap();
-            category = Some(
-                category_repo
-                    .find_category_by_id(category_id)
-                    .map_err(|_| ProductError::InvalidCategory)?,
+            find_primary_category(&mut *category_repo, record.category_id)?
+        };
+
+        let mut fields = Self::load_customization_fields(conn.deref_mut(), vec![record.id])?;
+        let customization_fields = fields.remove(&record.id).unwrap_or_default();
+        let mut categories = Self::load_categories(conn.deref_mut(), vec![record.id])?;
+        let categories = categories.remove(&record.id).unwrap_or_default();
+
+        Ok(record.to_model(category, categories, customization_fields))
+    }
+
+    fn resolve_product_slug(&mut self, slug: &str) -> Result<SlugLookup<i64>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let id = products::table
+            .filter(products::slug.eq(slug))
+            .filter(products::deleted_at.is_null())
+            .select(products::id)
+            .first::<i64>(conn.deref_mut())
+            .optional()?;
+        if let Some(id) = id {
+            return Ok(SlugLookup::Found(id));
+        }
+
+        product_slug_redirects::table
+            .inner_join(products::table)
+            .filter(product_slug_redirects::slug.eq(slug))
+            .filter(products::deleted_at.is_null())
+            .select(products::slug)
+            .first::<String>(conn.deref_mut())
+            .map(SlugLookup::Moved)
+            .map_err(ProductError::from)
+    }
+
+    fn is_product_slug_taken(
+        &mut self,
+        slug: &str,
+        except_product_id: Option<i64>,
+    ) -> Result<bool, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+        Self::is_slug_taken(conn.deref_mut(), slug, except_product_id).map_err(Into::into)
+    }
+
+    fn update_product_seo(
+        &mut self,
+        id: i64,
+        new_slug: String,
+        new_seo: SeoMetadata,
+    ) -> Result<Product, ProductError> {
+        let record = {
+            let mut conn = self.conn.get().unwrap();
+            conn.deref_mut()
+                .transaction::<ProductEntity, DieselError, _>(|conn| {
+                    let previous = products::table
+                        .filter(products::id.eq(id))
+                        .filter(products::deleted_at.is_null())
+                        .select(ProductEntity::as_select())
+                        .first(conn)?;
+
+                    if previous.slug != new_slug {
+                        // The new slug may be one the product was renamed from.
+                        diesel::delete(
+                            product_slug_redirects::table
+                                .filter(product_slug_redirects::slug.eq(&new_slug))
+                                .filter(product_slug_redirects::product_id.eq(id)),
+                        )
+                        .execute(conn)?;
+                        diesel::insert_into(product_slug_redirects::table)
+                            .values((
+                                product_slug_redirects::slug.eq(&previous.slug),
+                                product_slug_redirects::product_id.eq(id),
+                            ))
+                            .on_conflict(product_slug_redirects::slug)
+                            .do_update()
+                            .set(product_slug_redirects::product_id.eq(id))
+                            .execute(conn)?;
+                    }
+
+                    diesel::update(products::table.filter(products::id.eq(id)))
+                        .set((
+                            products::slug.eq(&new_slug),
+                            products::meta_title.eq(new_seo.meta_title),
+                            products::meta_description.eq(new_seo.meta_description),
+                            products::canonical_url.eq(new_seo.canonical_url),
+                        ))
+                        .returning(ProductEntity::as_returning())
+                        .get_result(conn)
+                })
+                .map_err(ProductError::from)?
+        };
+
+        self.find_product_by_id(record.id)
+    }
+
+    fn find_products_by_ids(&mut self, ids: &[i64]) -> Result<Vec<Product>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let entities = products::table
+            .filter(products::id.eq_any(ids))
+            .filter(products::deleted_at.is_null())
+            .select(ProductEntity::as_select())
+            .load(conn.deref_mut())?;
+        Self::to_models(conn.deref_mut(), entities)
+    }
+
+    fn find_all_products(
+        &mut self,
+        visibility: ProductVisibility,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let mut query = Self::filter_visible(products::table.into_boxed(), visibility)
+            .order((products::created_at.asc(), products::id.asc()))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                products::created_at
+                    .gt(after.created_at)
+                    .or(products::created_at
+                        .eq(after.created_at)
+                        .and(products::id.gt(after.id))),
             );
         }
+        let entities = query
+            .select(ProductEntity::as_select())
+            .load(conn.deref_mut())?;
+
+        let total = if pagination.with_total {
+            Some(
+                Self::filter_visible(products::table.into_boxed(), visibility)
+                    .count()
+                    .get_result(conn.deref_mut())?,
+            )
+        } else {
+            None
+        };
+        Self::to_page(conn.deref_mut(), entities, &pagination, total, |_| None)
+    }
 
-        Ok(record.to_model(category))
+    fn delete_product(&mut self, id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(
+            products::table
+                .filter(products::id.eq(id))
+                .filter(products::deleted_at.is_null()),
+        )
+        .set(products::deleted_at.eq(Utc::now().naive_utc()))
+        .execute(conn.deref_mut())
+        .map(|affected_rows| {
+            if affected_rows == 0 {
+                Err(ProductError::NotFound)
+            } else {
+                Ok(())
+            }
+        })
+        .map_err(|_| ProductError::InternalError)?
+    }
+
+    fn restore_product(&mut self, id: i64) -> Result<Product, ProductError> {
+        let record = {
+            let mut conn = self.conn.get().unwrap();
+            diesel::update(
+                products::table
+                    .filter(products::id.eq(id))
+                    .filter(products::deleted_at.is_not_null()),
+            )
+            .set(products::deleted_at.eq(None::<NaiveDateTime>))
+            .returning(ProductEntity::as_returning())
+            .get_result(conn.deref_mut())?
+        };
+
+        self.find_product_by_id(record.id)
     }
 
-    fn find_all_products(&mut self) -> Result<Vec<Product>, ProductError> {
+    fn find_deleted_products(
+        &mut self,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let mut query = products::table
+            .filter(products::deleted_at.is_not_null())
+            .order((products::created_at.asc(), products::id.asc()))
+            .limit(pagination.fetch_limit())
+            .into_boxed();
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                products::created_at
+                    .gt(after.created_at)
+                    .or(products::created_at
+                        .eq(after.created_at)
+                        .and(products::id.gt(after.id))),
+            );
+        }
+        let entities = query
+            .select(ProductEntity::as_select())
+            .load(conn.deref_mut())?;
+
+        let total = if pagination.with_total {
+            Some(
+                products::table
+                    .filter(products::deleted_at.is_not_null())
+                    .count()
+                    .get_result(conn.deref_mut())?,
+            )
+        } else {
+            None
+        };
+        Self::to_page(conn.deref_mut(), entities, &pagination, total, |_| None)
+    }
+
+    fn find_purgeable_product_ids(
+        &mut self,
+        deleted_before: NaiveDateTime,
+    ) -> Result<Vec<i64>, ProductError> {
         let mut conn = self.conn.get().unwrap();
 
         products::table
-            .load::<ProductEntity>(conn.deref_mut())
-            .map(|entities| {
-                entities
-                    .into_iter()
-                    .map(|entity| entity.to_model(None))
-                    .collect()
+            .filter(products::deleted_at.lt(deleted_before))
+            .filter(diesel::dsl::not(diesel::dsl::exists(
+                order_items::table.filter(order_items::product_id.eq(products::id)),
+            )))
+            // Plans with subscribers, even cancelled ones, cannot be deleted.
+            .filter(diesel::dsl::not(diesel::dsl::exists(
+                subscriptions::table
+                    .inner_join(subscription_plans::table)
+                    .filter(subscription_plans::product_id.eq(products::id)),
+            )))
+            .order(products::id.asc())
+            .select(products::id)
+            .load(conn.deref_mut())
+            .map_err(Into::into)
+    }
+
+    fn purge_product(&mut self, id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let affected_rows = diesel::delete(
+            products::table
+                .filter(products::id.eq(id))
+                .filter(products::deleted_at.is_not_null())
+                .filter(diesel::dsl::not(diesel::dsl::exists(
+                    order_items::table.filter(order_items::product_id.eq(products::id)),
+                )))
+                .filter(diesel::dsl::not(diesel::dsl::exists(
+                    subscriptions::table
+                        .inner_join(subscription_plans::table)
+                        .filter(subscription_plans::product_id.eq(products::id)),
+                ))),
+        )
+        .execute(conn.deref_mut())?;
+
+        if affected_rows == 0 {
+            return Err(ProductError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn suggest_names(
+        &mut self,
+        prefix: String,
+        limit: i64,
+    ) -> Result<Vec<SearchSuggestion>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+        let pattern = format!("{}%", escape_like(&prefix));
+
+        conn.deref_mut()
+            .transaction::<Vec<SearchSuggestionEntity>, DieselError, _>(|conn| {
+                set_similarity_threshold(conn)?;
+                diesel::sql_query(
+                    "SELECT kind, id, name \
+                     FROM ( \
+                         SELECT 'product' AS kind, id, name FROM products \
+                         WHERE deleted_at IS NULL AND status = 'published' \
+                             AND (unpublish_at IS NULL OR unpublish_at > $4) \
+                         UNION ALL \
+                         SELECT 'category' AS kind, id, name FROM categories \
+                         WHERE deleted_at IS NULL \
+                     ) AS names \
+                     WHERE name ILIKE $1 OR (char_length($2) >= 3 AND $2 <% name) \
+                     ORDER BY name ILIKE $1 DESC, word_similarity($2, name) DESC, name ASC \
+                     LIMIT $3",
+                )
+                .bind::<Text, _>(pattern)
+                .bind::<Text, _>(prefix)
+                .bind::<BigInt, _>(limit)
+                .bind::<Timestamp, _>(Utc::now().naive_utc())
+                .load::<SearchSuggestionEntity>(conn)
             })
+            .map(|records| records.iter().map(|record| record.to_model()).collect())
             .map_err(Into::into)
     }
 
-    fn delete_product(&mut self, id: i64) -> Result<(), ProductError> {
+    fn find_products(
+        &mut self,
+        filter: &ProductFilter,
+        visibility: ProductVisibility,
+        sort: ProductSort,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError> {
         let mut conn = self.conn.get().unwrap();
 
-        diesel::delete(products::table.filter(products::id.eq(id)))
-            .execute(conn.deref_mut())
-            .map(|affected_rows| {
-                if affected_rows == 0 {
-                    Err(ProductError::NotFound)
-                } else {
-                    Ok(())
-                }
+        let mut query = Self::filter_products(conn.deref_mut(), filter, visibility)?;
+        if let Some(after) = &pagination.after {
+            query = Self::filter_after(query, sort, after)?;
+        }
+        let query = match sort {
+            ProductSort::Newest => query.order((products::created_at.desc(), products::id.desc())),
+            ProductSort::PriceAsc => query.order((products::price.asc(), products::id.asc())),
+            ProductSort::PriceDesc => query.order((products::price.desc(), products::id.asc())),
+            ProductSort::NameAsc => query.order((products::name.asc(), products::id.asc())),
+            ProductSort::NameDesc => query.order((products::name.desc(), products::id.asc())),
+            ProductSort::Popularity => {
+                query.order((products::units_sold.desc(), products::id.asc()))
+            }
+            ProductSort::Rating => {
+                query.order((products::rating_average.desc(), products::id.asc()))
+            }
+            ProductSort::Trending => {
+                query.order((products::trending_views.desc(), products::id.asc()))
+            }
+        };
+
+        let records = query
+            .limit(pagination.fetch_limit())
+            .select((
+                ProductEntity::as_select(),
+                products::units_sold,
+                products::trending_views,
+            ))
+            .load::<(ProductEntity, i64, i64)>(conn.deref_mut())?;
+        let sort_keys: HashMap<i64, Option<String>> = records
+            .iter()
+            .map(|(entity, units_sold, trending_views)| {
+                (
+                    entity.id,
+                    Self::sort_key(entity, sort, *units_sold, *trending_views),
+                )
             })
-            .map_err(|_| ProductError::InternalError)?
+            .collect();
+        let entities = records.into_iter().map(|(entity, _, _)| entity).collect();
+
+        let total = if pagination.with_total {
+            Some(
+                Self::filter_products(conn.deref_mut(), filter, visibility)?
+                    .count()
+                    .get_result(conn.deref_mut())?,
+            )
+        } else {
+            None
+        };
+        Self::to_page(conn.deref_mut(), entities, &pagination, total, |entity| {
+            sort_keys.get(&entity.id).cloned().flatten()
+        })
     }
 
-    fn find_products_by_name(&mut self, name: String) -> Result<Vec<Product>, ProductError> {
+    fn count_product_facets(
+        &mut self,
+        filter: &ProductFilter,
+        visibility: ProductVisibility,
+    ) -> Result<ProductFacets, ProductError> {
         let mut conn = self.conn.get().unwrap();
 
-        products::table
-            .filter(products::name.like(format!("%{}%", name))) // Using LIKE for substring search
-            .load::<ProductEntity>(conn.deref_mut())
-            .map(|entities| {
-                entities
-                    .into_iter()
-                    .map(|entity| entity.to_model(None))
-                    .collect()
+        let matches = Self::filter_products(conn.deref_mut(), filter, visibility)?
+            .select((products::id, products::price))
+            .load::<(i64, f64)>(conn.deref_mut())?;
+        let product_ids: Vec<i64> = matches.iter().map(|(id, _)| *id).collect();
+
+        let categories = product_categories::table
+            .inner_join(categories::table)
+            .filter(product_categories::product_id.eq_any(&product_ids))
+            .filter(categories::deleted_at.is_null())
+            .group_by((categories::id, categories::name))
+            .select((categories::id, categories::name, count_star()))
+            .order((count_star().desc(), categories::name.asc()))
+            .load::<(i64, String, i64)>(conn.deref_mut())?
+            .into_iter()
+            .map(|(category_id, name, count)| CategoryFacet {
+                category_id,
+                name,
+                count,
             })
-            .map_err(Into::into)
+            .collect();
+
+        let mut price_counts = [0_i64; PRICE_BUCKETS.len()];
+        for (_, price) in &matches {
+            if let Some(bucket) = PRICE_BUCKETS
+                .iter()
+                .rposition(|min_price| price >= min_price)
+            {
+                price_counts[bucket] += 1;
+            }
+        }
+        let prices = PRICE_BUCKETS
+            .iter()
+            .enumerate()
+            .filter(|(bucket, _)| price_counts[*bucket] > 0)
+            .map(|(bucket, min_price)| PriceFacet {
+                min_price: *min_price,
+                max_price: PRICE_BUCKETS.get(bucket + 1).copied(),
+                count: price_counts[bucket],
+            })
+            .collect();
+
+        let option_counts = product_variation_options::table
+            .inner_join(variation_options::table)
+            .filter(product_variation_options::product_id.eq_any(&product_ids))
+            .group_by((
+                variation_options::id,
+                variation_options::variation_id,
+                variation_options::value,
+            ))
+            .select((
+                variation_options::id,
+                variation_options::variation_id,
+                variation_options::value,
+                count_star(),
+            ))
+            .load::<(i64, i64, String, i64)>(conn.deref_mut())?;
+        let variation_names: HashMap<i64, String> = variations::table
+            .filter(
+                variations::id.eq_any(
+                    option_counts
+                        .iter()
+                        .map(|(_, variation_id, _, _)| *variation_id)
+                        .collect::<Vec<_>>(),
+                ),
+            )
+            .select((variations::id, variations::name))
+            .load::<(i64, String)>(conn.deref_mut())?
+            .into_iter()
+            .collect();
+
+        let mut variation_options: Vec<VariationOptionFacet> = option_counts
+            .into_iter()
+            .map(
+                |(variation_option_id, variation_id, value, count)| VariationOptionFacet {
+                    variation_id,
+                    variation_name: variation_names
+                        .get(&variation_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    variation_option_id,
+                    value,
+                    count,
+                },
+            )
+            .collect();
+        variation_options
+            .sort_by(|a, b| (&a.variation_name, &a.value).cmp(&(&b.variation_name, &b.value)));
+
+        Ok(ProductFacets {
+            categories,
+            prices,
+            variation_options,
+        })
+    }
+
+    fn find_products_by_category_id(
+        &mut self,
+        category_id: i64,
+        visibility: ProductVisibility,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let in_category = || {
+            Self::filter_visible(products::table.into_boxed(), visibility).filter(
+                products::id.eq_any(
+                    product_categories::table
+                        .filter(product_categories::category_id.eq(category_id))
+                        .select(product_categories::product_id),
+                ),
+            )
+        };
+        let mut query = in_category()
+            .order((products::created_at.asc(), products::id.asc()))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                products::created_at
+                    .gt(after.created_at)
+                    .or(products::created_at
+                        .eq(after.created_at)
+                        .and(products::id.gt(after.id))),
+            );
+        }
+        let entities = query
+            .select(ProductEntity::as_select())
+            .load(conn.deref_mut())?;
+
+        let total = if pagination.with_total {
+            Some(in_category().count().get_result(conn.deref_mut())?)
+        } else {
+            None
+        };
+        Self::to_page(conn.deref_mut(), entities, &pagination, total, |_| None)
     }
 
     fn update_product(
@@ -156,36 +1171,297 @@ impl ProductRepository for ProductRepositoryImpl {
         let mut conn = self.conn.get().unwrap();
         let mut category_repo = self.category_repo.lock().unwrap();
 
-        let record = diesel::update(products::table.filter(products::id.eq(id)))
-            .set((
-                products::name.eq(new_name),
-                products::description.eq(new_description),
-                products::price.eq(new_price),
-                products::stock.eq(new_stock),
-                products::product_image.eq(new_product_image),
-                products::category_id.eq(new_category_id),
-            ))
-            .get_result::<ProductEntity>(conn.deref_mut())
+        let record = conn
+            .deref_mut()
+            .transaction::<ProductEntity, DieselError, _>(|conn| {
+                let previous = products::table
+                    .filter(products::id.eq(id))
+                    .filter(products::deleted_at.is_null())
+                    .select(ProductEntity::as_select())
+                    .first(conn)?;
+
+                let record = diesel::update(products::table.filter(products::id.eq(id)))
+                    .set((
+                        products::name.eq(new_name),
+                        products::description.eq(new_description),
+                        products::price.eq(new_price),
+                        products::stock.eq(new_stock),
+                        products::product_image.eq(new_product_image),
+                        products::category_id.eq(new_category_id),
+                    ))
+                    .returning(ProductEntity::as_returning())
+                    .get_result(conn)?;
+
+                if previous.category_id != record.category_id {
+                    Self::replace_primary_category(conn, record.id, record.category_id)?;
+                }
+                Ok(record)
+            })
             .map_err(|err| match err {
-                diesel::result::Error::DatabaseError(kind, _) => match kind {
-                    diesel::result::DatabaseErrorKind::ForeignKeyViolation => {
-                        ProductError::InvalidCategory
-                    }
-                    _ => ProductError::InternalError,
-                },
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => ProductError::InvalidCategory,
                 diesel::result::Error::NotFound => ProductError::NotFound,
                 _ => ProductError::InternalError,
             })?;
 
-        let mut category: Option<Category> = None;
-        if let Some(category_id) = record.category_id {
-            category = Some(
-                category_repo
-                    .find_category_by_id(category_id)
-                    .map_err(|_| ProductError::InvalidCategory)?,
-            );
+        let category = find_primary_category(&mut *category_repo, record.category_id)?;
+
+        let mut fields = Self::load_customization_fields(conn.deref_mut(), vec![record.id])?;
+        let customization_fields = fields.remove(&record.id).unwrap_or_default();
+        let mut categories = Self::load_categories(conn.deref_mut(), vec![record.id])?;
+        let categories = categories.remove(&record.id).unwrap_or_default();
+
+        Ok(record.to_model(category, categories, customization_fields))
+    }
+
+    fn update_product_status(
+        &mut self,
+        id: i64,
+        new_status: ProductStatus,
+        new_publish_at: Option<NaiveDateTime>,
+        new_unpublish_at: Option<NaiveDateTime>,
+    ) -> Result<Product, ProductError> {
+        let record = {
+            let mut conn = self.conn.get().unwrap();
+            diesel::update(
+                products::table
+                    .filter(products::id.eq(id))
+                    .filter(products::deleted_at.is_null()),
+            )
+            .set((
+                products::status.eq(new_status.to_string()),
+                products::publish_at.eq(new_publish_at),
+                products::unpublish_at.eq(new_unpublish_at),
+            ))
+            .returning(ProductEntity::as_returning())
+            .get_result(conn.deref_mut())?
+        };
+
+        self.find_product_by_id(record.id)
+    }
+
+    fn update_product_digital(
+        &mut self,
+        id: i64,
+        is_digital: bool,
+    ) -> Result<Product, ProductError> {
+        let record = {
+            let mut conn = self.conn.get().unwrap();
+            diesel::update(
+                products::table
+                    .filter(products::id.eq(id))
+                    .filter(products::deleted_at.is_null()),
+            )
+            .set((
+                products::is_digital.eq(is_digital),
+                products::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .returning(ProductEntity::as_returning())
+            .get_result(conn.deref_mut())?
+        };
+
+        self.find_product_by_id(record.id)
+    }
+
+    fn apply_product_schedules(&mut self, now: NaiveDateTime) -> Result<usize, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+        let published = ProductStatus::Published.to_string();
+
+        conn.deref_mut()
+            .transaction::<usize, DieselError, _>(|conn| {
+                let publications = diesel::update(
+                    products::table
+                        .filter(products::deleted_at.is_null())
+                        .filter(products::status.ne(&published))
+                        .filter(products::publish_at.le(now)),
+                )
+                .set((
+                    products::status.eq(&published),
+                    products::publish_at.eq(None::<NaiveDateTime>),
+                ))
+                .execute(conn)?;
+
+                let withdrawals = diesel::update(
+                    products::table
+                        .filter(products::deleted_at.is_null())
+                        .filter(products::status.eq(&published))
+                        .filter(products::unpublish_at.le(now)),
+                )
+                .set((
+                    products::status.eq(ProductStatus::Archived.to_string()),
+                    products::unpublish_at.eq(None::<NaiveDateTime>),
+                ))
+                .execute(conn)?;
+
+                Ok(publications + withdrawals)
+            })
+            .map_err(Into::into)
+    }
+
+    fn add_product_views(
+        &mut self,
+        views: &HashMap<i64, i64>,
+        day: NaiveDate,
+        trending_days: i64,
+    ) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<(), DieselError, _>(|conn| {
+                let product_ids: Vec<i64> = products::table
+                    .filter(products::id.eq_any(views.keys().copied().collect::<Vec<_>>()))
+                    .select(products::id)
+                    .load(conn)?;
+                let records: Vec<ProductViewCountEntity> = product_ids
+                    .iter()
+                    .map(|product_id| ProductViewCountEntity {
+                        product_id: *product_id,
+                        viewed_on: day,
+                        views: views[product_id],
+                    })
+                    .collect();
+                for chunk in records.chunks(VIEW_COUNTS_BATCH_SIZE) {
+                    diesel::insert_into(product_view_counts::table)
+                        .values(chunk)
+                        .on_conflict((
+                            product_view_counts::product_id,
+                            product_view_counts::viewed_on,
+                        ))
+                        .do_update()
+                        .set(
+                            product_view_counts::views
+                                .eq(product_view_counts::views
+                                    + excluded(product_view_counts::views)),
+                        )
+                        .execute(conn)?;
+                }
+
+                // Only the days the trending views count are kept.
+                diesel::delete(product_view_counts::table.filter(
+                    product_view_counts::viewed_on.le(day - Duration::days(trending_days.max(1))),
+                ))
+                .execute(conn)?;
+                diesel::sql_query(
+                    "UPDATE products \
+                     SET trending_views = coalesce(( \
+                         SELECT sum(views) FROM product_view_counts \
+                         WHERE product_view_counts.product_id = products.id \
+                     ), 0) \
+                     WHERE trending_views > 0 \
+                         OR id IN (SELECT product_id FROM product_view_counts)",
+                )
+                .execute(conn)?;
+                Ok(())
+            })
+            .map_err(Into::into)
+    }
+
+    fn import_products(
+        &mut self,
+        rows: &[ProductImport],
+        dry_run: bool,
+    ) -> Result<Vec<Result<ImportAction, ProductError>>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+        let mut outcomes = Vec::with_capacity(rows.len());
+
+        let result = conn.deref_mut().transaction::<(), DieselError, _>(|conn| {
+            for row in rows {
+                // Each row gets a savepoint, so that a failed row does not
+                // abort the transaction before the next rows are checked.
+                outcomes.push(conn.transaction::<ImportAction, ProductError, _>(|conn| {
+                    Self::import_product(conn, row)
+                }));
+            }
+            if dry_run || outcomes.iter().any(Result::is_err) {
+                return Err(DieselError::RollbackTransaction);
+            }
+            Ok(())
+        });
+
+        match result {
+            Ok(()) | Err(DieselError::RollbackTransaction) => Ok(outcomes),
+            Err(err) => Err(err.into()),
         }
+    }
 
-        Ok(record.to_model(category))
+    fn find_customization_fields(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<CustomizationField>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+        let mut fields = Self::load_customization_fields(conn.deref_mut(), vec![product_id])?;
+        Ok(fields.remove(&product_id).unwrap_or_default())
     }
+
+    fn set_customization_fields(
+        &mut self,
+        product_id: i64,
+        fields: Vec<CustomizationField>,
+    ) -> Result<Vec<CustomizationField>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<Vec<CustomizationFieldEntity>, diesel::result::Error, _>(|conn| {
+                products::table
+                    .filter(products::id.eq(product_id))
+                    .select(products::id)
+                    .first::<i64>(conn)?;
+
+                diesel::delete(
+                    product_customization_fields::table
+                        .filter(product_customization_fields::product_id.eq(product_id)),
+                )
+                .execute(conn)?;
+
+                let new_fields: Vec<NewCustomizationFieldEntity> = fields
+                    .into_iter()
+                    .map(|field| NewCustomizationFieldEntity {
+                        product_id,
+                        name: field.name,
+                        field_type: field.field_type.to_string(),
+                        required: field.required,
+                        max_length: field.max_length,
+                        extra_price: field.extra_price,
+                    })
+                    .collect();
+
+                diesel::insert_into(product_customization_fields::table)
+                    .values(&new_fields)
+                    .get_results::<CustomizationFieldEntity>(conn)
+            })
+            .map(|records| records.iter().map(|record| record.to_model()).collect())
+            .map_err(|err| match err {
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::UniqueViolation,
+                    _,
+                ) => ProductError::InvalidData,
+                err => err.into(),
+            })
+    }
+}
+
+/// Looks up the primary category of a product. A category in the trash no
+/// longer counts as one.
+fn find_primary_category(
+    category_repo: &mut dyn CategoryRepository,
+    category_id: Option<i64>,
+) -> Result<Option<Category>, ProductError> {
+    let Some(category_id) = category_id else {
+        return Ok(None);
+    };
+    match category_repo.find_category_by_id(category_id) {
+        Ok(category) => Ok(Some(category)),
+        Err(CategoryError::NotFound) => Ok(None),
+        Err(_) => Err(ProductError::InternalError),
+    }
+}
+
+/// Escapes the `LIKE` wildcards in `value` so it matches literally.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
@@ -0,0 +1,207 @@
+use std::{collections::HashMap, ops::DerefMut};
+
+use diesel::{
+    Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::product::{
+            NewProductVariantEntity, ProductVariantEntity, ProductVariantOptionEntity,
+            VariationOptionEntity,
+        },
+        schema::{product_variant_options, product_variants, variation_options},
+    },
+    core::{
+        models::product::{ProductError, ProductVariant, VariationOption},
+        ports::product_variant_repository::ProductVariantRepository,
+    },
+};
+
+pub struct ProductVariantRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ProductVariantRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        ProductVariantRepositoryImpl { conn }
+    }
+
+    fn load_options(
+        conn: &mut PgConnection,
+        variant_ids: Vec<i64>,
+    ) -> Result<HashMap<i64, Vec<VariationOption>>, ProductError> {
+        let records = product_variant_options::table
+            .inner_join(variation_options::table)
+            .filter(product_variant_options::variant_id.eq_any(variant_ids))
+            .order((
+                variation_options::variation_id.asc(),
+                variation_options::id.asc(),
+            ))
+            .select((
+                product_variant_options::variant_id,
+                VariationOptionEntity::as_select(),
+            ))
+            .load::<(i64, VariationOptionEntity)>(conn)?;
+
+        let mut options: HashMap<i64, Vec<VariationOption>> = HashMap::new();
+        for (variant_id, record) in records {
+            options
+                .entry(variant_id)
+                .or_default()
+                .push(record.to_model());
+        }
+        Ok(options)
+    }
+
+    fn to_models(
+        conn: &mut PgConnection,
+        entities: Vec<ProductVariantEntity>,
+    ) -> Result<Vec<ProductVariant>, ProductError> {
+        let mut options =
+            Self::load_options(conn, entities.iter().map(|entity| entity.id).collect())?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                let variant_options = options.remove(&entity.id).unwrap_or_default();
+                entity.to_model(variant_options)
+            })
+            .collect())
+    }
+
+    fn replace_options(
+        conn: &mut PgConnection,
+        variant_id: i64,
+        variation_option_ids: Vec<i64>,
+    ) -> Result<(), diesel::result::Error> {
+        diesel::delete(
+            product_variant_options::table
+                .filter(product_variant_options::variant_id.eq(variant_id)),
+        )
+        .execute(conn)?;
+
+        let records: Vec<ProductVariantOptionEntity> = variation_option_ids
+            .into_iter()
+            .map(|variation_option_id| ProductVariantOptionEntity {
+                variant_id,
+                variation_option_id,
+            })
+            .collect();
+
+        diesel::insert_into(product_variant_options::table)
+            .values(&records)
+            .execute(conn)?;
+
+        Ok(())
+    }
+}
+
+impl ProductVariantRepository for ProductVariantRepositoryImpl {
+    fn create_variant(
+        &mut self,
+        product_id: i64,
+        sku: String,
+        price: Option<f64>,
+        stock: i32,
+        image: Option<String>,
+        variation_option_ids: Vec<i64>,
+    ) -> Result<ProductVariant, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let record = conn
+            .deref_mut()
+            .transaction::<ProductVariantEntity, diesel::result::Error, _>(|conn| {
+                let record = diesel::insert_into(product_variants::table)
+                    .values(NewProductVariantEntity {
+                        product_id,
+                        sku,
+                        price,
+                        stock,
+                        image,
+                    })
+                    .get_result::<ProductVariantEntity>(conn)?;
+
+                Self::replace_options(conn, record.id, variation_option_ids)?;
+                Ok(record)
+            })?;
+
+        let mut variants = Self::to_models(conn.deref_mut(), vec![record])?;
+        Ok(variants.remove(0))
+    }
+
+    fn find_variant_by_id(&mut self, id: i64) -> Result<ProductVariant, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let record = product_variants::table
+            .filter(product_variants::id.eq(id))
+            .first::<ProductVariantEntity>(conn.deref_mut())?;
+
+        let mut variants = Self::to_models(conn.deref_mut(), vec![record])?;
+        Ok(variants.remove(0))
+    }
+
+    fn find_variants_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductVariant>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let records = product_variants::table
+            .filter(product_variants::product_id.eq(product_id))
+            .order(product_variants::id.asc())
+            .load::<ProductVariantEntity>(conn.deref_mut())?;
+
+        Self::to_models(conn.deref_mut(), records)
+    }
+
+    fn update_variant(
+        &mut self,
+        id: i64,
+        new_sku: String,
+        new_price: Option<f64>,
+        new_stock: i32,
+        new_image: Option<String>,
+        new_variation_option_ids: Vec<i64>,
+    ) -> Result<ProductVariant, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let record = conn
+            .deref_mut()
+            .transaction::<ProductVariantEntity, diesel::result::Error, _>(|conn| {
+                let record =
+                    diesel::update(product_variants::table.filter(product_variants::id.eq(id)))
+                        .set((
+                            product_variants::sku.eq(new_sku),
+                            product_variants::price.eq(new_price),
+                            product_variants::stock.eq(new_stock),
+                            product_variants::image.eq(new_image),
+                            product_variants::updated_at.eq(diesel::dsl::now),
+                        ))
+                        .get_result::<ProductVariantEntity>(conn)?;
+
+                Self::replace_options(conn, record.id, new_variation_option_ids)?;
+                Ok(record)
+            })?;
+
+        let mut variants = Self::to_models(conn.deref_mut(), vec![record])?;
+        Ok(variants.remove(0))
+    }
+
+    fn delete_variant(&mut self, id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(product_variants::table.filter(product_variants::id.eq(id)))
+            .execute(conn.deref_mut())
+            .map(|affected_rows| {
+                if affected_rows == 0 {
+                    Err(ProductError::NotFound)
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|_| ProductError::InternalError)?
+    }
+}
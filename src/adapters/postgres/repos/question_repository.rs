@@ -0,0 +1,337 @@
+use std::{collections::HashMap, ops::DerefMut};
+
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::question::{AnswerEntity, NewAnswerEntity, NewQuestionEntity, QuestionEntity},
+        schema::{product_answers, product_questions},
+    },
+    core::{
+        models::{
+            moderation::ModerationStatus,
+            pagination::{Cursor, Page, Pagination},
+            question::{ProductAnswer, ProductQuestion, QuestionError},
+        },
+        ports::question_repository::QuestionRepository,
+    },
+};
+
+pub struct QuestionRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl QuestionRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        QuestionRepositoryImpl { conn }
+    }
+
+    /// Loads the approved answers of each question, oldest first.
+    fn load_answers(
+        conn: &mut PgConnection,
+        question_ids: Vec<i64>,
+    ) -> Result<HashMap<i64, Vec<ProductAnswer>>, QuestionError> {
+        let entities = product_answers::table
+            .filter(product_answers::question_id.eq_any(question_ids))
+            .filter(product_answers::status.eq(ModerationStatus::Approved.to_string()))
+            .order((product_answers::created_at.asc(), product_answers::id.asc()))
+            .select(AnswerEntity::as_select())
+            .load(conn)?;
+
+        let mut answers: HashMap<i64, Vec<ProductAnswer>> = HashMap::new();
+        for entity in entities {
+            answers
+                .entry(entity.question_id)
+                .or_default()
+                .push(entity.to_model());
+        }
+        Ok(answers)
+    }
+
+    /// Builds questions along with their approved answers.
+    fn to_models_with_answers(
+        conn: &mut PgConnection,
+        entities: Vec<QuestionEntity>,
+    ) -> Result<Vec<ProductQuestion>, QuestionError> {
+        let question_ids = entities.iter().map(|entity| entity.id).collect();
+        let mut answers = Self::load_answers(conn, question_ids)?;
+        Ok(entities
+            .iter()
+            .map(|entity| entity.to_model(answers.remove(&entity.id).unwrap_or_default()))
+            .collect())
+    }
+}
+
+impl QuestionRepository for QuestionRepositoryImpl {
+    fn create_question(
+        &mut self,
+        question: ProductQuestion,
+    ) -> Result<ProductQuestion, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(product_questions::table)
+            .values(NewQuestionEntity {
+                product_id: question.product_id,
+                user_id: question.user_id,
+                body: question.body,
+                status: ModerationStatus::Pending.to_string(),
+            })
+            .returning(QuestionEntity::as_returning())
+            .get_result(conn.deref_mut())
+            .map(|entity| entity.to_model(vec![]))
+            .map_err(Into::into)
+    }
+
+    fn find_question_by_id(&mut self, id: i64) -> Result<ProductQuestion, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_questions::table
+            .filter(product_questions::id.eq(id))
+            .select(QuestionEntity::as_select())
+            .first(conn.deref_mut())
+            .map(|entity| entity.to_model(vec![]))
+            .map_err(Into::into)
+    }
+
+    fn find_questions_by_product_id(
+        &mut self,
+        product_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<ProductQuestion>, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let approved = || {
+            product_questions::table
+                .filter(product_questions::product_id.eq(product_id))
+                .filter(product_questions::status.eq(ModerationStatus::Approved.to_string()))
+                .into_boxed()
+        };
+        let mut query = approved()
+            .order((
+                product_questions::created_at.desc(),
+                product_questions::id.desc(),
+            ))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                product_questions::created_at.lt(after.created_at).or(
+                    product_questions::created_at
+                        .eq(after.created_at)
+                        .and(product_questions::id.lt(after.id)),
+                ),
+            );
+        }
+        let entities = query
+            .select(QuestionEntity::as_select())
+            .load(conn.deref_mut())?;
+
+        let total = if pagination.with_total {
+            Some(approved().count().get_result(conn.deref_mut())?)
+        } else {
+            None
+        };
+        let page = Page::new(entities, &pagination, total, |entity: &QuestionEntity| {
+            Cursor {
+                created_at: entity.created_at,
+                id: entity.id,
+                key: None,
+            }
+        });
+
+        Ok(Page {
+            items: Self::to_models_with_answers(conn.deref_mut(), page.items)?,
+            next_cursor: page.next_cursor,
+            total: page.total,
+        })
+    }
+
+    fn find_top_questions(
+        &mut self,
+        product_id: i64,
+        limit: i64,
+    ) -> Result<Vec<ProductQuestion>, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let entities = product_questions::table
+            .filter(product_questions::product_id.eq(product_id))
+            .filter(product_questions::status.eq(ModerationStatus::Approved.to_string()))
+            .filter(product_questions::answer_count.gt(0))
+            .order((
+                product_questions::answer_count.desc(),
+                product_questions::created_at.desc(),
+                product_questions::id.desc(),
+            ))
+            .limit(limit)
+            .select(QuestionEntity::as_select())
+            .load(conn.deref_mut())?;
+        Self::to_models_with_answers(conn.deref_mut(), entities)
+    }
+
+    fn find_questions_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<ProductQuestion>, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let with_status = || {
+            product_questions::table
+                .filter(product_questions::status.eq(status.to_string()))
+                .into_boxed()
+        };
+        let mut query = with_status()
+            .order((
+                product_questions::created_at.asc(),
+                product_questions::id.asc(),
+            ))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                product_questions::created_at.gt(after.created_at).or(
+                    product_questions::created_at
+                        .eq(after.created_at)
+                        .and(product_questions::id.gt(after.id)),
+                ),
+            );
+        }
+        let questions = query
+            .select(QuestionEntity::as_select())
+            .load(conn.deref_mut())?
+            .iter()
+            .map(|entity| entity.to_model(vec![]))
+            .collect();
+
+        let total = if pagination.with_total {
+            Some(with_status().count().get_result(conn.deref_mut())?)
+        } else {
+            None
+        };
+        Ok(Page::new(
+            questions,
+            &pagination,
+            total,
+            |question: &ProductQuestion| Cursor {
+                created_at: question.created_at,
+                id: question.id,
+                key: None,
+            },
+        ))
+    }
+
+    fn update_question_status(
+        &mut self,
+        id: i64,
+        new_status: ModerationStatus,
+    ) -> Result<ProductQuestion, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(product_questions::table.filter(product_questions::id.eq(id)))
+            .set((
+                product_questions::status.eq(new_status.to_string()),
+                product_questions::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(QuestionEntity::as_returning())
+            .get_result(conn.deref_mut())
+            .map(|entity| entity.to_model(vec![]))
+            .map_err(Into::into)
+    }
+
+    fn create_answer(&mut self, answer: ProductAnswer) -> Result<ProductAnswer, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(product_answers::table)
+            .values(NewAnswerEntity {
+                question_id: answer.question_id,
+                user_id: answer.user_id,
+                body: answer.body,
+                status: answer.status.to_string(),
+                is_staff: answer.is_staff,
+                is_verified_buyer: answer.is_verified_buyer,
+            })
+            .returning(AnswerEntity::as_returning())
+            .get_result(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_answer_by_id(&mut self, id: i64) -> Result<ProductAnswer, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_answers::table
+            .filter(product_answers::id.eq(id))
+            .select(AnswerEntity::as_select())
+            .first(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_answers_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<ProductAnswer>, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let with_status = || {
+            product_answers::table
+                .filter(product_answers::status.eq(status.to_string()))
+                .into_boxed()
+        };
+        let mut query = with_status()
+            .order((product_answers::created_at.asc(), product_answers::id.asc()))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                product_answers::created_at
+                    .gt(after.created_at)
+                    .or(product_answers::created_at
+                        .eq(after.created_at)
+                        .and(product_answers::id.gt(after.id))),
+            );
+        }
+        let answers = query
+            .select(AnswerEntity::as_select())
+            .load(conn.deref_mut())?
+            .iter()
+            .map(|entity| entity.to_model())
+            .collect();
+
+        let total = if pagination.with_total {
+            Some(with_status().count().get_result(conn.deref_mut())?)
+        } else {
+            None
+        };
+        Ok(Page::new(
+            answers,
+            &pagination,
+            total,
+            |answer: &ProductAnswer| Cursor {
+                created_at: answer.created_at,
+                id: answer.id,
+                key: None,
+            },
+        ))
+    }
+
+    fn update_answer_status(
+        &mut self,
+        id: i64,
+        new_status: ModerationStatus,
+    ) -> Result<ProductAnswer, QuestionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(product_answers::table.filter(product_answers::id.eq(id)))
+            .set((
+                product_answers::status.eq(new_status.to_string()),
+                product_answers::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(AnswerEntity::as_returning())
+            .get_result(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+}
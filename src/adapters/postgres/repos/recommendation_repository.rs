@@ -0,0 +1,132 @@
+use std::ops::DerefMut;
+
+use diesel::{
+    ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+    r2d2::{ConnectionManager, Pool},
+    sql_types::{BigInt, Double},
+};
+
+use crate::{
+    adapters::postgres::{entities::recommendation::RecommendationEntity, schema::*},
+    core::{
+        models::recommendation::{
+            CO_PURCHASE_WEIGHT, MAX_CATEGORY_CANDIDATES, ProductRecommendation,
+            RecommendationError, SAME_CATEGORY_WEIGHT,
+        },
+        ports::recommendation_repository::RecommendationRepository,
+    },
+};
+
+pub struct RecommendationRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl RecommendationRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        RecommendationRepositoryImpl { conn }
+    }
+}
+
+impl RecommendationRepository for RecommendationRepositoryImpl {
+    fn refresh_recommendations(&mut self, limit: i64) -> Result<usize, RecommendationError> {
+        let mut conn = self.conn.get().unwrap();
+
+        // Cancelled and failed orders do not count, like for popularity.
+        // Only the rows of products whose recommendations changed are
+        // written, the others are left alone.
+        diesel::sql_query(
+            "WITH co_purchases AS ( \
+                 SELECT a.product_id, b.product_id AS related_product_id, \
+                     count(DISTINCT a.order_id) * $1 AS score \
+                 FROM order_items a \
+                 JOIN order_items b \
+                     ON b.order_id = a.order_id AND b.product_id <> a.product_id \
+                 JOIN orders ON orders.id = a.order_id \
+                 WHERE orders.status NOT IN ('Cancelled', 'Error') \
+                 GROUP BY a.product_id, b.product_id \
+             ), category_candidates AS ( \
+                 SELECT category_id, product_id \
+                 FROM ( \
+                     SELECT product_categories.category_id, product_categories.product_id, \
+                         row_number() OVER ( \
+                             PARTITION BY product_categories.category_id \
+                             ORDER BY products.units_sold DESC, products.id ASC \
+                         ) AS position \
+                     FROM product_categories \
+                     JOIN products ON products.id = product_categories.product_id \
+                     WHERE products.deleted_at IS NULL \
+                 ) AS members \
+                 WHERE position <= $4 \
+             ), same_categories AS ( \
+                 SELECT a.product_id, b.product_id AS related_product_id, \
+                     count(*) * $2 AS score \
+                 FROM product_categories a \
+                 JOIN category_candidates b \
+                     ON b.category_id = a.category_id AND b.product_id <> a.product_id \
+                 JOIN categories ON categories.id = a.category_id \
+                 WHERE categories.deleted_at IS NULL \
+                 GROUP BY a.product_id, b.product_id \
+             ), scores AS ( \
+                 SELECT product_id, related_product_id, sum(score) AS score \
+                 FROM ( \
+                     SELECT * FROM co_purchases \
+                     UNION ALL \
+                     SELECT * FROM same_categories \
+                 ) AS pairs \
+                 GROUP BY product_id, related_product_id \
+             ), ranked AS ( \
+                 SELECT scores.product_id, scores.related_product_id, scores.score, \
+                     row_number() OVER ( \
+                         PARTITION BY scores.product_id \
+                         ORDER BY scores.score DESC, scores.related_product_id ASC \
+                     ) AS position \
+                 FROM scores \
+                 JOIN products ON products.id = scores.product_id \
+                 JOIN products related ON related.id = scores.related_product_id \
+                 WHERE products.deleted_at IS NULL AND related.deleted_at IS NULL \
+             ), kept AS ( \
+                 SELECT * FROM ranked WHERE position <= $3 \
+             ), removed AS ( \
+                 DELETE FROM product_recommendations \
+                 WHERE NOT EXISTS ( \
+                     SELECT 1 FROM kept \
+                     WHERE kept.product_id = product_recommendations.product_id \
+                         AND kept.related_product_id = \
+                             product_recommendations.related_product_id \
+                 ) \
+             ) \
+             INSERT INTO product_recommendations \
+                 (product_id, related_product_id, score, position) \
+             SELECT product_id, related_product_id, score, position \
+             FROM kept \
+             ON CONFLICT ON CONSTRAINT pk_product_recommendations DO UPDATE \
+             SET score = EXCLUDED.score, position = EXCLUDED.position \
+             WHERE (product_recommendations.score, product_recommendations.position) \
+                 IS DISTINCT FROM (EXCLUDED.score, EXCLUDED.position)",
+        )
+        .bind::<Double, _>(CO_PURCHASE_WEIGHT)
+        .bind::<Double, _>(SAME_CATEGORY_WEIGHT)
+        .bind::<BigInt, _>(limit)
+        .bind::<BigInt, _>(MAX_CATEGORY_CANDIDATES)
+        .execute(conn.deref_mut())
+        .map_err(Into::into)
+    }
+
+    fn find_recommendations(
+        &mut self,
+        product_ids: &[i64],
+    ) -> Result<Vec<ProductRecommendation>, RecommendationError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_recommendations::table
+            .filter(product_recommendations::product_id.eq_any(product_ids))
+            .order((
+                product_recommendations::product_id.asc(),
+                product_recommendations::position.asc(),
+            ))
+            .select(RecommendationEntity::as_select())
+            .load::<RecommendationEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+}
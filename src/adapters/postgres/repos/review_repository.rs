@@ -0,0 +1,208 @@
+use std::ops::DerefMut;
+
+use chrono::Utc;
+use diesel::{
+    BoolExpressionMethods, Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::review::{NewReviewEntity, NewReviewVoteEntity, ReviewEntity},
+        schema::{product_review_votes, product_reviews},
+    },
+    core::{
+        models::{
+            moderation::ModerationStatus,
+            pagination::{Cursor, Page, Pagination},
+            review::{Review, ReviewError},
+        },
+        ports::review_repository::ReviewRepository,
+    },
+};
+
+pub struct ReviewRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl ReviewRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        ReviewRepositoryImpl { conn }
+    }
+
+    fn to_page(
+        entities: Vec<ReviewEntity>,
+        pagination: &Pagination,
+        total: Option<i64>,
+    ) -> Page<Review> {
+        let reviews = entities.iter().map(|entity| entity.to_model()).collect();
+        Page::new(reviews, pagination, total, |review: &Review| Cursor {
+            created_at: review.created_at,
+            id: review.id,
+            key: None,
+        })
+    }
+}
+
+impl ReviewRepository for ReviewRepositoryImpl {
+    fn create_review(&mut self, review: Review) -> Result<Review, ReviewError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(product_reviews::table)
+            .values(NewReviewEntity {
+                product_id: review.product_id,
+                user_id: review.user_id,
+                rating: review.rating,
+                body: review.body,
+                status: ModerationStatus::Pending.to_string(),
+            })
+            .returning(ReviewEntity::as_returning())
+            .get_result(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_review_by_id(&mut self, id: i64) -> Result<Review, ReviewError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_reviews::table
+            .filter(product_reviews::id.eq(id))
+            .select(ReviewEntity::as_select())
+            .first(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_reviews_by_product_id(
+        &mut self,
+        product_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<Review>, ReviewError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let approved = || {
+            product_reviews::table
+                .filter(product_reviews::product_id.eq(product_id))
+                .filter(product_reviews::status.eq(ModerationStatus::Approved.to_string()))
+                .into_boxed()
+        };
+        let mut query = approved()
+            .order((
+                product_reviews::created_at.desc(),
+                product_reviews::id.desc(),
+            ))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                product_reviews::created_at
+                    .lt(after.created_at)
+                    .or(product_reviews::created_at
+                        .eq(after.created_at)
+                        .and(product_reviews::id.lt(after.id))),
+            );
+        }
+        let entities = query
+            .select(ReviewEntity::as_select())
+            .load(conn.deref_mut())?;
+
+        let total = if pagination.with_total {
+            Some(approved().count().get_result(conn.deref_mut())?)
+        } else {
+            None
+        };
+        Ok(Self::to_page(entities, &pagination, total))
+    }
+
+    fn find_reviews_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<Review>, ReviewError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let with_status = || {
+            product_reviews::table
+                .filter(product_reviews::status.eq(status.to_string()))
+                .into_boxed()
+        };
+        let mut query = with_status()
+            .order((product_reviews::created_at.asc(), product_reviews::id.asc()))
+            .limit(pagination.fetch_limit());
+        if let Some(after) = &pagination.after {
+            query = query.filter(
+                product_reviews::created_at
+                    .gt(after.created_at)
+                    .or(product_reviews::created_at
+                        .eq(after.created_at)
+                        .and(product_reviews::id.gt(after.id))),
+            );
+        }
+        let entities = query
+            .select(ReviewEntity::as_select())
+            .load(conn.deref_mut())?;
+
+        let total = if pagination.with_total {
+            Some(with_status().count().get_result(conn.deref_mut())?)
+        } else {
+            None
+        };
+        Ok(Self::to_page(entities, &pagination, total))
+    }
+
+    fn update_review_status(
+        &mut self,
+        id: i64,
+        new_status: ModerationStatus,
+    ) -> Result<Review, ReviewError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(product_reviews::table.filter(product_reviews::id.eq(id)))
+            .set((
+                product_reviews::status.eq(new_status.to_string()),
+                product_reviews::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(ReviewEntity::as_returning())
+            .get_result(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn update_review_response(
+        &mut self,
+        id: i64,
+        new_response: Option<String>,
+    ) -> Result<Review, ReviewError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let responded_at = new_response.as_ref().map(|_| Utc::now().naive_utc());
+        diesel::update(product_reviews::table.filter(product_reviews::id.eq(id)))
+            .set((
+                product_reviews::response.eq(new_response),
+                product_reviews::responded_at.eq(responded_at),
+                product_reviews::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(ReviewEntity::as_returning())
+            .get_result(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn add_helpful_vote(&mut self, review_id: i64, user_id: i64) -> Result<Review, ReviewError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<ReviewEntity, ReviewError, _>(|conn| {
+                diesel::insert_into(product_review_votes::table)
+                    .values(NewReviewVoteEntity { review_id, user_id })
+                    .execute(conn)?;
+
+                // The helpful count is refreshed by a trigger on the votes.
+                Ok(product_reviews::table
+                    .filter(product_reviews::id.eq(review_id))
+                    .select(ReviewEntity::as_select())
+                    .first(conn)?)
+            })
+            .map(|entity| entity.to_model())
+    }
+}
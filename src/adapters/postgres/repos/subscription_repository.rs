@@ -0,0 +1,261 @@
+use std::ops::DerefMut;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::{
+    ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::subscription::{
+            NewSubscriptionEntity, NewSubscriptionPlanEntity, SubscriptionEntity,
+            SubscriptionPlanEntity,
+        },
+        schema::{subscription_plans, subscriptions},
+    },
+    core::{
+        models::subscription::{
+            Subscription, SubscriptionError, SubscriptionPlan, SubscriptionStatus,
+        },
+        ports::subscription_repository::SubscriptionRepository,
+    },
+};
+
+pub struct SubscriptionRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl SubscriptionRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        SubscriptionRepositoryImpl { conn }
+    }
+
+    fn load_subscription(
+        conn: &mut PgConnection,
+        id: i64,
+    ) -> Result<Subscription, SubscriptionError> {
+        let (subscription, plan) = subscriptions::table
+            .inner_join(subscription_plans::table)
+            .filter(subscriptions::id.eq(id))
+            .select((
+                SubscriptionEntity::as_select(),
+                SubscriptionPlanEntity::as_select(),
+            ))
+            .first::<(SubscriptionEntity, SubscriptionPlanEntity)>(conn)?;
+        Ok(subscription.to_model(&plan))
+    }
+}
+
+impl SubscriptionRepository for SubscriptionRepositoryImpl {
+    fn create_plan(
+        &mut self,
+        plan: SubscriptionPlan,
+    ) -> Result<SubscriptionPlan, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(subscription_plans::table)
+            .values(NewSubscriptionPlanEntity {
+                product_id: plan.product_id,
+                interval_days: plan.interval_days,
+                discount_percent: plan.discount_percent,
+                is_active: plan.is_active,
+            })
+            .get_result::<SubscriptionPlanEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_plan_by_id(&mut self, id: i64) -> Result<SubscriptionPlan, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        subscription_plans::table
+            .filter(subscription_plans::id.eq(id))
+            .first::<SubscriptionPlanEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_plans_by_product_id(
+        &mut self,
+        product_id: i64,
+        active_only: bool,
+    ) -> Result<Vec<SubscriptionPlan>, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let mut query = subscription_plans::table
+            .filter(subscription_plans::product_id.eq(product_id))
+            .order(subscription_plans::interval_days.asc())
+            .into_boxed();
+        if active_only {
+            query = query.filter(subscription_plans::is_active.eq(true));
+        }
+        query
+            .load::<SubscriptionPlanEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn update_plan(
+        &mut self,
+        plan: SubscriptionPlan,
+    ) -> Result<SubscriptionPlan, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(subscription_plans::table.filter(subscription_plans::id.eq(plan.id)))
+            .set((
+                subscription_plans::discount_percent.eq(plan.discount_percent),
+                subscription_plans::is_active.eq(plan.is_active),
+                subscription_plans::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result::<SubscriptionPlanEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn delete_plan(&mut self, id: i64) -> Result<(), SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(subscription_plans::table.filter(subscription_plans::id.eq(id)))
+            .execute(conn.deref_mut())
+            .map_err(|err| match err {
+                // Subscribed to.
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => SubscriptionError::InvalidData,
+                err => err.into(),
+            })
+            .and_then(|affected_rows| {
+                if affected_rows == 0 {
+                    Err(SubscriptionError::NotFound)
+                } else {
+                    Ok(())
+                }
+            })
+    }
+
+    fn create_subscription(
+        &mut self,
+        subscription: Subscription,
+    ) -> Result<Subscription, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let id = diesel::insert_into(subscriptions::table)
+            .values(NewSubscriptionEntity {
+                user_id: subscription.user_id,
+                plan_id: subscription.plan.id,
+                variant_id: subscription.variant_id,
+                quantity: subscription.quantity,
+                status: subscription.status.to_string(),
+                next_run_at: subscription.next_run_at,
+            })
+            .returning(subscriptions::id)
+            .get_result::<i64>(conn.deref_mut())?;
+
+        Self::load_subscription(conn.deref_mut(), id)
+    }
+
+    fn find_subscription_by_id(&mut self, id: i64) -> Result<Subscription, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        Self::load_subscription(conn.deref_mut(), id)
+    }
+
+    fn find_subscriptions_by_user_id(
+        &mut self,
+        user_id: i64,
+    ) -> Result<Vec<Subscription>, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let records = subscriptions::table
+            .inner_join(subscription_plans::table)
+            .filter(subscriptions::user_id.eq(user_id))
+            .order((subscriptions::created_at.desc(), subscriptions::id.desc()))
+            .select((
+                SubscriptionEntity::as_select(),
+                SubscriptionPlanEntity::as_select(),
+            ))
+            .load::<(SubscriptionEntity, SubscriptionPlanEntity)>(conn.deref_mut())?;
+
+        Ok(records
+            .iter()
+            .map(|(subscription, plan)| subscription.to_model(plan))
+            .collect())
+    }
+
+    fn update_subscription_status(
+        &mut self,
+        id: i64,
+        new_status: SubscriptionStatus,
+        new_next_run_at: NaiveDateTime,
+    ) -> Result<Subscription, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(subscriptions::table.filter(subscriptions::id.eq(id)))
+            .set((
+                subscriptions::status.eq(new_status.to_string()),
+                subscriptions::next_run_at.eq(new_next_run_at),
+                subscriptions::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn.deref_mut())?;
+
+        Self::load_subscription(conn.deref_mut(), id)
+    }
+
+    fn find_due_subscriptions(
+        &mut self,
+        now: NaiveDateTime,
+    ) -> Result<Vec<Subscription>, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        let records = subscriptions::table
+            .inner_join(subscription_plans::table)
+            .filter(subscriptions::status.eq(SubscriptionStatus::Active.to_string()))
+            .filter(subscriptions::next_run_at.le(now))
+            .order((subscriptions::next_run_at.asc(), subscriptions::id.asc()))
+            .select((
+                SubscriptionEntity::as_select(),
+                SubscriptionPlanEntity::as_select(),
+            ))
+            .load::<(SubscriptionEntity, SubscriptionPlanEntity)>(conn.deref_mut())?;
+
+        Ok(records
+            .iter()
+            .map(|(subscription, plan)| subscription.to_model(plan))
+            .collect())
+    }
+
+    fn claim_subscription_run(
+        &mut self,
+        id: i64,
+        run_at: NaiveDateTime,
+        next_run_at: NaiveDateTime,
+    ) -> Result<bool, SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(
+            subscriptions::table
+                .filter(subscriptions::id.eq(id))
+                .filter(subscriptions::status.eq(SubscriptionStatus::Active.to_string()))
+                .filter(subscriptions::next_run_at.eq(run_at)),
+        )
+        .set((
+            subscriptions::next_run_at.eq(next_run_at),
+            subscriptions::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn.deref_mut())
+        .map(|affected_rows| affected_rows > 0)
+        .map_err(Into::into)
+    }
+
+    fn set_last_order(&mut self, id: i64, order_id: i64) -> Result<(), SubscriptionError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(subscriptions::table.filter(subscriptions::id.eq(id)))
+            .set(subscriptions::last_order_id.eq(Some(order_id)))
+            .execute(conn.deref_mut())
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+}
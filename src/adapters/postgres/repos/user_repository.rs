@@ -46,12 +46,10 @@ impl UserRepository for UserRepositoryImpl {
             .get_result::<UserEntity>(conn.deref_mut())
             .map(|entity| entity.to_model())
             .map_err(|err| match err {
-                diesel::result::Error::DatabaseError(kind, _) => match kind {
-                    diesel::result::DatabaseErrorKind::UniqueViolation => {
-                        UserError::EmailAlreadyExists
-                    }
-                    _ => UserError::InternalError,
-                },
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::UniqueViolation,
+                    _,
+                ) => UserError::EmailAlreadyExists,
                 _ => UserError::InternalError,
             })
     }
@@ -0,0 +1,234 @@
+use std::ops::DerefMut;
+
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+    r2d2::{ConnectionManager, Pool},
+};
+
+use crate::{
+    adapters::postgres::{
+        entities::product::{
+            NewVariationEntity, NewVariationOptionEntity, ProductVariationOptionEntity,
+            VariationEntity, VariationOptionEntity,
+        },
+        schema::{product_variation_options, variation_options, variations},
+    },
+    core::{
+        models::product::{ProductError, Variation, VariationOption},
+        ports::variation_repository::VariationRepository,
+    },
+};
+
+pub struct VariationRepositoryImpl {
+    conn: Pool<ConnectionManager<PgConnection>>,
+}
+
+impl VariationRepositoryImpl {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>) -> Self {
+        VariationRepositoryImpl { conn }
+    }
+}
+
+impl VariationRepository for VariationRepositoryImpl {
+    fn create_variation(
+        &mut self,
+        category_id: i64,
+        name: String,
+    ) -> Result<Variation, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(variations::table)
+            .values(NewVariationEntity { category_id, name })
+            .get_result::<VariationEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_variation_by_id(&mut self, id: i64) -> Result<Variation, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        variations::table
+            .filter(variations::id.eq(id))
+            .first::<VariationEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_variations_by_category_id(
+        &mut self,
+        category_id: i64,
+    ) -> Result<Vec<Variation>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        variations::table
+            .filter(variations::category_id.eq(category_id))
+            .order(variations::id.asc())
+            .load::<VariationEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn update_variation(&mut self, id: i64, new_name: String) -> Result<Variation, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(variations::table.filter(variations::id.eq(id)))
+            .set(variations::name.eq(new_name))
+            .get_result::<VariationEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn delete_variation(&mut self, id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(variations::table.filter(variations::id.eq(id)))
+            .execute(conn.deref_mut())
+            .map(|affected_rows| {
+                if affected_rows == 0 {
+                    Err(ProductError::NotFound)
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|_| ProductError::InternalError)?
+    }
+
+    fn create_variation_option(
+        &mut self,
+        variation_id: i64,
+        value: String,
+    ) -> Result<VariationOption, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(variation_options::table)
+            .values(NewVariationOptionEntity {
+                variation_id,
+                value,
+            })
+            .get_result::<VariationOptionEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(|err| match err {
+                diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::ForeignKeyViolation,
+                    _,
+                ) => ProductError::NotFound,
+                err => err.into(),
+            })
+    }
+
+    fn find_variation_option_by_id(&mut self, id: i64) -> Result<VariationOption, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        variation_options::table
+            .filter(variation_options::id.eq(id))
+            .first::<VariationOptionEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn find_variation_options_by_variation_id(
+        &mut self,
+        variation_id: i64,
+    ) -> Result<Vec<VariationOption>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        variation_options::table
+            .filter(variation_options::variation_id.eq(variation_id))
+            .order(variation_options::id.asc())
+            .load::<VariationOptionEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn update_variation_option(
+        &mut self,
+        id: i64,
+        new_value: String,
+    ) -> Result<VariationOption, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::update(variation_options::table.filter(variation_options::id.eq(id)))
+            .set(variation_options::value.eq(new_value))
+            .get_result::<VariationOptionEntity>(conn.deref_mut())
+            .map(|entity| entity.to_model())
+            .map_err(Into::into)
+    }
+
+    fn delete_variation_option(&mut self, id: i64) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(variation_options::table.filter(variation_options::id.eq(id)))
+            .execute(conn.deref_mut())
+            .map(|affected_rows| {
+                if affected_rows == 0 {
+                    Err(ProductError::NotFound)
+                } else {
+                    Ok(())
+                }
+            })
+            .map_err(|_| ProductError::InternalError)?
+    }
+
+    fn find_variation_options_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<VariationOption>, ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        product_variation_options::table
+            .inner_join(variation_options::table)
+            .filter(product_variation_options::product_id.eq(product_id))
+            .select(VariationOptionEntity::as_select())
+            .order((
+                variation_options::variation_id.asc(),
+                variation_options::id.asc(),
+            ))
+            .load::<VariationOptionEntity>(conn.deref_mut())
+            .map(|entities| entities.iter().map(|entity| entity.to_model()).collect())
+            .map_err(Into::into)
+    }
+
+    fn add_product_variation_option(
+        &mut self,
+        product_id: i64,
+        variation_option_id: i64,
+    ) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::insert_into(product_variation_options::table)
+            .values(ProductVariationOptionEntity {
+                product_id,
+                variation_option_id,
+            })
+            .on_conflict_do_nothing()
+            .execute(conn.deref_mut())
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    fn remove_product_variation_option(
+        &mut self,
+        product_id: i64,
+        variation_option_id: i64,
+    ) -> Result<(), ProductError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::delete(
+            product_variation_options::table.filter(
+                product_variation_options::product_id
+                    .eq(product_id)
+                    .and(product_variation_options::variation_option_id.eq(variation_option_id)),
+            ),
+        )
+        .execute(conn.deref_mut())
+        .map(|affected_rows| {
+            if affected_rows == 0 {
+                Err(ProductError::NotFound)
+            } else {
+                Ok(())
+            }
+        })
+        .map_err(|_| ProductError::InternalError)?
+    }
+}
@@ -1,5 +1,29 @@
 // @generated automatically by Diesel CLI.
 
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "regconfig", schema = "pg_catalog"))]
+    pub struct Regconfig;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "tsvector", schema = "pg_catalog"))]
+    pub struct Tsvector;
+}
+
+diesel::table! {
+    attributes (id) {
+        id -> Int8,
+        category_id -> Int8,
+        name -> Text,
+        data_type -> Text,
+        unit -> Nullable<Text>,
+        is_filterable -> Bool,
+        options -> Array<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     cart_items (id) {
         id -> Int8,
@@ -8,6 +32,9 @@ diesel::table! {
         quantity -> Int4,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        customizations -> Jsonb,
+        customization_price -> Float8,
+        variant_id -> Nullable<Int8>,
     }
 }
 
@@ -17,6 +44,8 @@ diesel::table! {
         user_id -> Int8,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        name -> Text,
+        is_active -> Bool,
     }
 }
 
@@ -28,6 +57,30 @@ diesel::table! {
         parent_id -> Nullable<Int8>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        slug -> Text,
+        meta_title -> Nullable<Text>,
+        meta_description -> Nullable<Text>,
+        canonical_url -> Nullable<Text>,
+        deleted_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    category_slug_redirects (slug) {
+        slug -> Text,
+        category_id -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    order_item_downloads (order_item_id) {
+        order_item_id -> Int8,
+        max_downloads -> Int4,
+        download_count -> Int4,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -40,6 +93,8 @@ diesel::table! {
         price_at_time_of_order -> Float8,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        customizations -> Jsonb,
+        variant_id -> Nullable<Int8>,
     }
 }
 
@@ -51,10 +106,192 @@ diesel::table! {
         status -> Text,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        requires_shipping -> Bool,
+        paid_at -> Nullable<Timestamp>,
     }
 }
 
 diesel::table! {
+    product_answers (id) {
+        id -> Int8,
+        question_id -> Int8,
+        user_id -> Int8,
+        body -> Text,
+        status -> Text,
+        is_staff -> Bool,
+        is_verified_buyer -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_attribute_values (product_id, attribute_id) {
+        product_id -> Int8,
+        attribute_id -> Int8,
+        text_value -> Nullable<Text>,
+        number_value -> Nullable<Float8>,
+        bool_value -> Nullable<Bool>,
+    }
+}
+
+diesel::table! {
+    product_bundle_items (bundle_id, component_id) {
+        bundle_id -> Int8,
+        component_id -> Int8,
+        quantity -> Int4,
+    }
+}
+
+diesel::table! {
+    product_bundles (product_id) {
+        product_id -> Int8,
+        discount_percent -> Nullable<Float8>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_categories (id) {
+        id -> Int8,
+        product_id -> Int8,
+        category_id -> Int8,
+        is_primary -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_customization_fields (id) {
+        id -> Int8,
+        product_id -> Int8,
+        name -> Text,
+        field_type -> Text,
+        required -> Bool,
+        max_length -> Nullable<Int4>,
+        extra_price -> Float8,
+    }
+}
+
+diesel::table! {
+    product_files (id) {
+        id -> Int8,
+        product_id -> Int8,
+        storage_key -> Text,
+        file_name -> Text,
+        content_type -> Text,
+        size_bytes -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_images (id) {
+        id -> Int8,
+        product_id -> Int8,
+        storage_key -> Text,
+        url -> Text,
+        content_type -> Text,
+        alt_text -> Nullable<Text>,
+        position -> Int4,
+        is_primary -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        width -> Nullable<Int4>,
+        height -> Nullable<Int4>,
+        renditions -> Jsonb,
+    }
+}
+
+diesel::table! {
+    product_questions (id) {
+        id -> Int8,
+        product_id -> Int8,
+        user_id -> Int8,
+        body -> Text,
+        status -> Text,
+        answer_count -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_recommendations (product_id, related_product_id) {
+        product_id -> Int8,
+        related_product_id -> Int8,
+        score -> Float8,
+        position -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_review_votes (review_id, user_id) {
+        review_id -> Int8,
+        user_id -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_reviews (id) {
+        id -> Int8,
+        product_id -> Int8,
+        user_id -> Int8,
+        rating -> Int4,
+        body -> Text,
+        status -> Text,
+        response -> Nullable<Text>,
+        responded_at -> Nullable<Timestamp>,
+        helpful_count -> Int8,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_variant_options (variant_id, variation_option_id) {
+        variant_id -> Int8,
+        variation_option_id -> Int8,
+    }
+}
+
+diesel::table! {
+    product_variants (id) {
+        id -> Int8,
+        product_id -> Int8,
+        sku -> Text,
+        price -> Nullable<Float8>,
+        stock -> Int4,
+        image -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    product_variation_options (product_id, variation_option_id) {
+        product_id -> Int8,
+        variation_option_id -> Int8,
+    }
+}
+
+diesel::table! {
+    product_view_counts (product_id, viewed_on) {
+        product_id -> Int8,
+        viewed_on -> Date,
+        views -> Int8,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
     products (id) {
         id -> Int8,
         name -> Text,
@@ -65,6 +302,66 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         category_id -> Nullable<Int8>,
+        search_document -> Tsvector,
+        units_sold -> Int8,
+        slug -> Text,
+        meta_title -> Nullable<Text>,
+        meta_description -> Nullable<Text>,
+        canonical_url -> Nullable<Text>,
+        status -> Text,
+        publish_at -> Nullable<Timestamp>,
+        unpublish_at -> Nullable<Timestamp>,
+        deleted_at -> Nullable<Timestamp>,
+        sku -> Nullable<Text>,
+        rating_average -> Float8,
+        rating_count -> Int8,
+        trending_views -> Int8,
+        is_digital -> Bool,
+    }
+}
+
+diesel::table! {
+    product_slug_redirects (slug) {
+        slug -> Text,
+        product_id -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Regconfig;
+
+    search_settings (id) {
+        id -> Bool,
+        language -> Regconfig,
+    }
+}
+
+diesel::table! {
+    subscription_plans (id) {
+        id -> Int8,
+        product_id -> Int8,
+        interval_days -> Int4,
+        discount_percent -> Float8,
+        is_active -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    subscriptions (id) {
+        id -> Int8,
+        user_id -> Int8,
+        plan_id -> Int8,
+        variant_id -> Nullable<Int8>,
+        quantity -> Int4,
+        status -> Text,
+        next_run_at -> Timestamp,
+        last_order_id -> Nullable<Int8>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -100,23 +397,81 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(attributes -> categories (category_id));
 diesel::joinable!(cart_items -> carts (cart_id));
+diesel::joinable!(cart_items -> product_variants (variant_id));
 diesel::joinable!(cart_items -> products (product_id));
 diesel::joinable!(carts -> users (user_id));
+diesel::joinable!(category_slug_redirects -> categories (category_id));
+diesel::joinable!(order_item_downloads -> order_items (order_item_id));
 diesel::joinable!(order_items -> orders (order_id));
+diesel::joinable!(order_items -> product_variants (variant_id));
 diesel::joinable!(order_items -> products (product_id));
 diesel::joinable!(orders -> users (user_id));
+diesel::joinable!(product_answers -> product_questions (question_id));
+diesel::joinable!(product_answers -> users (user_id));
+diesel::joinable!(product_attribute_values -> attributes (attribute_id));
+diesel::joinable!(product_attribute_values -> products (product_id));
+diesel::joinable!(product_bundle_items -> product_bundles (bundle_id));
+diesel::joinable!(product_bundle_items -> products (component_id));
+diesel::joinable!(product_bundles -> products (product_id));
+diesel::joinable!(product_categories -> categories (category_id));
+diesel::joinable!(product_categories -> products (product_id));
+diesel::joinable!(product_customization_fields -> products (product_id));
+diesel::joinable!(product_files -> products (product_id));
+diesel::joinable!(product_images -> products (product_id));
+diesel::joinable!(product_questions -> products (product_id));
+diesel::joinable!(product_questions -> users (user_id));
+diesel::joinable!(product_review_votes -> product_reviews (review_id));
+diesel::joinable!(product_review_votes -> users (user_id));
+diesel::joinable!(product_reviews -> products (product_id));
+diesel::joinable!(product_reviews -> users (user_id));
+diesel::joinable!(product_variant_options -> product_variants (variant_id));
+diesel::joinable!(product_variant_options -> variation_options (variation_option_id));
+diesel::joinable!(product_slug_redirects -> products (product_id));
+diesel::joinable!(product_variants -> products (product_id));
+diesel::joinable!(product_variation_options -> products (product_id));
+diesel::joinable!(product_variation_options -> variation_options (variation_option_id));
+diesel::joinable!(product_view_counts -> products (product_id));
 diesel::joinable!(products -> categories (category_id));
+diesel::joinable!(subscription_plans -> products (product_id));
+diesel::joinable!(subscriptions -> orders (last_order_id));
+diesel::joinable!(subscriptions -> product_variants (variant_id));
+diesel::joinable!(subscriptions -> subscription_plans (plan_id));
+diesel::joinable!(subscriptions -> users (user_id));
 diesel::joinable!(variation_options -> variations (variation_id));
 diesel::joinable!(variations -> categories (category_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    attributes,
     cart_items,
     carts,
     categories,
+    category_slug_redirects,
+    order_item_downloads,
     order_items,
     orders,
+    product_answers,
+    product_attribute_values,
+    product_bundle_items,
+    product_bundles,
+    product_categories,
+    product_customization_fields,
+    product_files,
+    product_images,
+    product_questions,
+    product_recommendations,
+    product_review_votes,
+    product_reviews,
+    product_slug_redirects,
+    product_variant_options,
+    product_variants,
+    product_variation_options,
+    product_view_counts,
     products,
+    search_settings,
+    subscription_plans,
+    subscriptions,
     users,
     variation_options,
     variations,
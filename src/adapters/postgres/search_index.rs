@@ -0,0 +1,131 @@
+use std::ops::DerefMut;
+
+use diesel::{
+    Connection, PgConnection, RunQueryDsl,
+    r2d2::{ConnectionManager, Pool},
+    result::Error as DieselError,
+    sql_types::{Array, BigInt, Int8, Text},
+};
+
+use crate::{
+    adapters::postgres::entities::product::SearchHitEntity,
+    core::{
+        models::product::Product,
+        ports::search_index::{SearchHit, SearchIndex, SearchIndexError},
+    },
+};
+
+/// Description excerpt with the terms of `query` highlighted.
+const SNIPPET: &str = "ts_headline($1::regconfig, products.description, query, \
+    'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=30, MinWords=10')";
+
+/// Lowest trigram word similarity for a name to count as a typo of the query.
+const SIMILARITY_THRESHOLD: f32 = 0.3;
+
+/// Searches the `search_document` column of the products, which the database
+/// triggers keep up to date; indexing a product is therefore a no-op.
+pub struct PostgresSearchIndex {
+    conn: Pool<ConnectionManager<PgConnection>>,
+    search_language: String,
+}
+
+impl PostgresSearchIndex {
+    pub fn new(conn: Pool<ConnectionManager<PgConnection>>, search_language: String) -> Self {
+        PostgresSearchIndex {
+            conn,
+            search_language,
+        }
+    }
+}
+
+/// Applies [`SIMILARITY_THRESHOLD`] to the `<%` operator for the rest of the
+/// current transaction.
+pub(crate) fn set_similarity_threshold(conn: &mut PgConnection) -> Result<(), DieselError> {
+    diesel::sql_query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+        .bind::<Text, _>(SIMILARITY_THRESHOLD.to_string())
+        .execute(conn)?;
+    Ok(())
+}
+
+impl SearchIndex for PostgresSearchIndex {
+    fn index_products(&mut self, _products: &[Product]) -> Result<(), SearchIndexError> {
+        Ok(())
+    }
+
+    fn remove_product(&mut self, _product_id: i64) -> Result<(), SearchIndexError> {
+        Ok(())
+    }
+
+    fn rebuild(&mut self, products: &[Product]) -> Result<(), SearchIndexError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::sql_query(
+            "UPDATE products \
+             SET search_document = product_search_document(id, name, description) \
+             WHERE id = ANY($1)",
+        )
+        .bind::<Array<Int8>, _>(
+            products
+                .iter()
+                .map(|product| product.id)
+                .collect::<Vec<_>>(),
+        )
+        .execute(conn.deref_mut())
+        .map(|_| ())
+        .map_err(|_| SearchIndexError::InternalError)
+    }
+
+    fn search(
+        &mut self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, SearchIndexError> {
+        let mut conn = self.conn.get().unwrap();
+
+        diesel::sql_query(format!(
+            "SELECT products.id, \
+                    ts_rank_cd(products.search_document, query) AS rank, \
+                    {SNIPPET} AS snippet \
+             FROM products, websearch_to_tsquery($1::regconfig, $2) AS query \
+             WHERE products.search_document @@ query \
+             ORDER BY rank DESC, products.id ASC \
+             LIMIT $3 OFFSET $4"
+        ))
+        .bind::<Text, _>(&self.search_language)
+        .bind::<Text, _>(query)
+        .bind::<BigInt, _>(limit as i64)
+        .bind::<BigInt, _>(offset as i64)
+        .load::<SearchHitEntity>(conn.deref_mut())
+        .map(|records| records.iter().map(|record| record.to_model()).collect())
+        .map_err(|_| SearchIndexError::InternalError)
+    }
+
+    fn find_similar(
+        &mut self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, SearchIndexError> {
+        let mut conn = self.conn.get().unwrap();
+
+        conn.deref_mut()
+            .transaction::<Vec<SearchHitEntity>, DieselError, _>(|conn| {
+                set_similarity_threshold(conn)?;
+                diesel::sql_query(format!(
+                    "SELECT products.id, \
+                            word_similarity($2, products.name) AS rank, \
+                            {SNIPPET} AS snippet \
+                     FROM products, websearch_to_tsquery($1::regconfig, $2) AS query \
+                     WHERE $2 <% products.name \
+                     ORDER BY rank DESC, products.id ASC \
+                     LIMIT $3"
+                ))
+                .bind::<Text, _>(&self.search_language)
+                .bind::<Text, _>(query)
+                .bind::<BigInt, _>(limit as i64)
+                .load::<SearchHitEntity>(conn)
+            })
+            .map(|records| records.iter().map(|record| record.to_model()).collect())
+            .map_err(|_| SearchIndexError::InternalError)
+    }
+}
@@ -27,13 +27,13 @@ impl AuthRepository for AuthRepositoryImpl {
         let value = serde_json::to_string(&RefreshToken { user_id })
             .map_err(|_| AuthError::InvalidPayload)?;
 
-        Ok(client
+        client
             .set_ex(
                 format!("user::{}::{}", user_id, token),
                 value,
                 expires_at.and_utc().timestamp().try_into().unwrap(),
             )
-            .map_err(|_| AuthError::InternalError)?)
+            .map_err(|_| AuthError::InternalError)
     }
 
     fn validate_refresh_token(&mut self, token: &str) -> Result<RefreshToken, AuthError> {
@@ -83,7 +83,7 @@ impl AuthRepositoryImpl {
             .map_err(|_| AuthError::InternalError)?
             .collect();
 
-        if let Some(key) = keys.get(0) {
+        if let Some(key) = keys.first() {
             let payload_str: String = client.get(key).map_err(|_| AuthError::InternalError)?;
 
             let payload: RefreshToken =
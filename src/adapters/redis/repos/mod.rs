@@ -1 +1,2 @@
-pub mod auth_repository;
\ No newline at end of file
+pub mod auth_repository;
+pub mod product_view_repository;
\ No newline at end of file
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::r2d2::Pool;
+use r2d2_redis::{
+    RedisConnectionManager,
+    redis::{self, Commands},
+};
+
+use crate::core::{
+    models::view::{ViewError, Viewer},
+    ports::product_view_repository::ProductViewRepository,
+};
+
+/// Hash of the views counted per product since the last flush.
+const VIEW_COUNTS_KEY: &str = "product_views";
+/// Counts being saved to Postgres, renamed from [`VIEW_COUNTS_KEY`].
+const TAKEN_VIEW_COUNTS_KEY: &str = "product_views::taken";
+
+pub struct ProductViewRepositoryImpl {
+    conn: Pool<RedisConnectionManager>,
+}
+
+impl ProductViewRepositoryImpl {
+    pub fn new(conn: Pool<RedisConnectionManager>) -> Self {
+        ProductViewRepositoryImpl { conn }
+    }
+
+    /// Sorted set of the products a viewer saw, scored by when they last saw
+    /// them.
+    fn recently_viewed_key(viewer: &Viewer) -> String {
+        match viewer {
+            Viewer::User(user_id) => format!("recently_viewed::user::{}", user_id),
+            Viewer::Anonymous(token) => format!("recently_viewed::anonymous::{}", token),
+        }
+    }
+}
+
+impl ProductViewRepository for ProductViewRepositoryImpl {
+    fn record_view(
+        &mut self,
+        viewer: Option<&Viewer>,
+        product_id: i64,
+        viewed_at: NaiveDateTime,
+        limit: i64,
+        anonymous_ttl_seconds: u64,
+    ) -> Result<(), ViewError> {
+        let mut client = self.conn.get().map_err(|_| ViewError::InternalError)?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().hincr(VIEW_COUNTS_KEY, product_id, 1).ignore();
+        if let Some(viewer) = viewer {
+            let key = Self::recently_viewed_key(viewer);
+            pipe.zadd(&key, product_id, viewed_at.and_utc().timestamp_millis())
+                .ignore()
+                // Drops all but the `limit` highest scores, the latest views.
+                .zremrangebyrank(&key, 0, -(limit.max(1) as isize) - 1)
+                .ignore();
+            if let Viewer::Anonymous(_) = viewer {
+                pipe.expire(&key, anonymous_ttl_seconds as usize).ignore();
+            }
+        }
+
+        pipe.query::<()>(&mut *client)
+            .map_err(|_| ViewError::InternalError)
+    }
+
+    fn find_recently_viewed(&mut self, viewer: &Viewer, limit: i64) -> Result<Vec<i64>, ViewError> {
+        let mut client = self.conn.get().map_err(|_| ViewError::InternalError)?;
+
+        client
+            .zrevrange(
+                Self::recently_viewed_key(viewer),
+                0,
+                limit.max(1) as isize - 1,
+            )
+            .map_err(|_| ViewError::InternalError)
+    }
+
+    fn take_view_counts(&mut self) -> Result<HashMap<i64, i64>, ViewError> {
+        let mut client = self.conn.get().map_err(|_| ViewError::InternalError)?;
+
+        let taken: bool = client
+            .exists(TAKEN_VIEW_COUNTS_KEY)
+            .map_err(|_| ViewError::InternalError)?;
+        if !taken {
+            let counted: bool = client
+                .exists(VIEW_COUNTS_KEY)
+                .map_err(|_| ViewError::InternalError)?;
+            if !counted {
+                return Ok(HashMap::new());
+            }
+            // Views recorded from now on start a new hash.
+            client
+                .rename::<_, ()>(VIEW_COUNTS_KEY, TAKEN_VIEW_COUNTS_KEY)
+                .map_err(|_| ViewError::InternalError)?;
+        }
+
+        client
+            .hgetall(TAKEN_VIEW_COUNTS_KEY)
+            .map_err(|_| ViewError::InternalError)
+    }
+
+    fn clear_view_counts(&mut self) -> Result<(), ViewError> {
+        let mut client = self.conn.get().map_err(|_| ViewError::InternalError)?;
+
+        client
+            .del::<_, ()>(TAKEN_VIEW_COUNTS_KEY)
+            .map_err(|_| ViewError::InternalError)
+    }
+}
@@ -0,0 +1,284 @@
+use std::io::Read;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::core::ports::blob_storage::{BlobStorage, BlobStorageError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Stores blobs in a bucket of any S3-compatible service (AWS S3, MinIO, ...),
+/// using path-style URLs and AWS Signature Version 4.
+pub struct S3BlobStorage {
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    public_url: String,
+    agent: ureq::Agent,
+}
+
+impl S3BlobStorage {
+    pub fn new(
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+        public_url: Option<String>,
+    ) -> Self {
+        let endpoint = endpoint.trim_end_matches('/').to_string();
+        let public_url = public_url.unwrap_or_else(|| format!("{}/{}", endpoint, bucket));
+
+        S3BlobStorage {
+            endpoint,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+            public_url,
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    fn host(&self) -> &str {
+        let without_scheme = self
+            .endpoint
+            .split_once("://")
+            .map_or(self.endpoint.as_str(), |(_, rest)| rest);
+        without_scheme.split('/').next().unwrap_or_default()
+    }
+
+    /// Builds a signed request for `key`; `payload` is the request body.
+    fn request(&self, method: &str, key: &str, payload: &[u8]) -> ureq::Request {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let canonical_uri = format!("/{}/{}", self.bucket, uri_encode(key));
+        let payload_hash = hex::encode(Sha256::digest(payload));
+
+        let canonical_headers = format!(
+            "host:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n",
+            self.host(),
+            payload_hash,
+            amz_date
+        );
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+        let canonical_request = format!(
+            "{}\n{}\n\n{}\n{}\n{}",
+            method, canonical_uri, canonical_headers, signed_headers, payload_hash
+        );
+
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = [self.region.as_str(), "s3", "aws4_request"].iter().fold(
+            hmac_sha256(
+                format!("AWS4{}", self.secret_key).as_bytes(),
+                date.as_bytes(),
+            ),
+            |key, part| hmac_sha256(&key, part.as_bytes()),
+        );
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, scope, signed_headers, signature
+        );
+
+        self.agent
+            .request(method, &format!("{}{}", self.endpoint, canonical_uri))
+            .set("x-amz-date", &amz_date)
+            .set("x-amz-content-sha256", &payload_hash)
+            .set("Authorization", &authorization)
+    }
+}
+
+impl BlobStorage for S3BlobStorage {
+    fn put(
+        &mut self,
+        key: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<String, BlobStorageError> {
+        if key.is_empty() {
+            return Err(BlobStorageError::InvalidKey);
+        }
+
+        self.request("PUT", key, &data)
+            .set("Content-Type", content_type)
+            .send_bytes(&data)
+            .map_err(|_| BlobStorageError::InternalError)?;
+        Ok(self.url(key))
+    }
+
+    fn get(&mut self, key: &str) -> Result<Vec<u8>, BlobStorageError> {
+        let mut data = Vec::new();
+        self.open(key)?
+            .read_to_end(&mut data)
+            .map_err(|_| BlobStorageError::InternalError)?;
+        Ok(data)
+    }
+
+    fn open(&mut self, key: &str) -> Result<Box<dyn Read + Send>, BlobStorageError> {
+        let response = self
+            .request("GET", key, &[])
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(404, _) => BlobStorageError::NotFound,
+                _ => BlobStorageError::InternalError,
+            })?;
+        Ok(response.into_reader())
+    }
+
+    fn delete(&mut self, key: &str) -> Result<(), BlobStorageError> {
+        match self.request("DELETE", key, &[]).call() {
+            Ok(_) | Err(ureq::Error::Status(404, _)) => Ok(()),
+            Err(_) => Err(BlobStorageError::InternalError),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!(
+            "{}/{}",
+            self.public_url.trim_end_matches('/'),
+            uri_encode(key)
+        )
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encodes an object key as SigV4 expects, keeping `/` separators.
+fn uri_encode(key: &str) -> String {
+    key.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage(endpoint: &str, public_url: Option<String>) -> S3BlobStorage {
+        S3BlobStorage::new(
+            endpoint.to_string(),
+            "ecommercers".to_string(),
+            "us-east-1".to_string(),
+            "admin".to_string(),
+            "adminadmin".to_string(),
+            public_url,
+        )
+    }
+
+    /// Storage against the S3-compatible service at `S3_TEST_ENDPOINT` (e.g. the
+    /// MinIO service of docker-compose.devel.yml, or `moto_server`), with its
+    /// bucket created. Tests needing one are skipped when the variable is unset.
+    fn live_storage() -> Option<S3BlobStorage> {
+        let Ok(endpoint) = std::env::var("S3_TEST_ENDPOINT") else {
+            eprintln!("S3_TEST_ENDPOINT is not set, skipping");
+            return None;
+        };
+        let storage = S3BlobStorage::new(
+            endpoint,
+            std::env::var("S3_TEST_BUCKET").unwrap_or_else(|_| "ecommercers-test".to_string()),
+            "us-east-1".to_string(),
+            std::env::var("S3_TEST_ACCESS_KEY").unwrap_or_else(|_| "admin".to_string()),
+            std::env::var("S3_TEST_SECRET_KEY").unwrap_or_else(|_| "adminadmin".to_string()),
+            None,
+        );
+        // Already existing is fine.
+        let _ = storage.request("PUT", "", &[]).call();
+        Some(storage)
+    }
+
+    #[test]
+    fn uri_encode_keeps_separators_and_unreserved_characters() {
+        assert_eq!(
+            uri_encode("products/1/a-b_c.d~e.png"),
+            "products/1/a-b_c.d~e.png"
+        );
+        assert_eq!(uri_encode("a b+c/é"), "a%20b%2Bc/%C3%A9");
+    }
+
+    #[test]
+    fn host_strips_scheme_and_path() {
+        assert_eq!(
+            storage("http://127.0.0.1:9000/", None).host(),
+            "127.0.0.1:9000"
+        );
+        assert_eq!(
+            storage("https://s3.example.com/base", None).host(),
+            "s3.example.com"
+        );
+    }
+
+    #[test]
+    fn url_defaults_to_path_style_bucket_url() {
+        assert_eq!(
+            storage("http://127.0.0.1:9000/", None).url("products/1/a b.png"),
+            "http://127.0.0.1:9000/ecommercers/products/1/a%20b.png"
+        );
+        assert_eq!(
+            storage(
+                "http://127.0.0.1:9000",
+                Some("https://cdn.example.com/".to_string())
+            )
+            .url("products/1.png"),
+            "https://cdn.example.com/products/1.png"
+        );
+    }
+
+    #[test]
+    fn put_rejects_empty_keys() {
+        let mut storage = storage("http://127.0.0.1:1", None);
+        assert!(matches!(
+            storage.put("", "image/png", vec![1]),
+            Err(BlobStorageError::InvalidKey)
+        ));
+    }
+
+    #[test]
+    fn put_get_and_delete_round_trip() {
+        let Some(mut storage) = live_storage() else {
+            return;
+        };
+        let key = format!(
+            "tests/{}/blob name.bin",
+            Utc::now().timestamp_nanos_opt().unwrap()
+        );
+
+        let url = storage
+            .put(&key, "application/octet-stream", vec![0, 1, 2, 255])
+            .unwrap();
+        assert_eq!(url, storage.url(&key));
+        assert_eq!(storage.get(&key).unwrap(), vec![0, 1, 2, 255]);
+
+        storage
+            .put(&key, "application/octet-stream", vec![42])
+            .unwrap();
+        assert_eq!(storage.get(&key).unwrap(), vec![42]);
+
+        storage.delete(&key).unwrap();
+        assert!(matches!(storage.get(&key), Err(BlobStorageError::NotFound)));
+        storage.delete(&key).unwrap();
+    }
+}
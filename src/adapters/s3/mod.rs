@@ -0,0 +1 @@
+pub mod blob_storage;
\ No newline at end of file
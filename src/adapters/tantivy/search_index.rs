@@ -0,0 +1,306 @@
+use std::fs;
+
+use tantivy::{
+    Index, IndexReader, IndexWriter, ReloadPolicy, Searcher, TantivyDocument, Term,
+    collector::TopDocs,
+    directory::MmapDirectory,
+    doc,
+    query::{BooleanQuery, FuzzyTermQuery, Occur, Query, QueryParser},
+    schema::{
+        FAST, Field, INDEXED, IndexRecordOption, STORED, Schema, TextFieldIndexing, TextOptions,
+        Value,
+    },
+    snippet::SnippetGenerator,
+    tokenizer::{
+        Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer, TextAnalyzer, TokenStream,
+    },
+};
+
+use crate::core::{
+    models::product::Product,
+    ports::search_index::{SearchHit, SearchIndex, SearchIndexError},
+};
+
+const TOKENIZER: &str = "catalog";
+
+/// Memory the writer buffers documents in before flushing them to disk.
+const WRITER_MEMORY: usize = 50_000_000;
+
+/// Weights of name and category matches relative to description matches.
+const NAME_BOOST: f32 = 3.0;
+const CATEGORIES_BOOST: f32 = 2.0;
+
+const SNIPPET_LENGTH: usize = 200;
+
+/// Index stored on local disk with tantivy.
+///
+/// A writer is only held while a change is applied, so that the rebuild
+/// command can write to the index of a running server. Changes made by other
+/// processes become searchable shortly after they are committed.
+pub struct TantivySearchIndex {
+    index: Index,
+    reader: IndexReader,
+    id: Field,
+    name: Field,
+    categories: Field,
+    description: Field,
+}
+
+impl TantivySearchIndex {
+    /// Opens the index stored in `path`, creating it on first use. Text is
+    /// stemmed for `search_language`, a Postgres text search configuration name;
+    /// languages tantivy has no stemmer for are indexed unstemmed.
+    pub fn open(path: &str, search_language: &str) -> Result<Self, SearchIndexError> {
+        Self::try_open(path, search_language).map_err(|_| SearchIndexError::InternalError)
+    }
+
+    fn try_open(path: &str, search_language: &str) -> tantivy::Result<Self> {
+        let text = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_tokenizer(TOKENIZER)
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        );
+        let mut schema = Schema::builder();
+        let id = schema.add_i64_field("id", INDEXED | STORED | FAST);
+        let name = schema.add_text_field("name", text.clone().set_stored());
+        let categories = schema.add_text_field("categories", text.clone());
+        let description = schema.add_text_field("description", text.set_stored());
+
+        fs::create_dir_all(path)?;
+        let index = Index::open_or_create(MmapDirectory::open(path)?, schema.build())?;
+        index
+            .tokenizers()
+            .register(TOKENIZER, Self::analyzer(search_language));
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
+            .try_into()?;
+
+        Ok(TantivySearchIndex {
+            index,
+            reader,
+            id,
+            name,
+            categories,
+            description,
+        })
+    }
+
+    fn analyzer(search_language: &str) -> TextAnalyzer {
+        let builder = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(RemoveLongFilter::limit(40))
+            .filter(LowerCaser);
+        match stemmer_language(search_language) {
+            Some(language) => builder.filter(Stemmer::new(language)).build(),
+            None => builder.build(),
+        }
+    }
+
+    /// Applies `update` with a writer of its own and makes it searchable.
+    fn write(
+        &self,
+        update: impl FnOnce(&IndexWriter) -> tantivy::Result<()>,
+    ) -> Result<(), SearchIndexError> {
+        let result = self
+            .index
+            .writer_with_num_threads(1, WRITER_MEMORY)
+            .and_then(|mut writer: IndexWriter| {
+                update(&writer)?;
+                writer.commit()?;
+                writer.wait_merging_threads()
+            })
+            .and_then(|_| self.reader.reload());
+        result.map_err(|_| SearchIndexError::InternalError)
+    }
+
+    fn add_products(&self, writer: &IndexWriter, products: &[Product]) -> tantivy::Result<()> {
+        for product in products {
+            let categories = product
+                .categories
+                .iter()
+                .map(|category| category.name.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            writer.add_document(doc!(
+                self.id => product.id,
+                self.name => product.name.as_str(),
+                self.categories => categories,
+                self.description => product.description.as_str(),
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Parses `query` the way Postgres' `websearch_to_tsquery` would: every
+    /// term is required unless `or` separates them. Syntax errors are ignored.
+    fn parse(&self, query: &str) -> Box<dyn Query> {
+        let mut parser = QueryParser::for_index(
+            &self.index,
+            vec![self.name, self.categories, self.description],
+        );
+        parser.set_conjunction_by_default();
+        parser.set_field_boost(self.name, NAME_BOOST);
+        parser.set_field_boost(self.categories, CATEGORIES_BOOST);
+
+        let query = query
+            .split_whitespace()
+            .map(|word| if word == "or" { "OR" } else { word })
+            .collect::<Vec<_>>()
+            .join(" ");
+        parser.parse_query_lenient(&query).0
+    }
+
+    /// Runs `query` and highlights the terms of `highlighted` in the snippets.
+    fn collect(
+        &self,
+        searcher: &Searcher,
+        query: &dyn Query,
+        highlighted: &dyn Query,
+        offset: usize,
+        limit: usize,
+    ) -> tantivy::Result<Vec<SearchHit>> {
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut snippets = SnippetGenerator::create(searcher, highlighted, self.description)?;
+        snippets.set_max_num_chars(SNIPPET_LENGTH);
+
+        searcher
+            .search(query, &TopDocs::with_limit(limit).and_offset(offset))?
+            .into_iter()
+            .map(|(score, address)| {
+                let document: TantivyDocument = searcher.doc(address)?;
+                let description = document
+                    .get_first(self.description)
+                    .and_then(|value| value.as_str())
+                    .unwrap_or_default();
+                let mut snippet = snippets.snippet(description);
+                snippet.set_snippet_prefix_postfix("<mark>", "</mark>");
+                let snippet = if snippet.is_empty() {
+                    leading_words(description)
+                } else {
+                    snippet.to_html()
+                };
+
+                Ok(SearchHit {
+                    product_id: document
+                        .get_first(self.id)
+                        .and_then(|value| value.as_i64())
+                        .unwrap_or_default(),
+                    rank: score,
+                    snippet,
+                })
+            })
+            .collect()
+    }
+}
+
+impl SearchIndex for TantivySearchIndex {
+    fn index_products(&mut self, products: &[Product]) -> Result<(), SearchIndexError> {
+        self.write(|writer| {
+            for product in products {
+                writer.delete_term(Term::from_field_i64(self.id, product.id));
+            }
+            self.add_products(writer, products)
+        })
+    }
+
+    fn remove_product(&mut self, product_id: i64) -> Result<(), SearchIndexError> {
+        self.write(|writer| {
+            writer.delete_term(Term::from_field_i64(self.id, product_id));
+            Ok(())
+        })
+    }
+
+    fn rebuild(&mut self, products: &[Product]) -> Result<(), SearchIndexError> {
+        self.write(|writer| {
+            writer.delete_all_documents()?;
+            self.add_products(writer, products)
+        })
+    }
+
+    fn search(
+        &mut self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, SearchIndexError> {
+        let query = self.parse(query);
+        self.collect(&self.reader.searcher(), &*query, &*query, offset, limit)
+            .map_err(|_| SearchIndexError::InternalError)
+    }
+
+    fn find_similar(
+        &mut self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, SearchIndexError> {
+        let mut analyzer = self
+            .index
+            .tokenizer_for_field(self.name)
+            .map_err(|_| SearchIndexError::InternalError)?;
+        let mut words: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        analyzer.token_stream(query).process(&mut |token| {
+            let distance = if token.text.chars().count() <= 4 {
+                1
+            } else {
+                2
+            };
+            words.push((
+                Occur::Should,
+                Box::new(FuzzyTermQuery::new(
+                    Term::from_field_text(self.name, &token.text),
+                    distance,
+                    true,
+                )),
+            ));
+        });
+
+        let similar = BooleanQuery::new(words);
+        let highlighted = self.parse(query);
+        self.collect(&self.reader.searcher(), &similar, &*highlighted, 0, limit)
+            .map_err(|_| SearchIndexError::InternalError)
+    }
+}
+
+/// Beginning of `text`, cut between words, for descriptions without any
+/// matched term to highlight.
+fn leading_words(text: &str) -> String {
+    let mut excerpt = String::new();
+    for word in text.split_whitespace() {
+        if !excerpt.is_empty() {
+            if excerpt.len() + word.len() >= SNIPPET_LENGTH {
+                break;
+            }
+            excerpt.push(' ');
+        }
+        excerpt.push_str(word);
+    }
+    excerpt
+}
+
+/// Tantivy stemmer matching a Postgres text search configuration name.
+fn stemmer_language(search_language: &str) -> Option<Language> {
+    match search_language {
+        "arabic" => Some(Language::Arabic),
+        "danish" => Some(Language::Danish),
+        "dutch" => Some(Language::Dutch),
+        "english" => Some(Language::English),
+        "finnish" => Some(Language::Finnish),
+        "french" => Some(Language::French),
+        "german" => Some(Language::German),
+        "greek" => Some(Language::Greek),
+        "hungarian" => Some(Language::Hungarian),
+        "italian" => Some(Language::Italian),
+        "norwegian" => Some(Language::Norwegian),
+        "portuguese" => Some(Language::Portuguese),
+        "romanian" => Some(Language::Romanian),
+        "russian" => Some(Language::Russian),
+        "spanish" => Some(Language::Spanish),
+        "swedish" => Some(Language::Swedish),
+        "tamil" => Some(Language::Tamil),
+        "turkish" => Some(Language::Turkish),
+        _ => None,
+    }
+}
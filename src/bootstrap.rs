@@ -1,19 +1,49 @@
 use crate::adapters;
-use crate::config::{self, Config};
+use crate::config::{self, Config, SearchBackend, Storage};
+use crate::core::models::download::{DownloadSettings, MIN_SECRET_LENGTH};
+use crate::core::models::export::ExportSettings;
+use crate::core::models::view::ViewSettings;
+use crate::core::ports::blob_storage::BlobStorage;
+use crate::core::ports::search_index::SearchIndex;
+use crate::core::services::cart_service::{CartService, new_cart_service};
 use crate::core::services::category_service::{CategoryService, new_category_service};
+use crate::core::services::download_service::{DownloadService, new_download_service};
 use crate::core::services::email_service::new_email_service_devel;
+use crate::core::services::export_service::{ExportService, new_export_service};
+use crate::core::services::import_service::{ImportService, new_import_service};
+use crate::core::services::order_service::{OrderService, new_order_service};
 use crate::core::services::product_service::{ProductService, new_product_service};
+use crate::core::services::question_service::{QuestionService, new_question_service};
+use crate::core::services::recommendation_service::{
+    RecommendationService, new_recommendation_service,
+};
+use crate::core::services::review_service::{ReviewService, new_review_service};
+use crate::core::services::subscription_service::{
+    SubscriptionService, new_subscription_service,
+};
 use crate::core::services::user_service::{UserService, new_user_service};
+use crate::core::services::view_service::{ViewService, new_view_service};
 use diesel::PgConnection;
 use diesel::r2d2::{ConnectionManager, Pool};
 use r2d2_redis::RedisConnectionManager;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub struct Services {
     pub cfg: Arc<Mutex<Config>>,
     pub user_service: Arc<Mutex<UserService>>,
     pub product_service: Arc<Mutex<ProductService>>,
     pub category_service: Arc<Mutex<CategoryService>>,
+    pub cart_service: Arc<Mutex<CartService>>,
+    pub order_service: Arc<Mutex<OrderService>>,
+    pub import_service: Arc<Mutex<ImportService>>,
+    pub export_service: Arc<Mutex<ExportService>>,
+    pub review_service: Arc<Mutex<ReviewService>>,
+    pub question_service: Arc<Mutex<QuestionService>>,
+    pub recommendation_service: Arc<Mutex<RecommendationService>>,
+    pub view_service: Arc<Mutex<ViewService>>,
+    pub download_service: Arc<Mutex<DownloadService>>,
+    pub subscription_service: Arc<Mutex<SubscriptionService>>,
 }
 
 pub fn bootstrap_services() -> Services {
@@ -36,6 +66,12 @@ pub fn bootstrap_services() -> Services {
         adapters::redis::repos::auth_repository::AuthRepositoryImpl::new(redis_pool.clone()),
     ));
 
+    let product_view_repository = Arc::new(Mutex::new(
+        adapters::redis::repos::product_view_repository::ProductViewRepositoryImpl::new(
+            redis_pool.clone(),
+        ),
+    ));
+
     let user_repository = Arc::new(Mutex::new(
         adapters::postgres::repos::user_repository::UserRepositoryImpl::new(pg_pool.clone()),
     ));
@@ -50,6 +86,52 @@ pub fn bootstrap_services() -> Services {
         adapters::postgres::repos::product_repository::ProductRepositoryImpl::new(
             pg_pool.clone(),
             category_repository.clone(),
+            cfg.search.language.clone(),
+        ),
+    ));
+    product_repository
+        .lock()
+        .unwrap()
+        .sync_search_language()
+        .expect("Failed to configure product search.");
+
+    let variation_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::variation_repository::VariationRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    let attribute_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::attribute_repository::AttributeRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    let bundle_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::bundle_repository::BundleRepositoryImpl::new(pg_pool.clone()),
+    ));
+
+    let product_variant_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::product_variant_repository::ProductVariantRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    let product_category_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::product_category_repository::ProductCategoryRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    let product_image_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::product_image_repository::ProductImageRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    let product_file_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::product_file_repository::ProductFileRepositoryImpl::new(
+            pg_pool.clone(),
         ),
     ));
 
@@ -61,16 +143,169 @@ pub fn bootstrap_services() -> Services {
         adapters::postgres::repos::order_repository::OrderRepositoryImpl::new(pg_pool.clone()),
     ));
 
+    let question_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::question_repository::QuestionRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    let recommendation_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::recommendation_repository::RecommendationRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    let review_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::review_repository::ReviewRepositoryImpl::new(pg_pool.clone()),
+    ));
+
+    let subscription_repository = Arc::new(Mutex::new(
+        adapters::postgres::repos::subscription_repository::SubscriptionRepositoryImpl::new(
+            pg_pool.clone(),
+        ),
+    ));
+
+    // Storage
+    let blob_storage: Arc<Mutex<dyn BlobStorage>> = match cfg.storage.clone() {
+        Storage::Local { root, public_url } => Arc::new(Mutex::new(
+            adapters::filesystem::blob_storage::LocalBlobStorage::new(root, public_url),
+        )),
+        Storage::S3 {
+            endpoint,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+            public_url,
+        } => Arc::new(Mutex::new(adapters::s3::blob_storage::S3BlobStorage::new(
+            endpoint, bucket, region, access_key, secret_key, public_url,
+        ))),
+    };
+    let image_processor = Arc::new(Mutex::new(
+        adapters::imaging::image_processor::ImageProcessorImpl::new(),
+    ));
+
+    // Search
+    let search_index: Arc<Mutex<dyn SearchIndex>> = match cfg.search.index.clone() {
+        SearchBackend::Postgres => Arc::new(Mutex::new(
+            adapters::postgres::search_index::PostgresSearchIndex::new(
+                pg_pool.clone(),
+                cfg.search.language.clone(),
+            ),
+        )),
+        SearchBackend::Tantivy { path } => Arc::new(Mutex::new(
+            adapters::tantivy::search_index::TantivySearchIndex::open(&path, &cfg.search.language)
+                .expect("Failed to open the search index."),
+        )),
+    };
+
     // Services
-    let email_service = new_email_service_devel();
+    let email_service = Arc::new(Mutex::new(new_email_service_devel()));
     let user_service = new_user_service(
         cfg.jwt.secret.clone(),
         auth_repository,
-        user_repository,
-        Arc::new(Mutex::new(email_service)),
+        user_repository.clone(),
+        email_service.clone(),
+    );
+    let product_service = new_product_service(
+        product_repository.clone(),
+        variation_repository,
+        product_variant_repository.clone(),
+        product_category_repository,
+        product_image_repository.clone(),
+        blob_storage.clone(),
+        image_processor,
+        search_index.clone(),
+        question_repository.clone(),
+        recommendation_repository.clone(),
+        attribute_repository,
+        bundle_repository.clone(),
+        product_file_repository.clone(),
+    );
+    let import_service = new_import_service(
+        product_repository.clone(),
+        category_repository.clone(),
+        search_index.clone(),
+    );
+    let export_service = new_export_service(
+        product_repository.clone(),
+        category_repository.clone(),
+        product_image_repository,
+        ExportSettings {
+            store_url: cfg.export.store_url.clone(),
+            title: cfg.export.title.clone(),
+            currency: cfg.export.currency.clone(),
+            cache_dir: cfg.export.cache_dir.clone(),
+            cache_lifetime: Duration::from_secs(cfg.export.cache_seconds),
+        },
     );
-    let product_service = new_product_service(product_repository);
     let category_service = new_category_service(category_repository);
+    let cart_service = new_cart_service(
+        cart_repository.clone(),
+        product_repository.clone(),
+        product_variant_repository.clone(),
+    );
+    let review_service = new_review_service(
+        review_repository,
+        order_repository.clone(),
+        product_repository.clone(),
+    );
+    let question_service = new_question_service(
+        question_repository,
+        product_repository.clone(),
+        order_repository.clone(),
+        user_repository.clone(),
+        email_service.clone(),
+    );
+    let recommendation_service = new_recommendation_service(
+        recommendation_repository,
+        product_repository.clone(),
+        cart_repository.clone(),
+        cfg.recommendations.limit,
+    );
+    let view_service = new_view_service(
+        product_view_repository,
+        product_repository.clone(),
+        ViewSettings {
+            recent_limit: cfg.views.recent_limit,
+            anonymous_ttl_seconds: cfg.views.anonymous_days * 24 * 60 * 60,
+            trending_days: cfg.views.trending_days,
+        },
+    );
+    if cfg.downloads.secret.len() < MIN_SECRET_LENGTH {
+        panic!(
+            "downloads.secret must be at least {} bytes long.",
+            MIN_SECRET_LENGTH
+        );
+    }
+    let download_settings = DownloadSettings {
+        secret: cfg.downloads.secret.clone(),
+        base_url: cfg.downloads.base_url.clone(),
+        max_downloads: cfg.downloads.max_downloads,
+        link_ttl_seconds: cfg.downloads.expiry_days * 24 * 60 * 60,
+    };
+    let download_service = new_download_service(
+        order_repository.clone(),
+        product_file_repository,
+        blob_storage,
+        download_settings.clone(),
+    );
+    let order_service = Arc::new(Mutex::new(new_order_service(
+        order_repository,
+        cart_repository,
+        product_repository.clone(),
+        product_variant_repository.clone(),
+        bundle_repository,
+        download_settings,
+    )));
+    let subscription_service = new_subscription_service(
+        subscription_repository,
+        product_repository,
+        product_variant_repository,
+        user_repository,
+        order_service.clone(),
+        email_service,
+    );
 
     println!("# EcommerceRS");
 
@@ -79,5 +314,15 @@ pub fn bootstrap_services() -> Services {
         user_service: Arc::new(Mutex::new(user_service)),
         product_service: Arc::new(Mutex::new(product_service)),
         category_service: Arc::new(Mutex::new(category_service)),
+        cart_service: Arc::new(Mutex::new(cart_service)),
+        order_service,
+        import_service: Arc::new(Mutex::new(import_service)),
+        export_service: Arc::new(Mutex::new(export_service)),
+        review_service: Arc::new(Mutex::new(review_service)),
+        question_service: Arc::new(Mutex::new(question_service)),
+        recommendation_service: Arc::new(Mutex::new(recommendation_service)),
+        view_service: Arc::new(Mutex::new(view_service)),
+        download_service: Arc::new(Mutex::new(download_service)),
+        subscription_service: Arc::new(Mutex::new(subscription_service)),
     }
 }
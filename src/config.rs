@@ -23,12 +23,214 @@ pub struct Jwt {
     pub secret: String,
 }
 
+/// Where uploaded files are stored, selected by the `backend` key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum Storage {
+    Local {
+        root: String,
+        public_url: String,
+    },
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+        public_url: Option<String>,
+    },
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Storage::Local {
+            root: "./uploads".to_string(),
+            public_url: "/media".to_string(),
+        }
+    }
+}
+
+/// Where products are indexed for search, selected by the `backend` key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum SearchBackend {
+    #[default]
+    Postgres,
+    Tantivy {
+        path: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Search {
+    /// Postgres text search configuration used for stemming, e.g. `english`.
+    pub language: String,
+    #[serde(default)]
+    pub index: SearchBackend,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Search {
+            language: "english".to_string(),
+            index: SearchBackend::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scheduler {
+    /// How often the publication schedules of products are applied, in seconds.
+    pub interval_seconds: u64,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Scheduler {
+            interval_seconds: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trash {
+    /// How long deleted products and categories are kept before being purged.
+    pub retention_days: i64,
+}
+
+impl Default for Trash {
+    fn default() -> Self {
+        Trash { retention_days: 30 }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Export {
+    /// Base URL of the storefront the product links of the feeds point to.
+    pub store_url: String,
+    pub title: String,
+    /// ISO 4217 code of the currency product prices are in.
+    pub currency: String,
+    /// Directory the feeds served over HTTP are cached in.
+    pub cache_dir: String,
+    /// How long a cached feed is served before being generated again.
+    pub cache_seconds: u64,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Export {
+            store_url: "http://127.0.0.1:8000".to_string(),
+            title: "EcommerceRS".to_string(),
+            currency: "USD".to_string(),
+            cache_dir: "./exports".to_string(),
+            cache_seconds: 3600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recommendations {
+    /// How often the related products are computed again from the orders, in seconds.
+    pub interval_seconds: u64,
+    /// Related products kept per product and suggested per cart.
+    pub limit: i64,
+}
+
+impl Default for Recommendations {
+    fn default() -> Self {
+        Recommendations {
+            interval_seconds: 3600,
+            limit: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Views {
+    /// Products kept in the recently viewed list of each user or visitor.
+    pub recent_limit: i64,
+    /// How long the list of an anonymous visitor is kept after their last view.
+    pub anonymous_days: u64,
+    /// How often the view counts are saved to the database, in seconds.
+    pub flush_interval_seconds: u64,
+    /// Days of views the trending sort counts.
+    pub trending_days: i64,
+}
+
+impl Default for Views {
+    fn default() -> Self {
+        Views {
+            recent_limit: 20,
+            anonymous_days: 30,
+            flush_interval_seconds: 300,
+            trending_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Downloads {
+    /// Key the download links of digital products are signed with.
+    pub secret: String,
+    /// Base URL of the API the download links point to.
+    pub base_url: String,
+    /// Downloads allowed per order item.
+    pub max_downloads: i32,
+    /// How long the files of a paid order can be downloaded, in days.
+    pub expiry_days: i64,
+}
+
+impl Default for Downloads {
+    /// Without a secret, which has to be configured.
+    fn default() -> Self {
+        Downloads {
+            secret: String::new(),
+            base_url: "http://127.0.0.1:8000".to_string(),
+            max_downloads: 5,
+            expiry_days: 7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscriptions {
+    /// How often the orders of the subscriptions due are placed, in seconds.
+    pub interval_seconds: u64,
+}
+
+impl Default for Subscriptions {
+    fn default() -> Self {
+        Subscriptions {
+            interval_seconds: 3600,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub server: Server,
     pub postgres: Postgres,
     pub redis: Redis,
     pub jwt: Jwt,
+    #[serde(default)]
+    pub storage: Storage,
+    #[serde(default)]
+    pub search: Search,
+    #[serde(default)]
+    pub scheduler: Scheduler,
+    #[serde(default)]
+    pub trash: Trash,
+    #[serde(default)]
+    pub export: Export,
+    #[serde(default)]
+    pub recommendations: Recommendations,
+    #[serde(default)]
+    pub views: Views,
+    #[serde(default)]
+    pub downloads: Downloads,
+    #[serde(default)]
+    pub subscriptions: Subscriptions,
     pub version: String,
 }
 
@@ -46,7 +248,7 @@ static INSTANCE: LazyLock<Config> = LazyLock::new(|| {
     let data = fs::read_to_string(file_path).unwrap();
     let config: Config = toml::from_str(data.as_str()).unwrap();
     set_database_url(config.postgres.url.clone());
-    return config;
+    config
 });
 
 pub fn read() -> Config {
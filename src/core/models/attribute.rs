@@ -0,0 +1,115 @@
+use std::{fmt, str::FromStr};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeType {
+    #[default]
+    Text,
+    Number,
+    Bool,
+    /// Text restricted to the options of the attribute.
+    Enum,
+}
+
+impl FromStr for AttributeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(AttributeType::Text),
+            "number" => Ok(AttributeType::Number),
+            "bool" => Ok(AttributeType::Bool),
+            "enum" => Ok(AttributeType::Enum),
+            _ => Err(format!("'{}' is not a valid AttributeType", s)),
+        }
+    }
+}
+
+impl fmt::Display for AttributeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeType::Text => write!(f, "text"),
+            AttributeType::Number => write!(f, "number"),
+            AttributeType::Bool => write!(f, "bool"),
+            AttributeType::Enum => write!(f, "enum"),
+        }
+    }
+}
+
+/// A specification products of a category carry, such as a screen size or a
+/// material. Products of descendant categories inherit it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Attribute {
+    pub id: i64,
+    pub category_id: i64,
+    pub name: String,
+    pub data_type: AttributeType,
+    /// Unit numbers are expressed in, such as `kg` or `in`.
+    pub unit: Option<String>,
+    /// Whether product listings can be filtered by the attribute.
+    pub is_filterable: bool,
+    /// Values allowed for an enum attribute; empty for other types.
+    pub options: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Attribute {
+    /// Checks that `value` suits the data type of the attribute, and returns it
+    /// trimmed. Enum values must be one of the options.
+    pub fn validate_value(&self, value: AttributeValue) -> Option<AttributeValue> {
+        match (self.data_type, value) {
+            (AttributeType::Text, AttributeValue::Text(text)) => {
+                let text = text.trim();
+                (!text.is_empty()).then(|| AttributeValue::Text(text.to_string()))
+            }
+            (AttributeType::Enum, AttributeValue::Text(text)) => self
+                .options
+                .iter()
+                .any(|option| option == text.trim())
+                .then(|| AttributeValue::Text(text.trim().to_string())),
+            (AttributeType::Number, AttributeValue::Number(number)) => {
+                number.is_finite().then_some(AttributeValue::Number(number))
+            }
+            (AttributeType::Bool, AttributeValue::Bool(flag)) => Some(AttributeValue::Bool(flag)),
+            _ => None,
+        }
+    }
+}
+
+/// Value of an attribute on a product, given in JSON as a plain string, number
+/// or boolean.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+/// An attribute set on a product, along with its value.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductAttribute {
+    pub attribute_id: i64,
+    pub name: String,
+    pub data_type: AttributeType,
+    pub unit: Option<String>,
+    pub value: AttributeValue,
+}
+
+/// Narrows a product listing down by the value of a filterable attribute.
+/// Without any criteria, it matches the products the attribute is set on.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttributeFilter {
+    pub attribute_id: i64,
+    /// Matches products with any of these values.
+    #[serde(default)]
+    pub values: Vec<AttributeValue>,
+    /// Lowest value matched, for number attributes.
+    pub min: Option<f64>,
+    /// Highest value matched, for number attributes.
+    pub max: Option<f64>,
+}
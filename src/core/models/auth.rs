@@ -14,4 +14,5 @@ pub enum AuthError {
     TokenExpired,
     EmailAlreadyExists,
     EmailNotVerified,
+    PermissionDenied,
 }
\ No newline at end of file
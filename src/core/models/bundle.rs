@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// A product sold as a kit of other products. The stock of the bundle product
+/// is the number of complete kits its components make up, and ordering the
+/// bundle takes stock from the components.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Bundle {
+    pub product_id: i64,
+    /// Percentage taken off the total price of the components, which then sets
+    /// the price of the bundle. Without it, the bundle keeps a fixed price.
+    pub discount_percent: Option<f64>,
+    pub items: Vec<BundleItem>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BundleItem {
+    pub component_id: i64,
+    /// Units of the component in one bundle.
+    pub quantity: i32,
+}
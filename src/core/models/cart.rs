@@ -1,10 +1,13 @@
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Cart {
     pub id: i64,
     pub user_id: i64,
+    pub name: String,
+    pub is_active: bool,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -14,7 +17,10 @@ pub struct CartItem {
     pub id: i64,
     pub cart_id: i64,
     pub product_id: i64,
+    pub variant_id: Option<i64>,
     pub quantity: i32,
+    pub customizations: Value,
+    pub customization_price: f64,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
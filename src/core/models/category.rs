@@ -1,14 +1,21 @@
+use super::seo::SeoMetadata;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Category {
     pub id: i64,
     pub name: String,
+    /// Unique URL-friendly name; replaced slugs keep redirecting to the category.
+    pub slug: String,
     pub description: String,
     pub parent: Option<Box<Category>>,
+    pub seo: SeoMetadata,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// When the category was moved to the trash.
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug)]
@@ -17,4 +24,35 @@ pub enum CategoryError {
     NotFound,
     InvalidData,
     CategoryAlreadyExist,
-}
\ No newline at end of file
+    SlugTaken,
+}
+
+/// Names of every category and of its ancestors from the root, by category id.
+/// Categories with an ancestor missing from `categories` are left out.
+pub fn category_paths(categories: &[Category]) -> HashMap<i64, Vec<String>> {
+    let parents: HashMap<i64, (&str, Option<i64>)> = categories
+        .iter()
+        .map(|category| {
+            let parent_id = category.parent.as_ref().map(|parent| parent.id);
+            (category.id, (category.name.as_str(), parent_id))
+        })
+        .collect();
+
+    let mut paths = HashMap::new();
+    'categories: for category in categories {
+        let mut path = vec![category.name.clone()];
+        let mut parent_id = category.parent.as_ref().map(|parent| parent.id);
+        while let Some(id) = parent_id {
+            match parents.get(&id) {
+                // A longer path than there are categories has a cycle.
+                Some((name, grandparent_id)) if path.len() <= parents.len() => {
+                    path.insert(0, name.to_string());
+                    parent_id = *grandparent_id;
+                }
+                _ => continue 'categories,
+            }
+        }
+        paths.insert(category.id, path);
+    }
+    paths
+}
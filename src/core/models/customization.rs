@@ -0,0 +1,109 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum CustomizationFieldType {
+    Text,
+    Boolean,
+}
+
+impl FromStr for CustomizationFieldType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Text" => Ok(CustomizationFieldType::Text),
+            "Boolean" => Ok(CustomizationFieldType::Boolean),
+            _ => Err(format!("'{}' is not a valid CustomizationFieldType", s)),
+        }
+    }
+}
+
+impl fmt::Display for CustomizationFieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomizationFieldType::Text => write!(f, "Text"),
+            CustomizationFieldType::Boolean => write!(f, "Boolean"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CustomizationField {
+    pub id: i64,
+    pub product_id: i64,
+    pub name: String,
+    pub field_type: CustomizationFieldType,
+    pub required: bool,
+    pub max_length: Option<i32>,
+    pub extra_price: f64,
+}
+
+#[derive(Debug)]
+pub enum CustomizationError {
+    UnknownField(String),
+    MissingField(String),
+    InvalidValue(String),
+    TooLong(String),
+}
+
+/// Checks `values` against the fields a product declares.
+///
+/// Returns the normalized values (unset fields, empty text and `false` flags are
+/// dropped so equal customizations compare equal) and the extra price they add.
+pub fn validate_customizations(
+    fields: &[CustomizationField],
+    values: &Value,
+) -> Result<(Value, f64), CustomizationError> {
+    let empty = Map::new();
+    let values = match values {
+        Value::Null => &empty,
+        Value::Object(map) => map,
+        _ => return Err(CustomizationError::InvalidValue(String::new())),
+    };
+
+    if let Some(unknown) = values
+        .keys()
+        .find(|key| !fields.iter().any(|field| &field.name == *key))
+    {
+        return Err(CustomizationError::UnknownField(unknown.clone()));
+    }
+
+    let mut normalized = Map::new();
+    let mut extra_price = 0.0;
+
+    for field in fields {
+        let value = match (&field.field_type, values.get(&field.name)) {
+            (_, None) | (_, Some(Value::Null)) => None,
+            (CustomizationFieldType::Text, Some(Value::String(text))) => {
+                let text = text.trim();
+                if field
+                    .max_length
+                    .is_some_and(|max_length| text.chars().count() > max_length as usize)
+                {
+                    return Err(CustomizationError::TooLong(field.name.clone()));
+                }
+                (!text.is_empty()).then(|| Value::String(text.to_string()))
+            }
+            (CustomizationFieldType::Boolean, Some(Value::Bool(flag))) => {
+                flag.then_some(Value::Bool(true))
+            }
+            _ => return Err(CustomizationError::InvalidValue(field.name.clone())),
+        };
+
+        match value {
+            Some(value) => {
+                extra_price += field.extra_price;
+                normalized.insert(field.name.clone(), value);
+            }
+            None if field.required => {
+                return Err(CustomizationError::MissingField(field.name.clone()));
+            }
+            None => {}
+        }
+    }
+
+    Ok((Value::Object(normalized), extra_price))
+}
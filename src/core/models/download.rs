@@ -0,0 +1,212 @@
+use chrono::{DateTime, NaiveDateTime};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Largest file accepted for a digital product, in bytes.
+pub const MAX_FILE_SIZE: usize = 256 * 1024 * 1024;
+/// Prefix of the storage keys of digital product files, which are only served
+/// through signed download links.
+pub const DIGITAL_FILES_PREFIX: &str = "digital/";
+/// Size of the pieces files are streamed to HTTP clients in.
+pub const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+/// Shortest key accepted to sign download links, in bytes.
+pub const MIN_SECRET_LENGTH: usize = 32;
+const MAX_FILE_NAME_LENGTH: usize = 255;
+
+/// A file customers get access to once they have paid for a digital product.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductFile {
+    pub id: i64,
+    pub product_id: i64,
+    /// Unguessable key of the file in the blob storage; never disclosed.
+    #[serde(skip)]
+    pub storage_key: String,
+    /// Name the file is downloaded as.
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Keeps the last path segment of an uploaded file name, without the characters
+/// that would break a `Content-Disposition` header.
+pub fn sanitize_file_name(file_name: &str) -> Option<String> {
+    let file_name: String = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .collect();
+    let file_name = file_name.trim();
+    (!file_name.is_empty() && file_name.len() <= MAX_FILE_NAME_LENGTH)
+        .then(|| file_name.to_string())
+}
+
+/// Downloads of the files of a digital product an order item gives access to.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadGrant {
+    pub order_item_id: i64,
+    /// Downloads allowed over every file of the product.
+    pub max_downloads: i32,
+    pub download_count: i32,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl DownloadGrant {
+    pub fn downloads_left(&self) -> i32 {
+        (self.max_downloads - self.download_count).max(0)
+    }
+}
+
+/// A signed link to a file of a paid digital product.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadLink {
+    pub order_item_id: i64,
+    pub product_id: i64,
+    pub file_id: i64,
+    pub file_name: String,
+    pub size_bytes: i64,
+    pub url: String,
+    pub expires_at: NaiveDateTime,
+    pub downloads_left: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadSettings {
+    /// Key the download links are signed with.
+    pub secret: String,
+    /// Base URL of the API; links point to `{base_url}/downloads/...`.
+    pub base_url: String,
+    /// Downloads allowed per order item.
+    pub max_downloads: i32,
+    /// How long the links of a paid order stay valid, in seconds.
+    pub link_ttl_seconds: i64,
+}
+
+impl DownloadSettings {
+    /// Returns the URL of the file for the order item, valid until `expires_at`.
+    pub fn sign_url(&self, order_item_id: i64, file_id: i64, expires_at: NaiveDateTime) -> String {
+        let expires = expires_at.and_utc().timestamp();
+        format!(
+            "{}/downloads/{}/{}?expires={}&signature={}",
+            self.base_url.trim_end_matches('/'),
+            order_item_id,
+            file_id,
+            expires,
+            hex::encode(
+                self.mac(order_item_id, file_id, expires)
+                    .finalize()
+                    .into_bytes()
+            )
+        )
+    }
+
+    /// Checks the signature of a download link and returns when it expires.
+    pub fn verify(
+        &self,
+        order_item_id: i64,
+        file_id: i64,
+        expires: i64,
+        signature: &str,
+    ) -> Option<NaiveDateTime> {
+        let signature = hex::decode(signature).ok()?;
+        self.mac(order_item_id, file_id, expires)
+            .verify_slice(&signature)
+            .ok()?;
+        DateTime::from_timestamp(expires, 0).map(|expires_at| expires_at.naive_utc())
+    }
+
+    fn mac(&self, order_item_id: i64, file_id: i64, expires: i64) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any size");
+        mac.update(format!("{}:{}:{}", order_item_id, file_id, expires).as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn settings(secret: &str) -> DownloadSettings {
+        DownloadSettings {
+            secret: secret.to_string(),
+            base_url: "https://api.example.com/".to_string(),
+            max_downloads: 5,
+            link_ttl_seconds: 3600,
+        }
+    }
+
+    fn expires_at() -> NaiveDateTime {
+        DateTime::from_timestamp(1_900_000_000, 0)
+            .unwrap()
+            .naive_utc()
+    }
+
+    /// Signature query parameter of a signed URL.
+    fn signature(url: &str) -> String {
+        url.rsplit_once("signature=").unwrap().1.to_string()
+    }
+
+    #[test]
+    fn sign_url_points_to_the_file_of_the_order_item() {
+        let url = settings("a-secret-that-is-long-enough-for-links").sign_url(7, 3, expires_at());
+        assert!(
+            url.starts_with("https://api.example.com/downloads/7/3?expires=1900000000&signature=")
+        );
+        assert_eq!(signature(&url).len(), 64);
+    }
+
+    #[test]
+    fn verify_accepts_signed_links() {
+        let settings = settings("a-secret-that-is-long-enough-for-links");
+        let url = settings.sign_url(7, 3, expires_at());
+        assert_eq!(
+            settings.verify(7, 3, 1_900_000_000, &signature(&url)),
+            Some(expires_at())
+        );
+    }
+
+    #[test]
+    fn verify_rejects_tampered_links() {
+        let settings = settings("a-secret-that-is-long-enough-for-links");
+        let signature = signature(&settings.sign_url(7, 3, expires_at()));
+        assert_eq!(settings.verify(8, 3, 1_900_000_000, &signature), None);
+        assert_eq!(settings.verify(7, 4, 1_900_000_000, &signature), None);
+        assert_eq!(settings.verify(7, 3, 1_900_000_001, &signature), None);
+        assert_eq!(settings.verify(7, 3, 1_900_000_000, "not hex"), None);
+        assert_eq!(settings.verify(7, 3, 1_900_000_000, &signature[2..]), None);
+    }
+
+    #[test]
+    fn verify_rejects_links_signed_with_another_secret() {
+        let url = settings("another-secret-that-is-long-enough").sign_url(7, 3, expires_at());
+        assert_eq!(
+            settings("a-secret-that-is-long-enough-for-links").verify(
+                7,
+                3,
+                1_900_000_000,
+                &signature(&url)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn sanitize_file_name_keeps_the_last_segment() {
+        assert_eq!(
+            sanitize_file_name("C:\\books\\guide \"v2\".pdf"),
+            Some("guide v2.pdf".to_string())
+        );
+        assert_eq!(sanitize_file_name("../"), None);
+    }
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::PathBuf, str::FromStr, time::Duration};
+
+/// Size of the pieces cached exports are streamed to HTTP clients in.
+pub const FEED_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    /// One JSON object per line.
+    Jsonl,
+    /// RSS 2.0 feed with the Google Merchant Center attributes.
+    Xml,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Xml => "xml",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Jsonl => "application/jsonl; charset=utf-8",
+            ExportFormat::Xml => "application/rss+xml; charset=utf-8",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "xml" => Ok(ExportFormat::Xml),
+            _ => Err(format!("'{}' is not a valid ExportFormat", s)),
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// How exported products are presented to sales channels.
+#[derive(Debug, Clone)]
+pub struct ExportSettings {
+    /// Base URL of the storefront; product pages are found under
+    /// `{store_url}/products/{slug}`.
+    pub store_url: String,
+    /// Title of the feed.
+    pub title: String,
+    /// ISO 4217 code of the currency prices are in.
+    pub currency: String,
+    /// Directory the exports served over HTTP are cached in.
+    pub cache_dir: String,
+    /// How long a cached export is served before being generated again.
+    pub cache_lifetime: Duration,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Availability {
+    InStock,
+    OutOfStock,
+}
+
+/// A published product as exported to sales channels.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductFeedItem {
+    pub id: i64,
+    pub sku: Option<String>,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub price: f64,
+    pub currency: String,
+    pub stock: i32,
+    pub availability: Availability,
+    /// Path of the primary category, e.g. `Clothing > Shirts`.
+    pub category: Option<String>,
+    /// Paths of every category of the product, primary first.
+    pub categories: Vec<String>,
+    /// URL of the product page.
+    pub link: String,
+    pub image_link: Option<String>,
+    /// The rest of the gallery, in display order.
+    pub additional_image_links: Vec<String>,
+}
+
+/// An export kept on disk for HTTP clients.
+#[derive(Debug, Clone)]
+pub struct CachedExport {
+    pub path: PathBuf,
+    /// How long the export stays fresh.
+    pub max_age: Duration,
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    InternalError,
+}
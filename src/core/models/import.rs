@@ -0,0 +1,121 @@
+use super::product::ProductStatus;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Rows written per transaction by an import.
+pub const IMPORT_BATCH_SIZE: usize = 100;
+
+/// Largest catalog file an import accepts.
+pub const MAX_IMPORT_SIZE: usize = 20 * 1024 * 1024;
+
+/// Separator between the category names of a category path.
+pub const CATEGORY_PATH_SEPARATOR: &str = ">";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    /// Comma separated values with a header row naming the columns.
+    Csv,
+    /// An array of objects.
+    Json,
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ImportFormat::Csv),
+            "json" => Ok(ImportFormat::Json),
+            _ => Err(format!("'{}' is not a valid ImportFormat", s)),
+        }
+    }
+}
+
+impl fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportFormat::Csv => write!(f, "csv"),
+            ImportFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct ImportOptions {
+    /// Validates the rows and reports what would change without saving anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Creates the categories rows refer to that do not exist yet, instead of
+    /// rejecting those rows.
+    #[serde(default)]
+    pub create_categories: bool,
+}
+
+/// A product as read from a catalog file.
+///
+/// Rows update the product with the same SKU, or else the one with the same
+/// slug, and create a product when there is none. Unset optional columns leave
+/// an existing product unchanged.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ImportRow {
+    pub sku: Option<String>,
+    /// Only used to find the product; slugs of existing products are changed
+    /// through their SEO metadata.
+    pub slug: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    pub stock: Option<i32>,
+    /// Names of the primary category and its ancestors from the root,
+    /// separated by `>`, e.g. `Clothing > Shirts`.
+    pub category: Option<String>,
+    pub status: Option<ProductStatus>,
+}
+
+/// A validated row, with its category resolved.
+#[derive(Debug, Clone)]
+pub struct ProductImport {
+    pub line: usize,
+    pub sku: Option<String>,
+    pub slug: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: f64,
+    pub stock: Option<i32>,
+    pub category_id: Option<i64>,
+    pub status: Option<ProductStatus>,
+}
+
+/// What importing a row did, or would do on a dry run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportAction {
+    Created(i64),
+    Updated(i64),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportRowError {
+    /// Line of the row in a CSV file, or its position in a JSON array,
+    /// counting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Outcome of an import. Nothing is saved unless every row is valid.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub updated: usize,
+    /// Categories created for the rows, or that would be on a dry run.
+    pub created_categories: usize,
+    pub errors: Vec<ImportRowError>,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    InternalError,
+    /// The file is not valid CSV or JSON, or is too large.
+    InvalidFile,
+}
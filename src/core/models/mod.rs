@@ -4,4 +4,18 @@ pub mod user;
 pub mod auth;
 pub mod product;
 pub mod category;
-pub mod product_category;
\ No newline at end of file
+pub mod product_category;
+pub mod customization;
+pub mod pagination;
+pub mod seo;
+pub mod import;
+pub mod export;
+pub mod review;
+pub mod moderation;
+pub mod question;
+pub mod recommendation;
+pub mod view;
+pub mod attribute;
+pub mod bundle;
+pub mod download;
+pub mod subscription;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Where content submitted by customers stands with the managers; only
+/// approved content is shown to other customers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationStatus {
+    /// Waiting for a manager.
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl FromStr for ModerationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ModerationStatus::Pending),
+            "approved" => Ok(ModerationStatus::Approved),
+            "rejected" => Ok(ModerationStatus::Rejected),
+            _ => Err(format!("'{}' is not a valid ModerationStatus", s)),
+        }
+    }
+}
+
+impl fmt::Display for ModerationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModerationStatus::Pending => write!(f, "pending"),
+            ModerationStatus::Approved => write!(f, "approved"),
+            ModerationStatus::Rejected => write!(f, "rejected"),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::{fmt, str::FromStr};
 
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum OrderStatus {
@@ -48,6 +49,10 @@ pub struct Order {
     pub user_id: i64,
     pub total_amount: f64,
     pub status: OrderStatus,
+    /// Whether the order has physical products to ship; orders of digital
+    /// products only are delivered as soon as they are paid.
+    pub requires_shipping: bool,
+    pub paid_at: Option<NaiveDateTime>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -57,16 +62,32 @@ pub struct OrderItem {
     pub id: i64,
     pub order_id: i64,
     pub product_id: i64,
+    pub variant_id: Option<i64>,
     pub quantity: i32,
     pub price_at_time_of_order: f64,
+    pub customizations: Value,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
 
+/// Units an order takes from the stock of a product or of a variant.
+#[derive(Debug, Clone, Copy)]
+pub enum StockReservation {
+    Product(i64, i32),
+    Variant(i64, i32),
+}
+
 #[derive(Debug)]
 pub enum OrderError {
     NotFound,
     InvalidData,
     DatabaseError,
     InvalidStatusTransition,
+    OutOfStock,
+    /// A product ordered is not published, or no longer.
+    ProductUnavailable,
+    /// The download link or the access it gives has expired.
+    DownloadExpired,
+    /// Every download allowed for the order item has been used.
+    DownloadLimitReached,
 }
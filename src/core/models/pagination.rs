@@ -0,0 +1,192 @@
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+/// Position of an item in a list ordered by `(created_at, id)`; a page starts
+/// right after it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    pub created_at: NaiveDateTime,
+    pub id: i64,
+    /// Value of the sort key of the item when the page was loaded, for lists
+    /// ordered by something else than `created_at`. Later changes to the item
+    /// then do not move the start of the next page.
+    pub key: Option<String>,
+}
+
+impl Cursor {
+    /// String form handed to clients as `next_cursor`.
+    pub fn encode(&self) -> String {
+        let position = format!(
+            "{}_{}",
+            self.created_at.and_utc().timestamp_micros(),
+            self.id
+        );
+        match &self.key {
+            Some(key) => format!("{}_{}", position, hex::encode(key)),
+            None => position,
+        }
+    }
+
+    pub fn decode(value: &str) -> Option<Cursor> {
+        let mut parts = value.splitn(3, '_');
+        let micros = parts.next()?;
+        let id = parts.next()?;
+        let key = match parts.next() {
+            Some(key) => Some(String::from_utf8(hex::decode(key).ok()?).ok()?),
+            None => None,
+        };
+        Some(Cursor {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?.naive_utc(),
+            id: id.parse().ok()?,
+            key,
+        })
+    }
+}
+
+/// Position in a list of search results. Results are ranked by relevance
+/// rather than ordered by a column, so the cursor counts the results before
+/// the next page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchCursor {
+    pub offset: usize,
+}
+
+impl SearchCursor {
+    /// String form handed to clients as `next_cursor`.
+    pub fn encode(&self) -> String {
+        self.offset.to_string()
+    }
+
+    pub fn decode(value: &str) -> Option<SearchCursor> {
+        Some(SearchCursor {
+            offset: value.parse().ok()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum PaginationError {
+    InvalidCursor,
+    InvalidLimit,
+}
+
+/// Which page of a list to load.
+#[derive(Debug, Clone)]
+pub struct Pagination {
+    /// Last item of the previous page; `None` for the first page.
+    pub after: Option<Cursor>,
+    pub limit: i64,
+    /// Whether to also count every item of the list.
+    pub with_total: bool,
+}
+
+impl Pagination {
+    /// Limits above [`MAX_PAGE_LIMIT`] are lowered to it.
+    pub fn new(
+        cursor: Option<&str>,
+        limit: Option<i64>,
+        with_total: bool,
+    ) -> Result<Self, PaginationError> {
+        let after = match cursor {
+            Some(cursor) => Some(Cursor::decode(cursor).ok_or(PaginationError::InvalidCursor)?),
+            None => None,
+        };
+
+        Ok(Pagination {
+            after,
+            limit: page_limit(limit)?,
+            with_total,
+        })
+    }
+
+    /// Number of items to load: one past the limit, which tells whether another
+    /// page follows.
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Pagination {
+            after: None,
+            limit: DEFAULT_PAGE_LIMIT,
+            with_total: false,
+        }
+    }
+}
+
+/// Which page of search results to load.
+#[derive(Debug, Clone)]
+pub struct SearchPagination {
+    /// End of the previous page; `None` for the first page.
+    pub after: Option<SearchCursor>,
+    pub limit: i64,
+}
+
+impl SearchPagination {
+    /// Limits above [`MAX_PAGE_LIMIT`] are lowered to it.
+    pub fn new(cursor: Option<&str>, limit: Option<i64>) -> Result<Self, PaginationError> {
+        let after = match cursor {
+            Some(cursor) => {
+                Some(SearchCursor::decode(cursor).ok_or(PaginationError::InvalidCursor)?)
+            }
+            None => None,
+        };
+
+        Ok(SearchPagination {
+            after,
+            limit: page_limit(limit)?,
+        })
+    }
+
+    /// Number of results to load: one past the limit, which tells whether
+    /// another page follows.
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+}
+
+fn page_limit(limit: Option<i64>) -> Result<i64, PaginationError> {
+    match limit {
+        Some(limit) if limit < 1 => Err(PaginationError::InvalidLimit),
+        Some(limit) => Ok(limit.min(MAX_PAGE_LIMIT)),
+        None => Ok(DEFAULT_PAGE_LIMIT),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor of the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+    /// Number of items in the whole list, when requested.
+    pub total: Option<i64>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page out of the items loaded for `pagination`, up to
+    /// [`Pagination::fetch_limit`] of them.
+    pub fn new(
+        mut items: Vec<T>,
+        pagination: &Pagination,
+        total: Option<i64>,
+        cursor: impl Fn(&T) -> Cursor,
+    ) -> Self {
+        let has_more = items.len() as i64 > pagination.limit;
+        items.truncate(pagination.limit as usize);
+        let next_cursor = items
+            .last()
+            .filter(|_| has_more)
+            .map(|item| cursor(item).encode());
+
+        Page {
+            items,
+            next_cursor,
+            total,
+        }
+    }
+}
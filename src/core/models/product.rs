@@ -1,18 +1,236 @@
-use super::category::Category;
+use super::{
+    attribute::{AttributeFilter, ProductAttribute},
+    bundle::Bundle,
+    category::Category,
+    customization::CustomizationField,
+    download::ProductFile,
+    pagination::Page,
+    question::ProductQuestion,
+    seo::SeoMetadata,
+};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Product {
     pub id: i64,
     pub name: String,
+    /// Unique URL-friendly name; replaced slugs keep redirecting to the product.
+    pub slug: String,
+    /// Stock keeping unit the product is identified by in catalog imports.
+    pub sku: Option<String>,
     pub description: String,
     pub price: f64,
     pub stock: i32,
     pub product_image: Option<String>,
+    /// Whether the product is delivered as downloadable files instead of being shipped.
+    pub is_digital: bool,
+    pub seo: SeoMetadata,
+    pub status: ProductStatus,
+    /// When a draft or archived product gets published.
+    pub publish_at: Option<NaiveDateTime>,
+    /// When a published product gets archived.
+    pub unpublish_at: Option<NaiveDateTime>,
+    /// Primary category of the product.
     pub category: Option<Category>,
+    /// Every category assigned to the product, primary first.
+    pub categories: Vec<Category>,
+    pub customization_fields: Vec<CustomizationField>,
+    /// Specifications of the product, by attribute; only populated on single product reads.
+    pub attributes: Vec<ProductAttribute>,
+    /// Components the product is sold as a kit of, when it is a bundle; only
+    /// populated on single product reads.
+    pub bundle: Option<Bundle>,
+    /// Variations the product is offered in; only populated on single product reads.
+    pub variations: Vec<Variation>,
+    /// Variation options offered by the product; only populated on single product reads.
+    pub variation_options: Vec<VariationOption>,
+    /// Sellable variants of the product; only populated on single product reads.
+    pub variants: Vec<ProductVariant>,
+    /// Image gallery of the product, in display order; only populated on single product reads.
+    pub images: Vec<ProductImage>,
+    /// Files a digital product gives access to once paid; only populated on
+    /// single product reads.
+    pub files: Vec<ProductFile>,
+    /// Average rating of the approved reviews; 0 without any.
+    pub rating_average: f64,
+    /// Number of approved reviews.
+    pub rating_count: i64,
+    /// Approved questions with the most answers; only populated on single product reads.
+    pub questions: Vec<ProductQuestion>,
+    /// Published products often bought along with or similar to this one, best
+    /// first; only populated on single product reads.
+    pub related_products: Vec<Product>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    /// When the product was moved to the trash.
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl Product {
+    /// Whether customers can see the product at `now`.
+    pub fn is_published(&self, now: NaiveDateTime) -> bool {
+        self.status == ProductStatus::Published && self.unpublish_at.is_none_or(|at| at > now)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProductStatus {
+    /// Being prepared; only managers see it.
+    #[default]
+    Draft,
+    Published,
+    /// Withdrawn from sale; kept for past orders and managers.
+    Archived,
+}
+
+impl FromStr for ProductStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "draft" => Ok(ProductStatus::Draft),
+            "published" => Ok(ProductStatus::Published),
+            "archived" => Ok(ProductStatus::Archived),
+            _ => Err(format!("'{}' is not a valid ProductStatus", s)),
+        }
+    }
+}
+
+impl fmt::Display for ProductStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProductStatus::Draft => write!(f, "draft"),
+            ProductStatus::Published => write!(f, "published"),
+            ProductStatus::Archived => write!(f, "archived"),
+        }
+    }
+}
+
+/// Which products a read returns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProductVisibility {
+    /// Only the products customers currently see.
+    Published,
+    /// Every product whatever its status, for managers.
+    All,
+}
+
+/// A product matched by a full-text search.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductSearchResult {
+    pub product: Product,
+    /// Relevance of the match; results come highest first.
+    pub rank: f32,
+    /// Excerpt of the description with the matched terms wrapped in `<mark>` tags.
+    pub snippet: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductSearch {
+    /// Full-text matches, followed by products with a similar name when there
+    /// were too few of them.
+    pub results: Vec<ProductSearchResult>,
+    /// Closest product or category name to the query, offered when it found
+    /// too few full-text matches.
+    pub did_you_mean: Option<String>,
+    /// Cursor of the next page of full-text matches, if any.
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionKind {
+    Product,
+    Category,
+}
+
+/// A product or category name completing what the customer is typing.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchSuggestion {
+    pub kind: SuggestionKind,
+    pub id: i64,
+    pub name: String,
+}
+
+/// Criteria a product listing is narrowed down by; unset criteria match everything.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProductFilter {
+    /// Matches products assigned to the category or to any of its descendants.
+    pub category_id: Option<i64>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    /// Matches products with stock left, on the product itself or on a variant.
+    #[serde(default)]
+    pub in_stock: bool,
+    /// Options of the same variation are alternatives; options of different
+    /// variations must all be offered.
+    #[serde(default)]
+    pub variation_option_ids: Vec<i64>,
+    /// Matches products whose average rating is at least this many stars.
+    pub min_rating: Option<f64>,
+    /// Every attribute filter must match.
+    #[serde(default)]
+    pub attributes: Vec<AttributeFilter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSort {
+    #[default]
+    Newest,
+    PriceAsc,
+    PriceDesc,
+    NameAsc,
+    NameDesc,
+    /// Most units ordered first.
+    Popularity,
+    /// Highest average rating first.
+    Rating,
+    /// Most viewed over the last days first.
+    Trending,
+}
+
+/// Lower bounds of the price facet buckets; the last bucket has no upper bound.
+pub const PRICE_BUCKETS: [f64; 6] = [0.0, 25.0, 50.0, 100.0, 250.0, 500.0];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryFacet {
+    pub category_id: i64,
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceFacet {
+    pub min_price: f64,
+    pub max_price: Option<f64>,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VariationOptionFacet {
+    pub variation_id: i64,
+    pub variation_name: String,
+    pub variation_option_id: i64,
+    pub value: String,
+    pub count: i64,
+}
+
+/// Number of matching products per category, price bucket and variation option.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProductFacets {
+    pub categories: Vec<CategoryFacet>,
+    pub prices: Vec<PriceFacet>,
+    pub variation_options: Vec<VariationOptionFacet>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductListing {
+    pub products: Page<Product>,
+    pub facets: Option<ProductFacets>,
 }
 
 #[derive(Debug)]
@@ -22,6 +240,20 @@ pub enum ProductError {
     InvalidData,
     InvalidCategory,
     PermissionDenied,
+    OutOfStock,
+    InvalidVariationOption,
+    /// Variants of the product still use the variation options involved.
+    VariationOptionInUse,
+    InvalidVariant,
+    /// The attribute does not apply to the product, cannot filter listings, or
+    /// does not accept the value.
+    InvalidAttribute,
+    /// Bundles cannot be nested, and neither bundles nor their components can
+    /// have variants.
+    InvalidBundle,
+    InvalidImage,
+    SlugTaken,
+    SkuTaken,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -37,3 +269,135 @@ pub struct VariationOption {
     pub variation_id: i64,
     pub value: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductVariant {
+    pub id: i64,
+    pub product_id: i64,
+    pub sku: String,
+    /// Overrides the product price when set.
+    pub price: Option<f64>,
+    pub stock: i32,
+    pub image: Option<String>,
+    pub options: Vec<VariationOption>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProductVariant {
+    pub fn effective_price(&self, product: &Product) -> f64 {
+        self.price.unwrap_or(product.price)
+    }
+}
+
+/// Largest image accepted for upload, in bytes.
+pub const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Largest width or height accepted for upload, in pixels.
+pub const MAX_IMAGE_DIMENSION: u32 = 8000;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductImage {
+    pub id: i64,
+    pub product_id: i64,
+    pub storage_key: String,
+    pub url: String,
+    pub content_type: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt_text: Option<String>,
+    pub position: i32,
+    /// The primary image is mirrored in `Product::product_image`.
+    pub is_primary: bool,
+    /// Resized copies of the image, one per [`ImageSize`], each also encoded as WebP.
+    pub renditions: Vec<ImageRendition>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProductImage {
+    /// Every blob stored for the image: the original and all its renditions.
+    pub fn storage_keys(&self) -> Vec<String> {
+        let mut keys = vec![self.storage_key.clone()];
+        for rendition in &self.renditions {
+            keys.push(rendition.storage_key.clone());
+            keys.push(rendition.webp_storage_key.clone());
+        }
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageSize {
+    /// Full size; only its WebP version differs from the uploaded file.
+    Original,
+    Thumbnail,
+    Medium,
+    Large,
+}
+
+impl ImageSize {
+    pub const ALL: [ImageSize; 4] = [
+        ImageSize::Original,
+        ImageSize::Thumbnail,
+        ImageSize::Medium,
+        ImageSize::Large,
+    ];
+
+    /// Bounding box the rendition is scaled down to fit in; `None` keeps the
+    /// original dimensions. Images are never scaled up.
+    pub fn max_dimension(&self) -> Option<u32> {
+        match self {
+            ImageSize::Original => None,
+            ImageSize::Thumbnail => Some(150),
+            ImageSize::Medium => Some(600),
+            ImageSize::Large => Some(1200),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImageSize::Original => "original",
+            ImageSize::Thumbnail => "thumbnail",
+            ImageSize::Medium => "medium",
+            ImageSize::Large => "large",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageRendition {
+    pub size: ImageSize,
+    pub width: u32,
+    pub height: u32,
+    pub storage_key: String,
+    pub url: String,
+    pub webp_storage_key: String,
+    pub webp_url: String,
+}
+
+/// Returns the file extension used to store an image of `content_type`, or
+/// `None` when the type is not accepted.
+pub fn image_extension(content_type: &str) -> Option<&'static str> {
+    match content_type {
+        "image/jpeg" => Some("jpg"),
+        "image/png" => Some("png"),
+        "image/webp" => Some("webp"),
+        "image/gif" => Some("gif"),
+        _ => None,
+    }
+}
+
+/// Inverse of [`image_extension`].
+pub fn image_content_type(extension: &str) -> Option<&'static str> {
+    match extension {
+        "jpg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "webp" => Some("image/webp"),
+        "gif" => Some("image/gif"),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
+use super::product::ProductError;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
@@ -6,6 +7,8 @@ pub struct ProductCategory {
     pub id: i64,
     pub product_id: i64,
     pub category_id: i64,
+    /// The primary category is mirrored in `Product::category`.
+    pub is_primary: bool,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -18,4 +21,19 @@ pub enum ProductCategoryError {
     Conflict,
     ProductNotFound,
     CategoryNotFound,
-}
\ No newline at end of file
+}
+
+impl From<ProductCategoryError> for ProductError {
+    fn from(value: ProductCategoryError) -> Self {
+        match value {
+            ProductCategoryError::NotFound | ProductCategoryError::ProductNotFound => {
+                ProductError::NotFound
+            }
+            ProductCategoryError::CategoryNotFound => ProductError::InvalidCategory,
+            ProductCategoryError::InvalidData | ProductCategoryError::Conflict => {
+                ProductError::InvalidData
+            }
+            ProductCategoryError::DatabaseError => ProductError::InternalError,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use super::moderation::ModerationStatus;
+
+/// Longest question or answer accepted, in characters.
+pub const MAX_QUESTION_LENGTH: usize = 2000;
+
+/// Answered questions shown along with a single product.
+pub const TOP_QUESTIONS_LIMIT: i64 = 5;
+
+/// A question asked about a product before buying it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductQuestion {
+    pub id: i64,
+    pub product_id: i64,
+    pub user_id: i64,
+    pub body: String,
+    /// Only approved questions are shown and can be answered.
+    pub status: ModerationStatus,
+    /// Number of approved answers.
+    pub answer_count: i64,
+    /// Approved answers, oldest first; only populated on customer reads.
+    pub answers: Vec<ProductAnswer>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// An answer to a product question by the staff or by a customer who received
+/// the product.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductAnswer {
+    pub id: i64,
+    pub question_id: i64,
+    pub user_id: i64,
+    pub body: String,
+    /// Answers of the staff are approved right away.
+    pub status: ModerationStatus,
+    pub is_staff: bool,
+    pub is_verified_buyer: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum QuestionError {
+    NotFound,
+    InvalidData,
+    DatabaseError,
+    /// Only the staff and customers who received the product answer questions.
+    PermissionDenied,
+}
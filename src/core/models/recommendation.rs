@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Score added for each order two products were bought together in.
+pub const CO_PURCHASE_WEIGHT: f64 = 1.0;
+
+/// Score added for each category two products share, so that products rarely
+/// ordered still get related products.
+pub const SAME_CATEGORY_WEIGHT: f64 = 0.1;
+
+/// Best selling products of a category that the other products of the
+/// category are paired with, so that large categories do not score every pair
+/// of their products.
+pub const MAX_CATEGORY_CANDIDATES: i64 = 50;
+
+/// A product recommended along with another one.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductRecommendation {
+    pub product_id: i64,
+    pub related_product_id: i64,
+    pub score: f64,
+    /// Rank among the products related to `product_id`, starting at 1.
+    pub position: i32,
+}
+
+#[derive(Debug)]
+pub enum RecommendationError {
+    DatabaseError,
+}
@@ -0,0 +1,42 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use super::moderation::ModerationStatus;
+
+pub const MIN_RATING: i32 = 1;
+pub const MAX_RATING: i32 = 5;
+
+/// Longest review text accepted, in characters.
+pub const MAX_REVIEW_LENGTH: usize = 5000;
+
+/// A customer's rating and opinion of a product they received. Each customer
+/// reviews a product at most once.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Review {
+    pub id: i64,
+    pub product_id: i64,
+    pub user_id: i64,
+    /// From [`MIN_RATING`] to [`MAX_RATING`] stars.
+    pub rating: i32,
+    pub body: String,
+    /// Only approved reviews are shown and count towards the product rating.
+    pub status: ModerationStatus,
+    /// Public answer of the store to the review.
+    pub response: Option<String>,
+    pub responded_at: Option<NaiveDateTime>,
+    /// Number of customers who found the review helpful.
+    pub helpful_count: i64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum ReviewError {
+    NotFound,
+    InvalidData,
+    DatabaseError,
+    /// The customer has no delivered order containing the product.
+    NotPurchased,
+    AlreadyReviewed,
+    AlreadyVoted,
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_SLUG_LENGTH: usize = 100;
+
+/// Search engine metadata of a product or category page. Pages without a meta
+/// title or description fall back to their name and description.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SeoMetadata {
+    pub meta_title: Option<String>,
+    pub meta_description: Option<String>,
+    /// Preferred URL of the page, when its content is reachable under several URLs.
+    pub canonical_url: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum SeoError {
+    InvalidSlug,
+    InvalidCanonicalUrl,
+}
+
+impl SeoMetadata {
+    /// Trims every field and unsets the empty ones. The canonical URL must be an
+    /// absolute `http` or `https` URL.
+    pub fn normalize(self) -> Result<SeoMetadata, SeoError> {
+        let normalize = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let canonical_url = normalize(self.canonical_url);
+        if canonical_url.as_ref().is_some_and(|url| {
+            !(url.starts_with("https://") || url.starts_with("http://")) || url.contains(' ')
+        }) {
+            return Err(SeoError::InvalidCanonicalUrl);
+        }
+
+        Ok(SeoMetadata {
+            meta_title: normalize(self.meta_title),
+            meta_description: normalize(self.meta_description),
+            canonical_url,
+        })
+    }
+}
+
+/// Outcome of looking a page up by slug.
+#[derive(Debug, Clone)]
+pub enum SlugLookup<T> {
+    Found(T),
+    /// The slug was replaced; the page now lives under this one.
+    Moved(String),
+}
+
+/// Turns a name into a slug: lowercase ASCII letters and digits, with every
+/// other run of characters replaced by a single dash. The result is empty when
+/// the name has no ASCII letter or digit.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LENGTH);
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Normalizes a slug chosen by hand the way [`slugify`] does names.
+pub fn parse_slug(slug: &str) -> Result<String, SeoError> {
+    let slug = slugify(slug);
+    if slug.is_empty() {
+        return Err(SeoError::InvalidSlug);
+    }
+    Ok(slug)
+}
+
+/// Returns `slug`, or the first of `slug-2`, `slug-3`, ... that is not taken.
+/// An empty `slug` is replaced by `fallback`.
+pub fn unique_slug<E>(
+    slug: String,
+    fallback: &str,
+    mut is_taken: impl FnMut(&str) -> Result<bool, E>,
+) -> Result<String, E> {
+    let slug = if slug.is_empty() {
+        fallback.to_string()
+    } else {
+        slug
+    };
+    if !is_taken(&slug)? {
+        return Ok(slug);
+    }
+
+    let mut counter = 2;
+    loop {
+        let suffix = format!("-{}", counter);
+        let mut candidate = slug.clone();
+        candidate.truncate(MAX_SLUG_LENGTH - suffix.len());
+        let candidate = format!("{}{}", candidate.trim_end_matches('-'), suffix);
+        if !is_taken(&candidate)? {
+            return Ok(candidate);
+        }
+        counter += 1;
+    }
+}
@@ -0,0 +1,115 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{Duration, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Longest interval a plan can have between two orders, in days.
+pub const MAX_INTERVAL_DAYS: i32 = 365;
+/// Most units a subscription can order per run.
+pub const MAX_SUBSCRIPTION_QUANTITY: i32 = 100;
+
+/// A schedule a product can be subscribed to.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubscriptionPlan {
+    pub id: i64,
+    pub product_id: i64,
+    /// Days between two orders.
+    pub interval_days: i32,
+    /// Taken off the price of the product on every order.
+    pub discount_percent: f64,
+    /// Inactive plans cannot be subscribed to; existing subscriptions go on.
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl SubscriptionPlan {
+    /// Price of a unit ordered through the plan, rounded to the cent.
+    pub fn discounted_price(&self, unit_price: f64) -> f64 {
+        (unit_price * (100.0 - self.discount_percent)).round() / 100.0
+    }
+
+    /// First run after `run_at` that is later than `now`, so that runs missed
+    /// while the scheduler was down are not ordered all at once.
+    pub fn next_run_after(&self, run_at: NaiveDateTime, now: NaiveDateTime) -> NaiveDateTime {
+        let interval = Duration::days(self.interval_days as i64);
+        let mut next_run_at = run_at + interval;
+        if next_run_at <= now {
+            let missed = (now - next_run_at).num_seconds() / interval.num_seconds() + 1;
+            next_run_at += interval * missed as i32;
+        }
+        next_run_at
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionStatus {
+    /// Ordered on every run.
+    #[default]
+    Active,
+    /// Kept without being ordered until resumed.
+    Paused,
+    Cancelled,
+}
+
+impl FromStr for SubscriptionStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(SubscriptionStatus::Active),
+            "paused" => Ok(SubscriptionStatus::Paused),
+            "cancelled" => Ok(SubscriptionStatus::Cancelled),
+            _ => Err(format!("'{}' is not a valid SubscriptionStatus", s)),
+        }
+    }
+}
+
+impl fmt::Display for SubscriptionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubscriptionStatus::Active => write!(f, "active"),
+            SubscriptionStatus::Paused => write!(f, "paused"),
+            SubscriptionStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// A product ordered for a user on the schedule of a plan.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Subscription {
+    pub id: i64,
+    pub user_id: i64,
+    pub plan: SubscriptionPlan,
+    pub variant_id: Option<i64>,
+    pub quantity: i32,
+    pub status: SubscriptionStatus,
+    /// When the next order is placed.
+    pub next_run_at: NaiveDateTime,
+    /// Order placed by the latest run.
+    pub last_order_id: Option<i64>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Outcome of running the subscriptions due.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SubscriptionRunReport {
+    /// Runs an order was placed for.
+    pub ordered: usize,
+    /// Runs skipped because the product was out of stock or unavailable.
+    pub skipped: usize,
+    /// Runs that failed and are tried again on the next pass.
+    pub failed: usize,
+}
+
+#[derive(Debug)]
+pub enum SubscriptionError {
+    NotFound,
+    InvalidData,
+    InvalidStatusTransition,
+    /// The product of the plan is not published, or no longer.
+    ProductUnavailable,
+    InternalError,
+}
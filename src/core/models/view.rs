@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// Shortest and longest anonymous visitor token accepted.
+pub const MIN_ANONYMOUS_TOKEN_LENGTH: usize = 16;
+pub const MAX_ANONYMOUS_TOKEN_LENGTH: usize = 128;
+
+/// Who viewed a product: a signed-in user or an anonymous visitor identified by
+/// a token of their own.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum Viewer {
+    User(i64),
+    Anonymous(String),
+}
+
+impl Viewer {
+    /// Accepts tokens of letters, digits, `-` and `_`, such as UUIDs.
+    pub fn anonymous(token: &str) -> Result<Viewer, ViewError> {
+        let token = token.trim();
+        if !(MIN_ANONYMOUS_TOKEN_LENGTH..=MAX_ANONYMOUS_TOKEN_LENGTH).contains(&token.len())
+            || !token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(ViewError::InvalidData);
+        }
+        Ok(Viewer::Anonymous(token.to_string()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewSettings {
+    /// Products kept in the recently viewed list of each viewer.
+    pub recent_limit: i64,
+    /// How long the list of an anonymous visitor is kept after their last view.
+    pub anonymous_ttl_seconds: u64,
+    /// Days of views the trending sort counts.
+    pub trending_days: i64,
+}
+
+#[derive(Debug)]
+pub enum ViewError {
+    InvalidData,
+    InternalError,
+}
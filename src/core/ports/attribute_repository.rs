@@ -0,0 +1,41 @@
+use crate::core::models::{
+    attribute::{Attribute, AttributeValue, ProductAttribute},
+    product::ProductError,
+};
+
+pub trait AttributeRepository: Send + Sync {
+    fn create_attribute(&mut self, attribute: Attribute) -> Result<Attribute, ProductError>;
+
+    fn find_attribute_by_id(&mut self, id: i64) -> Result<Attribute, ProductError>;
+
+    /// Returns the attributes defined on the categories or on any of their
+    /// ancestors, closest category first.
+    fn find_inherited_attributes(
+        &mut self,
+        category_ids: &[i64],
+    ) -> Result<Vec<Attribute>, ProductError>;
+
+    /// Saves the name, unit, filterable flag and options of an attribute. Fails
+    /// with [`ProductError::InvalidAttribute`] when a product still uses an
+    /// option being removed.
+    fn update_attribute(&mut self, attribute: Attribute) -> Result<Attribute, ProductError>;
+
+    /// Deletes an attribute along with its values on every product.
+    fn delete_attribute(&mut self, id: i64) -> Result<(), ProductError>;
+
+    fn find_product_attributes(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductAttribute>, ProductError>;
+
+    /// Deletes the attribute values of a product that none of its categories
+    /// nor their ancestors define any more.
+    fn delete_stale_product_attributes(&mut self, product_id: i64) -> Result<(), ProductError>;
+
+    /// Replaces every attribute value of a product.
+    fn set_product_attributes(
+        &mut self,
+        product_id: i64,
+        values: Vec<(i64, AttributeValue)>,
+    ) -> Result<Vec<ProductAttribute>, ProductError>;
+}
@@ -0,0 +1,30 @@
+use std::io::Read;
+
+/// Stores uploaded files (product images, ...) outside of the database.
+pub trait BlobStorage: Send + Sync {
+    /// Stores `data` under `key`, replacing any previous blob, and returns its public URL.
+    fn put(
+        &mut self,
+        key: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> Result<String, BlobStorageError>;
+
+    fn get(&mut self, key: &str) -> Result<Vec<u8>, BlobStorageError>;
+
+    /// Opens the blob stored under `key` to read it a piece at a time, for blobs
+    /// too large to load at once.
+    fn open(&mut self, key: &str) -> Result<Box<dyn Read + Send>, BlobStorageError>;
+
+    /// Removes the blob stored under `key`; removing a missing blob is not an error.
+    fn delete(&mut self, key: &str) -> Result<(), BlobStorageError>;
+
+    fn url(&self, key: &str) -> String;
+}
+
+#[derive(Debug)]
+pub enum BlobStorageError {
+    NotFound,
+    InvalidKey,
+    InternalError,
+}
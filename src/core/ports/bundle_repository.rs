@@ -0,0 +1,21 @@
+use crate::core::models::{bundle::Bundle, product::ProductError};
+
+pub trait BundleRepository: Send + Sync {
+    /// Loads the bundle a product is sold as; [`ProductError::NotFound`] when it
+    /// is not a bundle.
+    fn find_bundle_by_product_id(&mut self, product_id: i64) -> Result<Bundle, ProductError>;
+
+    /// Returns the ids of the bundles the product is a component of.
+    fn find_bundle_ids_by_component_id(
+        &mut self,
+        component_id: i64,
+    ) -> Result<Vec<i64>, ProductError>;
+
+    /// Turns a product into a bundle, or replaces its discount and components
+    /// when it already is one.
+    fn save_bundle(&mut self, bundle: Bundle) -> Result<Bundle, ProductError>;
+
+    /// Sells the product on its own again; its stock is kept as it was last
+    /// derived from the components.
+    fn delete_bundle(&mut self, product_id: i64) -> Result<(), ProductError>;
+}
@@ -1,16 +1,35 @@
 use crate::core::models::cart::{Cart, CartItem};
 
 pub trait CartRepository: Send + Sync {
-    fn create_cart(&mut self, user_id: i64) -> Result<Cart, CartError>;
+    fn create_cart(
+        &mut self,
+        user_id: i64,
+        name: String,
+        is_active: bool,
+    ) -> Result<Cart, CartError>;
     fn find_cart_by_id(&mut self, id: i64) -> Result<Cart, CartError>;
-    fn find_carts_by_user_id(&mut self, user_id: i64) -> Result<Cart, CartError>;
+    fn find_carts_by_user_id(&mut self, user_id: i64) -> Result<Vec<Cart>, CartError>;
+    fn find_active_cart_by_user_id(&mut self, user_id: i64) -> Result<Cart, CartError>;
+    fn rename_cart(&mut self, id: i64, new_name: String) -> Result<Cart, CartError>;
+    /// Marks `cart_id` as the active cart of `user_id` and deactivates the others
+    /// within a single transaction.
+    fn set_active_cart(&mut self, user_id: i64, cart_id: i64) -> Result<Cart, CartError>;
     fn delete_cart(&mut self, id: i64) -> Result<(), CartError>;
+    fn find_cart_item_by_id(&mut self, cart_item_id: i64) -> Result<CartItem, CartError>;
+    fn find_cart_items_by_cart_id(&mut self, cart_id: i64) -> Result<Vec<CartItem>, CartError>;
     fn add_cart_item(&mut self, cart_item: CartItem) -> Result<CartItem, CartError>;
     fn update_cart_item_quantity(
         &mut self,
         cart_item_id: i64,
         new_quantity: i32,
     ) -> Result<CartItem, CartError>;
+    /// Moves an item into `target_cart_id`, merging its quantity into an existing
+    /// line for the same product and customizations when there is one.
+    fn move_cart_item(
+        &mut self,
+        cart_item_id: i64,
+        target_cart_id: i64,
+    ) -> Result<CartItem, CartError>;
     fn remove_cart_item(&mut self, cart_item_id: i64) -> Result<(), CartError>;
 }
 
@@ -21,4 +40,5 @@ pub enum CartError {
     DatabaseError,
     Conflict,
     InvalidQuantity,
+    InvalidCustomization,
 }
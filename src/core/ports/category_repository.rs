@@ -1,14 +1,45 @@
-use crate::core::models::category::{Category, CategoryError};
+use chrono::NaiveDateTime;
+
+use crate::core::models::{
+    category::{Category, CategoryError},
+    pagination::{Page, Pagination},
+    seo::{SeoMetadata, SlugLookup},
+};
 
 pub trait CategoryRepository: Send + Sync {
     fn create_category(
         &mut self,
         name: String,
+        slug: String,
         description: String,
         parent_id: Option<i64>,
     ) -> Result<Category, CategoryError>;
+    /// Categories in the trash are left out of this and every other read but
+    /// [`Self::find_deleted_categories`].
     fn find_category_by_id(&mut self, id: i64) -> Result<Category, CategoryError>;
-    fn find_all_categories(&mut self) -> Result<Vec<Category>, CategoryError>;
+    /// Returns the id of the category `slug` belongs to, or the current slug of
+    /// the category when `slug` is one it was renamed from.
+    fn resolve_category_slug(&mut self, slug: &str) -> Result<SlugLookup<i64>, CategoryError>;
+    /// Whether `slug` is the current or a former slug of any category other than
+    /// `except_category_id`.
+    fn is_category_slug_taken(
+        &mut self,
+        slug: &str,
+        except_category_id: Option<i64>,
+    ) -> Result<bool, CategoryError>;
+    /// Replaces the slug and SEO metadata of the category. The previous slug is
+    /// kept as a redirect when it changes.
+    fn update_category_seo(
+        &mut self,
+        category_id: i64,
+        new_slug: String,
+        new_seo: SeoMetadata,
+    ) -> Result<Category, CategoryError>;
+    /// Returns a page of every category, oldest first.
+    fn find_all_categories(
+        &mut self,
+        pagination: Pagination,
+    ) -> Result<Page<Category>, CategoryError>;
     fn update_category(
         &mut self,
         category_id: i64,
@@ -16,5 +47,20 @@ pub trait CategoryRepository: Send + Sync {
         new_description: String,
         new_parent_id: Option<i64>,
     ) -> Result<Category, CategoryError>;
+    /// Moves the category to the trash.
     fn delete_category(&mut self, category_id: i64) -> Result<(), CategoryError>;
+    /// Takes the category back out of the trash.
+    fn restore_category(&mut self, category_id: i64) -> Result<Category, CategoryError>;
+    /// Returns a page of the categories in the trash, oldest first.
+    fn find_deleted_categories(
+        &mut self,
+        pagination: Pagination,
+    ) -> Result<Page<Category>, CategoryError>;
+    /// Deletes for good the categories moved to the trash before
+    /// `deleted_before`, except the ones still the primary category of a
+    /// product or the parent of another category. Returns how many were deleted.
+    fn purge_deleted_categories(
+        &mut self,
+        deleted_before: NaiveDateTime,
+    ) -> Result<usize, CategoryError>;
 }
@@ -0,0 +1,39 @@
+use crate::core::models::product::ImageSize;
+
+/// Decodes uploaded images and produces their resized renditions.
+pub trait ImageProcessor: Send + Sync {
+    /// Reads the real format and dimensions of `data` from its content, without
+    /// decoding the whole image.
+    fn inspect(&self, data: &[u8]) -> Result<ImageInfo, ImageProcessorError>;
+
+    /// Decodes `data` once and renders it at every size in `sizes`, both in its
+    /// own format and as WebP.
+    fn render(
+        &self,
+        data: &[u8],
+        sizes: &[ImageSize],
+    ) -> Result<Vec<RenderedImage>, ImageProcessorError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageInfo {
+    pub content_type: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedImage {
+    pub size: ImageSize,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+    pub webp_data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum ImageProcessorError {
+    UnsupportedFormat,
+    InvalidImage,
+    InternalError,
+}
@@ -3,4 +3,18 @@ pub mod auth_repository;
 pub mod product_repository;
 pub mod order_repository;
 pub mod category_repository;
-pub mod cart_repository;
\ No newline at end of file
+pub mod cart_repository;
+pub mod variation_repository;
+pub mod product_variant_repository;
+pub mod product_image_repository;
+pub mod blob_storage;
+pub mod image_processor;
+pub mod search_index;
+pub mod review_repository;
+pub mod question_repository;
+pub mod recommendation_repository;
+pub mod product_view_repository;
+pub mod attribute_repository;
+pub mod bundle_repository;
+pub mod product_file_repository;
+pub mod subscription_repository;
\ No newline at end of file
@@ -1,13 +1,36 @@
-use crate::core::models::order::{Order, OrderError, OrderItem, OrderStatus};
+use chrono::NaiveDateTime;
+
+use crate::core::models::{
+    download::DownloadGrant,
+    order::{Order, OrderError, OrderItem, OrderStatus, StockReservation},
+    pagination::{Page, Pagination},
+};
 
 pub trait OrderRepository: Send + Sync {
     fn create_order(&mut self, order: Order) -> Result<Order, OrderError>;
+    /// Takes the reserved stock, writes the order with its items and removes the
+    /// cart items it was placed from, all in one transaction. Fails with
+    /// [`OrderError::OutOfStock`] without changing anything when a reservation
+    /// would drop a stock below zero.
+    fn place_order(&mut self, order: Order, items: Vec<OrderItem>, reservations: Vec<StockReservation>, cart_item_ids: Vec<i64>) -> Result<(Order, Vec<OrderItem>), OrderError>;
     fn find_order_by_id(&mut self, id: i64) -> Result<Order, OrderError>;
-    fn find_orders_by_user_id(&mut self, user_id: i64) -> Result<Vec<Order>, OrderError>;
+    /// Returns a page of the orders of the user, newest first.
+    fn find_orders_by_user_id(&mut self, user_id: i64, pagination: Pagination) -> Result<Page<Order>, OrderError>;
     fn update_order_status(&mut self, id: i64, new_status: OrderStatus) -> Result<Order, OrderError>;
+    /// Marks a pending order as paid with `new_status`, and grants the downloads
+    /// of its digital products. Fails with [`OrderError::InvalidStatusTransition`]
+    /// when the order is no longer pending.
+    fn mark_order_paid(&mut self, id: i64, paid_at: NaiveDateTime, new_status: OrderStatus, grants: Vec<DownloadGrant>) -> Result<Order, OrderError>;
     fn delete_order(&mut self, id: i64) -> Result<(), OrderError>;
     fn add_order_item(&mut self, order_item: OrderItem) -> Result<OrderItem, OrderError>;
     fn find_order_items_by_order_id(&mut self, order_id: i64) -> Result<Vec<OrderItem>, OrderError>;
+    fn find_order_item_by_id(&mut self, order_item_id: i64) -> Result<OrderItem, OrderError>;
     fn update_order_item_quantity(&mut self, order_item_id: i64, new_quantity: i32) -> Result<OrderItem, OrderError>;
     fn remove_order_item(&mut self, order_item_id: i64) -> Result<(), OrderError>;
+    /// Whether the user has received the product in one of their orders.
+    fn has_delivered_product(&mut self, user_id: i64, product_id: i64) -> Result<bool, OrderError>;
+    fn find_download_grants_by_order_id(&mut self, order_id: i64) -> Result<Vec<DownloadGrant>, OrderError>;
+    /// Counts a download of the files of the order item, unless its access has
+    /// expired by `now` or every allowed download has been used.
+    fn record_download(&mut self, order_item_id: i64, now: NaiveDateTime) -> Result<DownloadGrant, OrderError>;
 }
\ No newline at end of file
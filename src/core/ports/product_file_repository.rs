@@ -0,0 +1,16 @@
+use crate::core::models::{download::ProductFile, product::ProductError};
+
+pub trait ProductFileRepository: Send + Sync {
+    fn create_file(&mut self, file: ProductFile) -> Result<ProductFile, ProductError>;
+
+    fn find_file_by_id(&mut self, id: i64) -> Result<ProductFile, ProductError>;
+
+    /// Returns the files of a product, oldest first.
+    fn find_files_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductFile>, ProductError>;
+
+    /// Deletes a file and returns it.
+    fn delete_file(&mut self, id: i64) -> Result<ProductFile, ProductError>;
+}
@@ -0,0 +1,42 @@
+use crate::core::models::product::{ProductError, ProductImage};
+
+pub trait ProductImageRepository: Send + Sync {
+    /// Appends an image to the end of the product gallery; its position and
+    /// primary flag are assigned here. The first image of a product becomes its
+    /// primary image.
+    fn create_image(&mut self, image: ProductImage) -> Result<ProductImage, ProductError>;
+
+    fn find_image_by_id(&mut self, id: i64) -> Result<ProductImage, ProductError>;
+
+    /// Returns the gallery of a product in display order.
+    fn find_images_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductImage>, ProductError>;
+
+    /// Returns the galleries of several products, each in display order.
+    fn find_images_by_product_ids(
+        &mut self,
+        product_ids: &[i64],
+    ) -> Result<Vec<ProductImage>, ProductError>;
+
+    fn update_image_alt_text(
+        &mut self,
+        id: i64,
+        new_alt_text: Option<String>,
+    ) -> Result<ProductImage, ProductError>;
+
+    /// Sets the display order of the gallery; `image_ids` must list every image
+    /// of the product exactly once.
+    fn reorder_images(
+        &mut self,
+        product_id: i64,
+        image_ids: Vec<i64>,
+    ) -> Result<Vec<ProductImage>, ProductError>;
+
+    fn set_primary_image(&mut self, id: i64) -> Result<ProductImage, ProductError>;
+
+    /// Deletes an image and returns it. When it was the primary image, the next
+    /// image of the gallery takes its place.
+    fn delete_image(&mut self, id: i64) -> Result<ProductImage, ProductError>;
+}
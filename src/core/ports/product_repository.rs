@@ -1,27 +1,128 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+
 use crate::core::models::{
-    product::{Product, ProductError},
+    customization::CustomizationField,
+    import::{ImportAction, ProductImport},
+    pagination::{Page, Pagination},
+    product::{
+        Product, ProductError, ProductFacets, ProductFilter, ProductSort, ProductStatus,
+        ProductVisibility, SearchSuggestion,
+    },
     product_category::{ProductCategory, ProductCategoryError},
+    seo::{SeoMetadata, SlugLookup},
 };
 
 pub trait ProductRepository: Send + Sync {
+    #[allow(clippy::too_many_arguments)]
     fn create_product(
         &mut self,
         name: String,
+        slug: String,
         description: String,
         price: f64,
         stock: i32,
         product_image: Option<String>,
         category_id: Option<i64>,
+        status: ProductStatus,
     ) -> Result<Product, ProductError>;
 
+    /// Loads a product whatever its status. Products in the trash are left out
+    /// of this and every other read but [`Self::find_deleted_products`].
     fn find_product_by_id(&mut self, id: i64) -> Result<Product, ProductError>;
 
-    fn find_all_products(&mut self) -> Result<Vec<Product>, ProductError>;
+    /// Returns the id of the product `slug` belongs to, or the current slug of
+    /// the product when `slug` is one it was renamed from.
+    fn resolve_product_slug(&mut self, slug: &str) -> Result<SlugLookup<i64>, ProductError>;
+
+    /// Whether `slug` is the current or a former slug of any product other than
+    /// `except_product_id`.
+    fn is_product_slug_taken(
+        &mut self,
+        slug: &str,
+        except_product_id: Option<i64>,
+    ) -> Result<bool, ProductError>;
 
-    fn find_products_by_name(&mut self, name: String) -> Result<Vec<Product>, ProductError>;
+    /// Replaces the slug and SEO metadata of the product. The previous slug is
+    /// kept as a redirect when it changes.
+    fn update_product_seo(
+        &mut self,
+        id: i64,
+        new_slug: String,
+        new_seo: SeoMetadata,
+    ) -> Result<Product, ProductError>;
 
+    /// Loads the products with the given ids, in no particular order; unknown
+    /// ids are skipped.
+    fn find_products_by_ids(&mut self, ids: &[i64]) -> Result<Vec<Product>, ProductError>;
+
+    /// Returns a page of every visible product, oldest first.
+    fn find_all_products(
+        &mut self,
+        visibility: ProductVisibility,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError>;
+
+    /// Product and category names starting with `prefix`, followed by names
+    /// that resemble it once it is at least three characters long. Only the
+    /// names of published products are suggested.
+    fn suggest_names(
+        &mut self,
+        prefix: String,
+        limit: i64,
+    ) -> Result<Vec<SearchSuggestion>, ProductError>;
+
+    /// Returns a page of the visible products matching `filter`, in `sort` order.
+    fn find_products(
+        &mut self,
+        filter: &ProductFilter,
+        visibility: ProductVisibility,
+        sort: ProductSort,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError>;
+
+    /// Counts the visible products matching `filter` per category, price bucket
+    /// and variation option. Buckets and options without products are left out.
+    fn count_product_facets(
+        &mut self,
+        filter: &ProductFilter,
+        visibility: ProductVisibility,
+    ) -> Result<ProductFacets, ProductError>;
+
+    /// Returns a page of the visible products assigned to the category,
+    /// primary or not, oldest first.
+    fn find_products_by_category_id(
+        &mut self,
+        category_id: i64,
+        visibility: ProductVisibility,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError>;
+
+    /// Moves the product to the trash.
     fn delete_product(&mut self, id: i64) -> Result<(), ProductError>;
 
+    /// Takes the product back out of the trash.
+    fn restore_product(&mut self, id: i64) -> Result<Product, ProductError>;
+
+    /// Returns a page of the products in the trash, oldest first.
+    fn find_deleted_products(
+        &mut self,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError>;
+
+    /// Ids of the products moved to the trash before `deleted_before` that no
+    /// order nor subscription references.
+    fn find_purgeable_product_ids(
+        &mut self,
+        deleted_before: NaiveDateTime,
+    ) -> Result<Vec<i64>, ProductError>;
+
+    /// Deletes a product in the trash for good, unless an order or a
+    /// subscription references it.
+    fn purge_product(&mut self, id: i64) -> Result<(), ProductError>;
+
+    #[allow(clippy::too_many_arguments)]
     fn update_product(
         &mut self,
         id: i64,
@@ -32,9 +133,64 @@ pub trait ProductRepository: Send + Sync {
         new_product_image: Option<String>,
         new_category_id: Option<i64>,
     ) -> Result<Product, ProductError>;
+
+    fn update_product_status(
+        &mut self,
+        id: i64,
+        new_status: ProductStatus,
+        new_publish_at: Option<NaiveDateTime>,
+        new_unpublish_at: Option<NaiveDateTime>,
+    ) -> Result<Product, ProductError>;
+
+    fn update_product_digital(
+        &mut self,
+        id: i64,
+        is_digital: bool,
+    ) -> Result<Product, ProductError>;
+
+    /// Publishes the products whose `publish_at` has passed by `now`, then
+    /// archives the published ones whose `unpublish_at` has, clearing the
+    /// times applied. Returns the number of status changes.
+    fn apply_product_schedules(&mut self, now: NaiveDateTime) -> Result<usize, ProductError>;
+
+    /// Adds the views counted on `day` by product id, skipping products that no
+    /// longer exist, then sets the trending views of the products to their
+    /// views of the last `trending_days` days up to `day`.
+    fn add_product_views(
+        &mut self,
+        views: &HashMap<i64, i64>,
+        day: NaiveDate,
+        trending_days: i64,
+    ) -> Result<(), ProductError>;
+
+    /// Creates or updates the products of an import batch in order, in one
+    /// transaction. Rows match a product by SKU, then by slug. Returns the
+    /// outcome of every row; the batch is rolled back when any row fails, and on
+    /// a dry run.
+    fn import_products(
+        &mut self,
+        rows: &[ProductImport],
+        dry_run: bool,
+    ) -> Result<Vec<Result<ImportAction, ProductError>>, ProductError>;
+
+    fn find_customization_fields(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<CustomizationField>, ProductError>;
+
+    /// Replaces every customization field of the product with `fields`.
+    fn set_customization_fields(
+        &mut self,
+        product_id: i64,
+        fields: Vec<CustomizationField>,
+    ) -> Result<Vec<CustomizationField>, ProductError>;
 }
 
-pub trait ProductCategoryRepository {
+/// Categories assigned to products. At most one assignment per product is
+/// primary, and it is kept in sync with the product `category_id`.
+pub trait ProductCategoryRepository: Send + Sync {
+    /// Assigns a category to a product; a primary assignment replaces the
+    /// previous primary one.
     fn create_product_category(
         &self,
         product_category: ProductCategory,
@@ -43,6 +199,7 @@ pub trait ProductCategoryRepository {
     fn find_product_category_by_id(&self, id: i64)
     -> Result<ProductCategory, ProductCategoryError>;
 
+    /// Returns the assignments of a product, primary first.
     fn find_categories_by_product_id(
         &self,
         product_id: i64,
@@ -53,6 +210,14 @@ pub trait ProductCategoryRepository {
         category_id: i64,
     ) -> Result<Vec<ProductCategory>, ProductCategoryError>;
 
+    /// Marks `category_id` as the primary category of the product, assigning it
+    /// first when needed.
+    fn set_primary_product_category(
+        &self,
+        product_id: i64,
+        category_id: i64,
+    ) -> Result<ProductCategory, ProductCategoryError>;
+
     fn delete_product_category(&self, id: i64) -> Result<(), ProductCategoryError>;
 
     fn delete_product_categories_by_product_id(
@@ -0,0 +1,32 @@
+use crate::core::models::product::{ProductError, ProductVariant};
+
+pub trait ProductVariantRepository: Send + Sync {
+    fn create_variant(
+        &mut self,
+        product_id: i64,
+        sku: String,
+        price: Option<f64>,
+        stock: i32,
+        image: Option<String>,
+        variation_option_ids: Vec<i64>,
+    ) -> Result<ProductVariant, ProductError>;
+
+    fn find_variant_by_id(&mut self, id: i64) -> Result<ProductVariant, ProductError>;
+
+    fn find_variants_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<ProductVariant>, ProductError>;
+
+    fn update_variant(
+        &mut self,
+        id: i64,
+        new_sku: String,
+        new_price: Option<f64>,
+        new_stock: i32,
+        new_image: Option<String>,
+        new_variation_option_ids: Vec<i64>,
+    ) -> Result<ProductVariant, ProductError>;
+
+    fn delete_variant(&mut self, id: i64) -> Result<(), ProductError>;
+}
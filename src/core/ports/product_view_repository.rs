@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+
+use crate::core::models::view::{ViewError, Viewer};
+
+pub trait ProductViewRepository: Send + Sync {
+    /// Counts a view of the product and, when the viewer is known, moves it to
+    /// the front of their recently viewed products, of which the `limit` latest
+    /// are kept. Lists of anonymous visitors expire after `anonymous_ttl_seconds`
+    /// without views.
+    fn record_view(
+        &mut self,
+        viewer: Option<&Viewer>,
+        product_id: i64,
+        viewed_at: NaiveDateTime,
+        limit: i64,
+        anonymous_ttl_seconds: u64,
+    ) -> Result<(), ViewError>;
+
+    /// Returns the ids of the products the viewer saw last, latest first.
+    fn find_recently_viewed(&mut self, viewer: &Viewer, limit: i64) -> Result<Vec<i64>, ViewError>;
+
+    /// Sets the views counted so far aside and returns them by product id.
+    /// Views counted meanwhile are kept for the next time. Views set aside
+    /// before and not cleared yet are returned again instead.
+    fn take_view_counts(&mut self) -> Result<HashMap<i64, i64>, ViewError>;
+
+    /// Forgets the views returned by [`ProductViewRepository::take_view_counts`]
+    /// once they are saved.
+    fn clear_view_counts(&mut self) -> Result<(), ViewError>;
+}
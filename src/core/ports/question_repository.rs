@@ -0,0 +1,63 @@
+use crate::core::models::{
+    moderation::ModerationStatus,
+    pagination::{Page, Pagination},
+    question::{ProductAnswer, ProductQuestion, QuestionError},
+};
+
+pub trait QuestionRepository: Send + Sync {
+    /// Saves a question as pending.
+    fn create_question(
+        &mut self,
+        question: ProductQuestion,
+    ) -> Result<ProductQuestion, QuestionError>;
+
+    /// Loads a question without its answers.
+    fn find_question_by_id(&mut self, id: i64) -> Result<ProductQuestion, QuestionError>;
+
+    /// Returns a page of the approved questions of a product with their
+    /// approved answers, newest first.
+    fn find_questions_by_product_id(
+        &mut self,
+        product_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<ProductQuestion>, QuestionError>;
+
+    /// Returns the approved questions of a product with the most approved
+    /// answers, along with those answers.
+    fn find_top_questions(
+        &mut self,
+        product_id: i64,
+        limit: i64,
+    ) -> Result<Vec<ProductQuestion>, QuestionError>;
+
+    /// Returns a page of the questions with the given status, without their
+    /// answers, oldest first.
+    fn find_questions_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<ProductQuestion>, QuestionError>;
+
+    fn update_question_status(
+        &mut self,
+        id: i64,
+        new_status: ModerationStatus,
+    ) -> Result<ProductQuestion, QuestionError>;
+
+    fn create_answer(&mut self, answer: ProductAnswer) -> Result<ProductAnswer, QuestionError>;
+
+    fn find_answer_by_id(&mut self, id: i64) -> Result<ProductAnswer, QuestionError>;
+
+    /// Returns a page of the answers with the given status, oldest first.
+    fn find_answers_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<ProductAnswer>, QuestionError>;
+
+    fn update_answer_status(
+        &mut self,
+        id: i64,
+        new_status: ModerationStatus,
+    ) -> Result<ProductAnswer, QuestionError>;
+}
@@ -0,0 +1,16 @@
+use crate::core::models::recommendation::{ProductRecommendation, RecommendationError};
+
+pub trait RecommendationRepository: Send + Sync {
+    /// Scores every pair of products in the catalog by how often they were
+    /// ordered together and how many categories they share, then replaces the
+    /// recommendations of each product with its `limit` best scored pairs.
+    /// Returns the number of recommendations added or changed.
+    fn refresh_recommendations(&mut self, limit: i64) -> Result<usize, RecommendationError>;
+
+    /// Returns the recommendations of the given products, best first for each
+    /// product.
+    fn find_recommendations(
+        &mut self,
+        product_ids: &[i64],
+    ) -> Result<Vec<ProductRecommendation>, RecommendationError>;
+}
@@ -0,0 +1,46 @@
+use crate::core::models::{
+    moderation::ModerationStatus,
+    pagination::{Page, Pagination},
+    review::{Review, ReviewError},
+};
+
+pub trait ReviewRepository: Send + Sync {
+    /// Saves a review as pending. Fails with [`ReviewError::AlreadyReviewed`]
+    /// when the user has reviewed the product before.
+    fn create_review(&mut self, review: Review) -> Result<Review, ReviewError>;
+
+    fn find_review_by_id(&mut self, id: i64) -> Result<Review, ReviewError>;
+
+    /// Returns a page of the approved reviews of a product, newest first.
+    fn find_reviews_by_product_id(
+        &mut self,
+        product_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<Review>, ReviewError>;
+
+    /// Returns a page of the reviews with the given status, oldest first.
+    fn find_reviews_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<Review>, ReviewError>;
+
+    /// Sets the status of a review; the rating of its product only counts
+    /// approved reviews.
+    fn update_review_status(
+        &mut self,
+        id: i64,
+        new_status: ModerationStatus,
+    ) -> Result<Review, ReviewError>;
+
+    /// Sets or, with `None`, removes the response of the store to a review.
+    fn update_review_response(
+        &mut self,
+        id: i64,
+        new_response: Option<String>,
+    ) -> Result<Review, ReviewError>;
+
+    /// Records that the user found the review helpful. Fails with
+    /// [`ReviewError::AlreadyVoted`] when they already did.
+    fn add_helpful_vote(&mut self, review_id: i64, user_id: i64) -> Result<Review, ReviewError>;
+}
@@ -0,0 +1,44 @@
+use crate::core::models::product::Product;
+
+/// Full-text index of the catalog that product searches run against.
+pub trait SearchIndex: Send + Sync {
+    /// Adds the documents of `products`, replacing the ones they already have.
+    fn index_products(&mut self, products: &[Product]) -> Result<(), SearchIndexError>;
+
+    /// Removes the document of a product; removing a missing one is not an error.
+    fn remove_product(&mut self, product_id: i64) -> Result<(), SearchIndexError>;
+
+    /// Replaces every document with the ones of `products`. Searches keep
+    /// seeing the previous documents until it completes.
+    fn rebuild(&mut self, products: &[Product]) -> Result<(), SearchIndexError>;
+
+    /// Products matching `query`, which accepts web search syntax: quoted
+    /// phrases, `or` and `-` to exclude a term. Hits come most relevant first,
+    /// skipping the `offset` most relevant ones.
+    fn search(
+        &mut self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, SearchIndexError>;
+
+    /// Products whose name resembles `query` despite typos, most similar first.
+    fn find_similar(
+        &mut self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, SearchIndexError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub product_id: i64,
+    pub rank: f32,
+    /// Excerpt of the description with the matched terms wrapped in `<mark>` tags.
+    pub snippet: String,
+}
+
+#[derive(Debug)]
+pub enum SearchIndexError {
+    InternalError,
+}
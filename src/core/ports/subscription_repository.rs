@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+
+use crate::core::models::subscription::{
+    Subscription, SubscriptionError, SubscriptionPlan, SubscriptionStatus,
+};
+
+pub trait SubscriptionRepository: Send + Sync {
+    /// Fails with [`SubscriptionError::InvalidData`] when the product already
+    /// has a plan with the same interval.
+    fn create_plan(
+        &mut self,
+        plan: SubscriptionPlan,
+    ) -> Result<SubscriptionPlan, SubscriptionError>;
+
+    fn find_plan_by_id(&mut self, id: i64) -> Result<SubscriptionPlan, SubscriptionError>;
+
+    /// Returns the plans of a product, shortest interval first.
+    fn find_plans_by_product_id(
+        &mut self,
+        product_id: i64,
+        active_only: bool,
+    ) -> Result<Vec<SubscriptionPlan>, SubscriptionError>;
+
+    /// Saves the discount and active flag of a plan.
+    fn update_plan(
+        &mut self,
+        plan: SubscriptionPlan,
+    ) -> Result<SubscriptionPlan, SubscriptionError>;
+
+    /// Fails with [`SubscriptionError::InvalidData`] while the plan has
+    /// subscriptions, cancelled or not.
+    fn delete_plan(&mut self, id: i64) -> Result<(), SubscriptionError>;
+
+    fn create_subscription(
+        &mut self,
+        subscription: Subscription,
+    ) -> Result<Subscription, SubscriptionError>;
+
+    fn find_subscription_by_id(&mut self, id: i64) -> Result<Subscription, SubscriptionError>;
+
+    /// Returns the subscriptions of the user, newest first.
+    fn find_subscriptions_by_user_id(
+        &mut self,
+        user_id: i64,
+    ) -> Result<Vec<Subscription>, SubscriptionError>;
+
+    fn update_subscription_status(
+        &mut self,
+        id: i64,
+        new_status: SubscriptionStatus,
+        new_next_run_at: NaiveDateTime,
+    ) -> Result<Subscription, SubscriptionError>;
+
+    /// Returns the active subscriptions whose next run is due by `now`, earliest
+    /// first.
+    fn find_due_subscriptions(
+        &mut self,
+        now: NaiveDateTime,
+    ) -> Result<Vec<Subscription>, SubscriptionError>;
+
+    /// Moves the next run of an active subscription from `run_at` to
+    /// `next_run_at`. Returns whether it did, which it does not when the run
+    /// was claimed, or the subscription paused or changed, in the meantime.
+    fn claim_subscription_run(
+        &mut self,
+        id: i64,
+        run_at: NaiveDateTime,
+        next_run_at: NaiveDateTime,
+    ) -> Result<bool, SubscriptionError>;
+
+    fn set_last_order(&mut self, id: i64, order_id: i64) -> Result<(), SubscriptionError>;
+}
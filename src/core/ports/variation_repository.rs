@@ -0,0 +1,55 @@
+use crate::core::models::product::{ProductError, Variation, VariationOption};
+
+pub trait VariationRepository: Send + Sync {
+    fn create_variation(&mut self, category_id: i64, name: String)
+    -> Result<Variation, ProductError>;
+
+    fn find_variation_by_id(&mut self, id: i64) -> Result<Variation, ProductError>;
+
+    fn find_variations_by_category_id(
+        &mut self,
+        category_id: i64,
+    ) -> Result<Vec<Variation>, ProductError>;
+
+    fn update_variation(&mut self, id: i64, new_name: String) -> Result<Variation, ProductError>;
+
+    fn delete_variation(&mut self, id: i64) -> Result<(), ProductError>;
+
+    fn create_variation_option(
+        &mut self,
+        variation_id: i64,
+        value: String,
+    ) -> Result<VariationOption, ProductError>;
+
+    fn find_variation_option_by_id(&mut self, id: i64) -> Result<VariationOption, ProductError>;
+
+    fn find_variation_options_by_variation_id(
+        &mut self,
+        variation_id: i64,
+    ) -> Result<Vec<VariationOption>, ProductError>;
+
+    fn update_variation_option(
+        &mut self,
+        id: i64,
+        new_value: String,
+    ) -> Result<VariationOption, ProductError>;
+
+    fn delete_variation_option(&mut self, id: i64) -> Result<(), ProductError>;
+
+    fn find_variation_options_by_product_id(
+        &mut self,
+        product_id: i64,
+    ) -> Result<Vec<VariationOption>, ProductError>;
+
+    fn add_product_variation_option(
+        &mut self,
+        product_id: i64,
+        variation_option_id: i64,
+    ) -> Result<(), ProductError>;
+
+    fn remove_product_variation_option(
+        &mut self,
+        product_id: i64,
+        variation_option_id: i64,
+    ) -> Result<(), ProductError>;
+}
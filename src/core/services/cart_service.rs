@@ -0,0 +1,276 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use serde_json::Value;
+
+use crate::core::{
+    models::{
+        cart::{Cart, CartItem},
+        customization::validate_customizations,
+        product::ProductError,
+    },
+    ports::{
+        cart_repository::{CartError, CartRepository},
+        product_repository::ProductRepository,
+        product_variant_repository::ProductVariantRepository,
+    },
+};
+
+const DEFAULT_CART_NAME: &str = "Default";
+
+#[derive(Clone)]
+pub struct CartService {
+    pub(crate) cart_repo: Arc<Mutex<dyn CartRepository>>,
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+}
+
+pub fn new_cart_service(
+    cart_repo: Arc<Mutex<dyn CartRepository>>,
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+) -> CartService {
+    CartService {
+        cart_repo,
+        product_repo,
+        variant_repo,
+    }
+}
+
+impl CartService {
+    pub fn get_all(&mut self, user_id: i64) -> Result<Vec<Cart>, CartError> {
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let carts = cart_repo.find_carts_by_user_id(user_id)?;
+        Ok(carts)
+    }
+
+    pub fn get(&mut self, user_id: i64, cart_id: i64) -> Result<(Cart, Vec<CartItem>), CartError> {
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let cart = Self::find_owned_cart(&mut *cart_repo, user_id, cart_id)?;
+        let items = cart_repo.find_cart_items_by_cart_id(cart.id)?;
+        Ok((cart, items))
+    }
+
+    /// Returns the active cart of the user, creating a default one the first time.
+    pub fn get_active(&mut self, user_id: i64) -> Result<(Cart, Vec<CartItem>), CartError> {
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let cart = Self::find_or_create_active_cart(&mut *cart_repo, user_id)?;
+        let items = cart_repo.find_cart_items_by_cart_id(cart.id)?;
+        Ok((cart, items))
+    }
+
+    pub fn create(&mut self, user_id: i64, name: String) -> Result<Cart, CartError> {
+        let name = Self::validate_name(name)?;
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let is_first = cart_repo.find_carts_by_user_id(user_id)?.is_empty();
+        let cart = cart_repo.create_cart(user_id, name, is_first)?;
+        Ok(cart)
+    }
+
+    pub fn rename(
+        &mut self,
+        user_id: i64,
+        cart_id: i64,
+        new_name: String,
+    ) -> Result<Cart, CartError> {
+        let new_name = Self::validate_name(new_name)?;
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let cart = Self::find_owned_cart(&mut *cart_repo, user_id, cart_id)?;
+        let cart = cart_repo.rename_cart(cart.id, new_name)?;
+        Ok(cart)
+    }
+
+    pub fn activate(&mut self, user_id: i64, cart_id: i64) -> Result<Cart, CartError> {
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let cart = Self::find_owned_cart(&mut *cart_repo, user_id, cart_id)?;
+        let cart = cart_repo.set_active_cart(user_id, cart.id)?;
+        Ok(cart)
+    }
+
+    /// Deletes a cart; when it was the active one, the oldest remaining cart takes its place.
+    pub fn delete(&mut self, user_id: i64, cart_id: i64) -> Result<(), CartError> {
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let cart = Self::find_owned_cart(&mut *cart_repo, user_id, cart_id)?;
+        cart_repo.delete_cart(cart.id)?;
+
+        if cart.is_active {
+            let remaining = cart_repo.find_carts_by_user_id(user_id)?;
+            if let Some(next) = remaining.first() {
+                cart_repo.set_active_cart(user_id, next.id)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a product to `cart_id`, or to the active cart when no cart is given.
+    ///
+    /// Only published products can be added. Products sold as variants require a
+    /// `variant_id` of that product. Lines are merged only when the product, the
+    /// variant and the customizations all match.
+    pub fn add_item(
+        &mut self,
+        user_id: i64,
+        cart_id: Option<i64>,
+        product_id: i64,
+        variant_id: Option<i64>,
+        quantity: i32,
+        customizations: Value,
+    ) -> Result<CartItem, CartError> {
+        if quantity <= 0 {
+            return Err(CartError::InvalidQuantity);
+        }
+
+        {
+            let mut variant_repo = self.variant_repo.lock().unwrap();
+            let variants = variant_repo
+                .find_variants_by_product_id(product_id)
+                .map_err(|_| CartError::DatabaseError)?;
+            let valid = match variant_id {
+                Some(variant_id) => variants.iter().any(|variant| variant.id == variant_id),
+                None => variants.is_empty(),
+            };
+            if !valid {
+                return Err(CartError::InvalidData);
+            }
+        }
+
+        let fields = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            let product = product_repo
+                .find_product_by_id(product_id)
+                .map_err(|err| match err {
+                    ProductError::NotFound => CartError::InvalidData,
+                    _ => CartError::DatabaseError,
+                })?;
+            if !product.is_published(Utc::now().naive_utc()) {
+                return Err(CartError::InvalidData);
+            }
+            product_repo
+                .find_customization_fields(product_id)
+                .map_err(|err| match err {
+                    ProductError::NotFound => CartError::InvalidData,
+                    _ => CartError::DatabaseError,
+                })?
+        };
+        let (customizations, customization_price) =
+            validate_customizations(&fields, &customizations)
+                .map_err(|_| CartError::InvalidCustomization)?;
+
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let cart = match cart_id {
+            Some(cart_id) => Self::find_owned_cart(&mut *cart_repo, user_id, cart_id)?,
+            None => Self::find_or_create_active_cart(&mut *cart_repo, user_id)?,
+        };
+
+        let items = cart_repo.find_cart_items_by_cart_id(cart.id)?;
+        if let Some(existing) = items.iter().find(|item| {
+            item.product_id == product_id
+                && item.variant_id == variant_id
+                && item.customizations == customizations
+        }) {
+            return cart_repo.update_cart_item_quantity(existing.id, existing.quantity + quantity);
+        }
+
+        let now = Utc::now().naive_utc();
+        let item = cart_repo.add_cart_item(CartItem {
+            id: 0,
+            cart_id: cart.id,
+            product_id,
+            variant_id,
+            quantity,
+            customizations,
+            customization_price,
+            created_at: now,
+            updated_at: now,
+        })?;
+        Ok(item)
+    }
+
+    pub fn update_item_quantity(
+        &mut self,
+        user_id: i64,
+        cart_item_id: i64,
+        new_quantity: i32,
+    ) -> Result<CartItem, CartError> {
+        if new_quantity <= 0 {
+            return Err(CartError::InvalidQuantity);
+        }
+
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let item = Self::find_owned_cart_item(&mut *cart_repo, user_id, cart_item_id)?;
+        let item = cart_repo.update_cart_item_quantity(item.id, new_quantity)?;
+        Ok(item)
+    }
+
+    pub fn remove_item(&mut self, user_id: i64, cart_item_id: i64) -> Result<(), CartError> {
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let item = Self::find_owned_cart_item(&mut *cart_repo, user_id, cart_item_id)?;
+        cart_repo.remove_cart_item(item.id)?;
+        Ok(())
+    }
+
+    pub fn move_item(
+        &mut self,
+        user_id: i64,
+        cart_item_id: i64,
+        target_cart_id: i64,
+    ) -> Result<CartItem, CartError> {
+        let mut cart_repo = self.cart_repo.lock().unwrap();
+        let item = Self::find_owned_cart_item(&mut *cart_repo, user_id, cart_item_id)?;
+        let target = Self::find_owned_cart(&mut *cart_repo, user_id, target_cart_id)?;
+
+        if item.cart_id == target.id {
+            return Err(CartError::InvalidData);
+        }
+
+        let item = cart_repo.move_cart_item(item.id, target.id)?;
+        Ok(item)
+    }
+
+    // Private Methods
+
+    fn validate_name(name: String) -> Result<String, CartError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(CartError::InvalidData);
+        }
+        Ok(name)
+    }
+
+    fn find_owned_cart(
+        cart_repo: &mut dyn CartRepository,
+        user_id: i64,
+        cart_id: i64,
+    ) -> Result<Cart, CartError> {
+        let cart = cart_repo.find_cart_by_id(cart_id)?;
+        if cart.user_id != user_id {
+            return Err(CartError::NotFound);
+        }
+        Ok(cart)
+    }
+
+    fn find_owned_cart_item(
+        cart_repo: &mut dyn CartRepository,
+        user_id: i64,
+        cart_item_id: i64,
+    ) -> Result<CartItem, CartError> {
+        let item = cart_repo.find_cart_item_by_id(cart_item_id)?;
+        Self::find_owned_cart(cart_repo, user_id, item.cart_id)?;
+        Ok(item)
+    }
+
+    fn find_or_create_active_cart(
+        cart_repo: &mut dyn CartRepository,
+        user_id: i64,
+    ) -> Result<Cart, CartError> {
+        match cart_repo.find_active_cart_by_user_id(user_id) {
+            Ok(cart) => Ok(cart),
+            Err(CartError::NotFound) => match cart_repo.find_carts_by_user_id(user_id)?.first() {
+                Some(cart) => cart_repo.set_active_cart(user_id, cart.id),
+                None => cart_repo.create_cart(user_id, DEFAULT_CART_NAME.to_string(), true),
+            },
+            Err(err) => Err(err),
+        }
+    }
+}
@@ -1,7 +1,13 @@
 use std::sync::{Arc, Mutex};
 
+use chrono::{Duration, Utc};
+
 use crate::core::{
-    models::category::{Category, CategoryError},
+    models::{
+        category::{Category, CategoryError},
+        pagination::{Page, Pagination},
+        seo::{SeoMetadata, SlugLookup, parse_slug, slugify, unique_slug},
+    },
     ports::category_repository::CategoryRepository,
 };
 #[derive(Clone)]
@@ -21,13 +27,16 @@ impl CategoryService {
         parent_id: Option<i64>,
     ) -> Result<Category, CategoryError> {
         let mut category_repo = self.category_repo.lock().unwrap();
-        let category = category_repo.create_category(name, description, parent_id)?;
+        let slug = unique_slug(slugify(&name), "category", |slug| {
+            category_repo.is_category_slug_taken(slug, None)
+        })?;
+        let category = category_repo.create_category(name, slug, description, parent_id)?;
         Ok(category)
     }
 
-    pub fn get_all(&mut self) -> Result<Vec<Category>, CategoryError> {
+    pub fn get_all(&mut self, pagination: Pagination) -> Result<Page<Category>, CategoryError> {
         let mut category_repo = self.category_repo.lock().unwrap();
-        let categories: Vec<Category> = category_repo.find_all_categories()?;
+        let categories = category_repo.find_all_categories(pagination)?;
         Ok(categories)
     }
 
@@ -37,6 +46,18 @@ impl CategoryService {
         Ok(category)
     }
 
+    /// Looks a category up by slug. Former slugs resolve to
+    /// [`SlugLookup::Moved`] with the slug the category is now found under.
+    pub fn get_by_slug(&mut self, slug: &str) -> Result<SlugLookup<Category>, CategoryError> {
+        let mut category_repo = self.category_repo.lock().unwrap();
+        match category_repo.resolve_category_slug(slug)? {
+            SlugLookup::Found(category_id) => Ok(SlugLookup::Found(
+                category_repo.find_category_by_id(category_id)?,
+            )),
+            SlugLookup::Moved(slug) => Ok(SlugLookup::Moved(slug)),
+        }
+    }
+
     pub fn update(
         &mut self,
         category_id: i64,
@@ -50,9 +71,61 @@ impl CategoryService {
         Ok(category)
     }
 
+    /// Replaces the SEO metadata of a category, and its slug when `new_slug` is
+    /// given. The previous slug keeps redirecting to the category.
+    pub fn update_seo(
+        &mut self,
+        category_id: i64,
+        new_slug: Option<String>,
+        new_seo: SeoMetadata,
+    ) -> Result<Category, CategoryError> {
+        let new_seo = new_seo
+            .normalize()
+            .map_err(|_| CategoryError::InvalidData)?;
+        let mut category_repo = self.category_repo.lock().unwrap();
+        let category = category_repo.find_category_by_id(category_id)?;
+
+        let new_slug = match new_slug {
+            Some(new_slug) => {
+                let new_slug = parse_slug(&new_slug).map_err(|_| CategoryError::InvalidData)?;
+                if category_repo.is_category_slug_taken(&new_slug, Some(category.id))? {
+                    return Err(CategoryError::SlugTaken);
+                }
+                new_slug
+            }
+            None => category.slug,
+        };
+
+        let category = category_repo.update_category_seo(category.id, new_slug, new_seo)?;
+        Ok(category)
+    }
+
+    /// Moves a category to the trash. Its products and subcategories stay where
+    /// they are until it is purged.
     pub fn delete(&mut self, category_id: i64) -> Result<(), CategoryError> {
         let mut category_repo = self.category_repo.lock().unwrap();
         category_repo.delete_category(category_id)?;
         Ok(())
     }
+
+    pub fn restore(&mut self, category_id: i64) -> Result<Category, CategoryError> {
+        let mut category_repo = self.category_repo.lock().unwrap();
+        let category = category_repo.restore_category(category_id)?;
+        Ok(category)
+    }
+
+    pub fn get_trash(&mut self, pagination: Pagination) -> Result<Page<Category>, CategoryError> {
+        let mut category_repo = self.category_repo.lock().unwrap();
+        let categories = category_repo.find_deleted_categories(pagination)?;
+        Ok(categories)
+    }
+
+    /// Deletes for good the categories that have been in the trash for more
+    /// than `retention_days`. Returns the number of categories purged.
+    pub fn purge_trash(&mut self, retention_days: i64) -> Result<usize, CategoryError> {
+        let deleted_before = Utc::now().naive_utc() - Duration::days(retention_days);
+        let mut category_repo = self.category_repo.lock().unwrap();
+        let purged = category_repo.purge_deleted_categories(deleted_before)?;
+        Ok(purged)
+    }
 }
@@ -0,0 +1,148 @@
+use std::{
+    io::Read,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+
+use crate::core::{
+    models::{
+        download::{DownloadLink, DownloadSettings, ProductFile},
+        order::{OrderError, OrderStatus},
+        product::ProductError,
+    },
+    ports::{
+        blob_storage::BlobStorage, order_repository::OrderRepository,
+        product_file_repository::ProductFileRepository,
+    },
+};
+
+#[derive(Clone)]
+pub struct DownloadService {
+    pub(crate) order_repo: Arc<Mutex<dyn OrderRepository>>,
+    pub(crate) file_repo: Arc<Mutex<dyn ProductFileRepository>>,
+    pub(crate) blob_storage: Arc<Mutex<dyn BlobStorage>>,
+    pub(crate) settings: DownloadSettings,
+}
+
+pub fn new_download_service(
+    order_repo: Arc<Mutex<dyn OrderRepository>>,
+    file_repo: Arc<Mutex<dyn ProductFileRepository>>,
+    blob_storage: Arc<Mutex<dyn BlobStorage>>,
+    settings: DownloadSettings,
+) -> DownloadService {
+    DownloadService {
+        order_repo,
+        file_repo,
+        blob_storage,
+        settings,
+    }
+}
+
+impl DownloadService {
+    /// Signs a link to every file of the digital products of a paid order of the
+    /// user. Links expire along with the access granted at payment.
+    pub fn get_links(
+        &mut self,
+        user_id: i64,
+        order_id: i64,
+    ) -> Result<Vec<DownloadLink>, OrderError> {
+        let (items, grants) = {
+            let mut order_repo = self.order_repo.lock().unwrap();
+            let order = order_repo.find_order_by_id(order_id)?;
+            if order.user_id != user_id {
+                return Err(OrderError::NotFound);
+            }
+            if !Self::is_downloadable(&order.status) {
+                return Ok(vec![]);
+            }
+            let items = order_repo.find_order_items_by_order_id(order.id)?;
+            let grants = order_repo.find_download_grants_by_order_id(order.id)?;
+            (items, grants)
+        };
+
+        let mut file_repo = self.file_repo.lock().unwrap();
+        let mut links = Vec::new();
+        for grant in grants {
+            let Some(item) = items.iter().find(|item| item.id == grant.order_item_id) else {
+                continue;
+            };
+            let files = file_repo
+                .find_files_by_product_id(item.product_id)
+                .map_err(|_| OrderError::DatabaseError)?;
+            links.extend(files.into_iter().map(|file| DownloadLink {
+                order_item_id: item.id,
+                product_id: item.product_id,
+                file_id: file.id,
+                url: self.settings.sign_url(item.id, file.id, grant.expires_at),
+                file_name: file.file_name,
+                size_bytes: file.size_bytes,
+                expires_at: grant.expires_at,
+                downloads_left: grant.downloads_left(),
+            }));
+        }
+        Ok(links)
+    }
+
+    /// Opens a file through a signed link, counting the download against the
+    /// order item. The file is read by the caller, after every lock is released.
+    /// Links that were tampered with, point to another product or belong to a
+    /// cancelled order are not found.
+    pub fn download(
+        &mut self,
+        order_item_id: i64,
+        file_id: i64,
+        expires: i64,
+        signature: &str,
+    ) -> Result<(ProductFile, Box<dyn Read + Send>), OrderError> {
+        let expires_at = self
+            .settings
+            .verify(order_item_id, file_id, expires, signature)
+            .ok_or(OrderError::NotFound)?;
+        let now = Utc::now().naive_utc();
+        if expires_at <= now {
+            return Err(OrderError::DownloadExpired);
+        }
+
+        let item = {
+            let mut order_repo = self.order_repo.lock().unwrap();
+            let item = order_repo.find_order_item_by_id(order_item_id)?;
+            let order = order_repo.find_order_by_id(item.order_id)?;
+            if order.paid_at.is_none() || !Self::is_downloadable(&order.status) {
+                return Err(OrderError::NotFound);
+            }
+            item
+        };
+
+        let file = {
+            let mut file_repo = self.file_repo.lock().unwrap();
+            file_repo
+                .find_file_by_id(file_id)
+                .map_err(|err| match err {
+                    ProductError::NotFound => OrderError::NotFound,
+                    _ => OrderError::DatabaseError,
+                })?
+        };
+        if file.product_id != item.product_id {
+            return Err(OrderError::NotFound);
+        }
+
+        let reader = {
+            let mut blob_storage = self.blob_storage.lock().unwrap();
+            blob_storage
+                .open(&file.storage_key)
+                .map_err(|_| OrderError::DatabaseError)?
+        };
+
+        // Only counted once the file could be opened.
+        let mut order_repo = self.order_repo.lock().unwrap();
+        order_repo.record_download(item.id, now)?;
+        Ok((file, reader))
+    }
+
+    // Private Methods
+
+    fn is_downloadable(status: &OrderStatus) -> bool {
+        !matches!(status, OrderStatus::Cancelled | OrderStatus::Error)
+    }
+}
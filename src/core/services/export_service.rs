@@ -0,0 +1,364 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use crate::core::{
+    models::{
+        category::{Category, category_paths},
+        export::{
+            Availability, CachedExport, ExportError, ExportFormat, ExportSettings, ProductFeedItem,
+        },
+        import::CATEGORY_PATH_SEPARATOR,
+        pagination::{MAX_PAGE_LIMIT, Pagination},
+        product::{Product, ProductImage, ProductVisibility},
+    },
+    ports::{
+        category_repository::CategoryRepository, product_image_repository::ProductImageRepository,
+        product_repository::ProductRepository,
+    },
+};
+
+/// Separator between the values of a multi-valued CSV column.
+const CSV_LIST_SEPARATOR: &str = " | ";
+
+const CSV_HEADER: [&str; 14] = [
+    "id",
+    "sku",
+    "slug",
+    "name",
+    "description",
+    "price",
+    "currency",
+    "stock",
+    "availability",
+    "category",
+    "categories",
+    "link",
+    "image_link",
+    "additional_image_links",
+];
+
+#[derive(Clone)]
+pub struct ExportService {
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) category_repo: Arc<Mutex<dyn CategoryRepository>>,
+    pub(crate) image_repo: Arc<Mutex<dyn ProductImageRepository>>,
+    pub(crate) settings: ExportSettings,
+}
+
+pub fn new_export_service(
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    category_repo: Arc<Mutex<dyn CategoryRepository>>,
+    image_repo: Arc<Mutex<dyn ProductImageRepository>>,
+    settings: ExportSettings,
+) -> ExportService {
+    ExportService {
+        product_repo,
+        category_repo,
+        image_repo,
+        settings,
+    }
+}
+
+impl ExportService {
+    /// Writes every published product to `out` and returns how many were
+    /// written. Products are loaded a page at a time, so the catalog is never
+    /// held in memory as a whole.
+    pub fn export_products(
+        &self,
+        format: ExportFormat,
+        out: &mut dyn Write,
+    ) -> Result<usize, ExportError> {
+        let paths = self.load_category_paths()?;
+        let mut writer = FeedWriter::new(format, out, &self.settings)?;
+
+        let mut count = 0;
+        let mut cursor = None;
+        loop {
+            let pagination = Pagination::new(cursor.as_deref(), Some(MAX_PAGE_LIMIT), false)
+                .map_err(|_| ExportError::InternalError)?;
+            let page = self
+                .product_repo
+                .lock()
+                .unwrap()
+                .find_all_products(ProductVisibility::Published, pagination)
+                .map_err(|_| ExportError::InternalError)?;
+
+            let product_ids: Vec<i64> = page.items.iter().map(|product| product.id).collect();
+            let mut images: HashMap<i64, Vec<ProductImage>> = HashMap::new();
+            for image in self
+                .image_repo
+                .lock()
+                .unwrap()
+                .find_images_by_product_ids(&product_ids)
+                .map_err(|_| ExportError::InternalError)?
+            {
+                images.entry(image.product_id).or_default().push(image);
+            }
+
+            for product in page.items {
+                let gallery = images.remove(&product.id).unwrap_or_default();
+                writer.write_item(&self.feed_item(product, gallery, &paths))?;
+                count += 1;
+            }
+
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        writer.finish()?;
+        Ok(count)
+    }
+
+    /// Returns the export kept on disk for HTTP clients, generating it again
+    /// once it is older than the cache lifetime.
+    pub fn cached_export(&self, format: ExportFormat) -> Result<CachedExport, ExportError> {
+        let dir = Path::new(&self.settings.cache_dir);
+        let path = dir.join(format!("products.{}", format.extension()));
+
+        let age = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+        if let Some(age) = age
+            && age < self.settings.cache_lifetime
+        {
+            return Ok(CachedExport {
+                path,
+                max_age: self.settings.cache_lifetime - age,
+            });
+        }
+
+        fs::create_dir_all(dir).map_err(|_| ExportError::InternalError)?;
+        // Written aside and moved into place, so readers never see a partial export.
+        let tmp_path = dir.join(format!("products.{}.tmp", format.extension()));
+        let file = fs::File::create(&tmp_path).map_err(|_| ExportError::InternalError)?;
+        let mut out = BufWriter::new(file);
+        let result = self
+            .export_products(format, &mut out)
+            .and_then(|_| out.flush().map_err(|_| ExportError::InternalError));
+        if let Err(err) = result {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err);
+        }
+        fs::rename(&tmp_path, &path).map_err(|_| ExportError::InternalError)?;
+
+        Ok(CachedExport {
+            path,
+            max_age: self.settings.cache_lifetime,
+        })
+    }
+
+    /// Paths of every category, by category id, as shown in the feeds.
+    fn load_category_paths(&self) -> Result<HashMap<i64, String>, ExportError> {
+        let mut categories: Vec<Category> = Vec::new();
+        let mut category_repo = self.category_repo.lock().unwrap();
+        let mut cursor = None;
+        loop {
+            let pagination = Pagination::new(cursor.as_deref(), Some(MAX_PAGE_LIMIT), false)
+                .map_err(|_| ExportError::InternalError)?;
+            let page = category_repo
+                .find_all_categories(pagination)
+                .map_err(|_| ExportError::InternalError)?;
+            categories.extend(page.items);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let separator = format!(" {} ", CATEGORY_PATH_SEPARATOR);
+        Ok(category_paths(&categories)
+            .into_iter()
+            .map(|(id, names)| (id, names.join(&separator)))
+            .collect())
+    }
+
+    fn feed_item(
+        &self,
+        product: Product,
+        gallery: Vec<ProductImage>,
+        paths: &HashMap<i64, String>,
+    ) -> ProductFeedItem {
+        let store_url = self.settings.store_url.trim_end_matches('/');
+
+        let (primary, additional): (Vec<ProductImage>, Vec<ProductImage>) =
+            gallery.into_iter().partition(|image| image.is_primary);
+        let image_link = primary
+            .into_iter()
+            .next()
+            .map(|image| image.url)
+            .or(product.product_image);
+
+        ProductFeedItem {
+            id: product.id,
+            sku: product.sku,
+            link: format!("{}/products/{}", store_url, product.slug),
+            slug: product.slug,
+            name: product.name,
+            description: product.description,
+            price: product.price,
+            currency: self.settings.currency.clone(),
+            stock: product.stock,
+            availability: if product.stock > 0 {
+                Availability::InStock
+            } else {
+                Availability::OutOfStock
+            },
+            category: product
+                .category
+                .and_then(|category| paths.get(&category.id).cloned()),
+            categories: product
+                .categories
+                .iter()
+                .filter_map(|category| paths.get(&category.id).cloned())
+                .collect(),
+            image_link,
+            additional_image_links: additional.into_iter().map(|image| image.url).collect(),
+        }
+    }
+}
+
+/// Writes feed items in one of the export formats.
+enum FeedWriter<'a> {
+    Csv(Box<csv::Writer<&'a mut dyn Write>>),
+    Jsonl(&'a mut dyn Write),
+    Xml(&'a mut dyn Write),
+}
+
+impl<'a> FeedWriter<'a> {
+    /// Starts the feed, writing its header.
+    fn new(
+        format: ExportFormat,
+        out: &'a mut dyn Write,
+        settings: &ExportSettings,
+    ) -> Result<Self, ExportError> {
+        match format {
+            ExportFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(out);
+                writer
+                    .write_record(CSV_HEADER)
+                    .map_err(|_| ExportError::InternalError)?;
+                Ok(FeedWriter::Csv(Box::new(writer)))
+            }
+            ExportFormat::Jsonl => Ok(FeedWriter::Jsonl(out)),
+            ExportFormat::Xml => {
+                write!(
+                    out,
+                    concat!(
+                        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                        "<rss version=\"2.0\" xmlns:g=\"http://base.google.com/ns/1.0\">\n",
+                        "<channel>\n",
+                        "<title>{}</title>\n",
+                        "<link>{}</link>\n",
+                        "<description>{}</description>\n",
+                    ),
+                    escape_xml(&settings.title),
+                    escape_xml(&settings.store_url),
+                    escape_xml(&settings.title),
+                )
+                .map_err(|_| ExportError::InternalError)?;
+                Ok(FeedWriter::Xml(out))
+            }
+        }
+    }
+
+    fn write_item(&mut self, item: &ProductFeedItem) -> Result<(), ExportError> {
+        match self {
+            FeedWriter::Csv(writer) => writer
+                .write_record([
+                    item.id.to_string(),
+                    item.sku.clone().unwrap_or_default(),
+                    item.slug.clone(),
+                    item.name.clone(),
+                    item.description.clone(),
+                    format!("{:.2}", item.price),
+                    item.currency.clone(),
+                    item.stock.to_string(),
+                    availability_name(item.availability).to_string(),
+                    item.category.clone().unwrap_or_default(),
+                    item.categories.join(CSV_LIST_SEPARATOR),
+                    item.link.clone(),
+                    item.image_link.clone().unwrap_or_default(),
+                    item.additional_image_links.join(CSV_LIST_SEPARATOR),
+                ])
+                .map_err(|_| ExportError::InternalError),
+            FeedWriter::Jsonl(out) => {
+                serde_json::to_writer(&mut *out, item).map_err(|_| ExportError::InternalError)?;
+                writeln!(out).map_err(|_| ExportError::InternalError)
+            }
+            FeedWriter::Xml(out) => write_xml_item(*out, item),
+        }
+    }
+
+    /// Ends the feed, writing its footer, and flushes it.
+    fn finish(self) -> Result<(), ExportError> {
+        match self {
+            FeedWriter::Csv(mut writer) => writer.flush(),
+            FeedWriter::Jsonl(out) => out.flush(),
+            FeedWriter::Xml(out) => write!(out, "</channel>\n</rss>\n").and_then(|_| out.flush()),
+        }
+        .map_err(|_| ExportError::InternalError)
+    }
+}
+
+/// Writes an `<item>` with the Google Merchant Center attributes.
+fn write_xml_item(out: &mut dyn Write, item: &ProductFeedItem) -> Result<(), ExportError> {
+    let id = item.sku.clone().unwrap_or_else(|| item.id.to_string());
+    let mut xml = String::from("<item>\n");
+    let mut element = |name: &str, value: &str| {
+        xml.push_str(&format!("<g:{0}>{1}</g:{0}>\n", name, escape_xml(value)));
+    };
+    element("id", &id);
+    element("title", &item.name);
+    element("description", &item.description);
+    element("link", &item.link);
+    if let Some(image_link) = &item.image_link {
+        element("image_link", image_link);
+    }
+    for image_link in &item.additional_image_links {
+        element("additional_image_link", image_link);
+    }
+    element("availability", availability_name(item.availability));
+    element("price", &format!("{:.2} {}", item.price, item.currency));
+    if let Some(category) = &item.category {
+        element("product_type", category);
+    }
+    element("condition", "new");
+    xml.push_str("</item>\n");
+
+    out.write_all(xml.as_bytes())
+        .map_err(|_| ExportError::InternalError)
+}
+
+/// Availability as Google Merchant Center spells it.
+fn availability_name(availability: Availability) -> &'static str {
+    match availability {
+        Availability::InStock => "in_stock",
+        Availability::OutOfStock => "out_of_stock",
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab and newlines are not allowed in XML.
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
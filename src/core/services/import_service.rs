@@ -0,0 +1,431 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use crate::core::{
+    models::{
+        category::{CategoryError, category_paths},
+        import::{
+            CATEGORY_PATH_SEPARATOR, IMPORT_BATCH_SIZE, ImportAction, ImportError, ImportFormat,
+            ImportOptions, ImportReport, ImportRow, ImportRowError, MAX_IMPORT_SIZE, ProductImport,
+        },
+        pagination::{MAX_PAGE_LIMIT, Pagination},
+        product::ProductError,
+        seo::{parse_slug, slugify, unique_slug},
+    },
+    ports::{
+        category_repository::CategoryRepository, product_repository::ProductRepository,
+        search_index::SearchIndex,
+    },
+};
+
+#[derive(Clone)]
+pub struct ImportService {
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) category_repo: Arc<Mutex<dyn CategoryRepository>>,
+    pub(crate) search_index: Arc<Mutex<dyn SearchIndex>>,
+}
+
+pub fn new_import_service(
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    category_repo: Arc<Mutex<dyn CategoryRepository>>,
+    search_index: Arc<Mutex<dyn SearchIndex>>,
+) -> ImportService {
+    ImportService {
+        product_repo,
+        category_repo,
+        search_index,
+    }
+}
+
+/// Lowercased names of a category and its ancestors, from the root.
+type CategoryPath = Vec<String>;
+
+/// Line of a catalog row, with the row or the reason it cannot be read.
+type RawRow = (usize, Result<ImportRow, String>);
+
+impl ImportService {
+    /// Creates and updates products from a catalog file.
+    ///
+    /// Every row is validated and checked against the catalog in a dry run
+    /// first; unless that finds no error, nothing is saved and the report lists
+    /// the rejected rows. The products are then saved in batches of
+    /// [`IMPORT_BATCH_SIZE`] rows, each in its own transaction.
+    pub fn import_products(
+        &mut self,
+        data: &[u8],
+        format: ImportFormat,
+        options: ImportOptions,
+    ) -> Result<ImportReport, ImportError> {
+        if data.len() > MAX_IMPORT_SIZE {
+            return Err(ImportError::InvalidFile);
+        }
+
+        let mut report = ImportReport {
+            dry_run: options.dry_run,
+            ..ImportReport::default()
+        };
+        let mut category_ids = self.load_category_paths()?;
+        // Names of the categories to create, by path.
+        let mut missing_categories: BTreeMap<CategoryPath, String> = BTreeMap::new();
+
+        let mut rows: Vec<(ProductImport, Option<CategoryPath>)> = Vec::new();
+        let mut skus: HashMap<String, usize> = HashMap::new();
+        let mut slugs: HashMap<String, usize> = HashMap::new();
+        for (line, row) in read_rows(data, format)? {
+            let row = row.and_then(|row| validate_row(line, row));
+            let (product, category) = match row {
+                Ok(row) => row,
+                Err(message) => {
+                    report.errors.push(ImportRowError { line, message });
+                    continue;
+                }
+            };
+
+            let duplicate = product
+                .sku
+                .as_ref()
+                .and_then(|sku| skus.insert(sku.clone(), line).map(|first| ("sku", first)))
+                .or_else(|| {
+                    product.slug.as_ref().and_then(|slug| {
+                        slugs
+                            .insert(slug.clone(), line)
+                            .map(|first| ("slug", first))
+                    })
+                });
+            if let Some((key, first)) = duplicate {
+                report.errors.push(ImportRowError {
+                    line,
+                    message: format!("duplicate {}, first used on line {}", key, first),
+                });
+                continue;
+            }
+
+            let path = category.as_ref().map(|names| category_path(names));
+            if let Some(path) = &path
+                && !category_ids.contains_key(path)
+            {
+                if !options.create_categories {
+                    report.errors.push(ImportRowError {
+                        line,
+                        message: format!(
+                            "unknown category '{}'",
+                            category
+                                .unwrap_or_default()
+                                .join(&format!(" {} ", CATEGORY_PATH_SEPARATOR))
+                        ),
+                    });
+                    continue;
+                }
+                let names = category.unwrap_or_default();
+                for depth in 1..=path.len() {
+                    if !category_ids.contains_key(&path[..depth]) {
+                        missing_categories
+                            .entry(path[..depth].to_vec())
+                            .or_insert_with(|| names[depth - 1].clone());
+                    }
+                }
+            }
+            rows.push((product, path));
+        }
+        if !report.errors.is_empty() {
+            return Ok(report);
+        }
+
+        // Rows of categories still to create are checked without one.
+        let resolve = |rows: &mut Vec<(ProductImport, Option<CategoryPath>)>,
+                       category_ids: &HashMap<CategoryPath, i64>| {
+            for (product, path) in rows.iter_mut() {
+                product.category_id = path
+                    .as_ref()
+                    .and_then(|path| category_ids.get(path).copied());
+            }
+        };
+        resolve(&mut rows, &category_ids);
+        let products: Vec<ProductImport> =
+            rows.iter().map(|(product, _)| product.clone()).collect();
+
+        let checked = self.save_batches(&products, true)?;
+        if options.dry_run || !checked.errors.is_empty() {
+            report.errors = checked.errors;
+            if options.dry_run {
+                report.created = checked.created;
+                report.updated = checked.updated;
+                report.created_categories = missing_categories.len();
+            }
+            return Ok(report);
+        }
+
+        report.created_categories = missing_categories.len();
+        self.create_categories(missing_categories, &mut category_ids)?;
+        resolve(&mut rows, &category_ids);
+        let products: Vec<ProductImport> = rows.into_iter().map(|(product, _)| product).collect();
+
+        let saved = self.save_batches(&products, false)?;
+        report.created = saved.created;
+        report.updated = saved.updated;
+        report.errors = saved.errors;
+        Ok(report)
+    }
+
+    /// Saves the rows batch by batch, reindexing the products of every batch
+    /// saved. Returns the counts of the rows saved, or that would be on a dry
+    /// run, and the rejected rows.
+    fn save_batches(
+        &mut self,
+        products: &[ProductImport],
+        dry_run: bool,
+    ) -> Result<ImportReport, ImportError> {
+        let mut report = ImportReport {
+            dry_run,
+            ..ImportReport::default()
+        };
+
+        for batch in products.chunks(IMPORT_BATCH_SIZE) {
+            let outcomes = {
+                let mut product_repo = self.product_repo.lock().unwrap();
+                product_repo
+                    .import_products(batch, dry_run)
+                    .map_err(|_| ImportError::InternalError)?
+            };
+
+            let mut saved = Vec::new();
+            let mut created = 0;
+            let mut failed = false;
+            for (product, outcome) in batch.iter().zip(outcomes) {
+                match outcome {
+                    Ok(ImportAction::Created(id)) => {
+                        created += 1;
+                        saved.push(id);
+                    }
+                    Ok(ImportAction::Updated(id)) => saved.push(id),
+                    Err(err) => {
+                        failed = true;
+                        report.errors.push(ImportRowError {
+                            line: product.line,
+                            message: import_error_message(err).to_string(),
+                        });
+                    }
+                }
+            }
+            // A batch with a rejected row was rolled back.
+            if failed {
+                continue;
+            }
+            report.created += created;
+            report.updated += saved.len() - created;
+
+            if !dry_run {
+                self.index_products(&saved);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Updates the documents of imported products. The import is committed by
+    /// then, so a failure is only logged; rebuilding the search index catches up.
+    fn index_products(&self, product_ids: &[i64]) {
+        let products = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            match product_repo.find_products_by_ids(product_ids) {
+                Ok(products) => products,
+                Err(err) => {
+                    eprintln!("Failed to load imported products to index: {:?}", err);
+                    return;
+                }
+            }
+        };
+        let mut search_index = self.search_index.lock().unwrap();
+        if let Err(err) = search_index.index_products(&products) {
+            eprintln!("Failed to index imported products: {:?}", err);
+        }
+    }
+
+    /// Maps the path of every category to its id.
+    fn load_category_paths(&self) -> Result<HashMap<CategoryPath, i64>, ImportError> {
+        let mut categories = Vec::new();
+        {
+            let mut category_repo = self.category_repo.lock().unwrap();
+            let mut cursor = None;
+            loop {
+                let pagination = Pagination::new(cursor.as_deref(), Some(MAX_PAGE_LIMIT), false)
+                    .map_err(|_| ImportError::InternalError)?;
+                let page = category_repo
+                    .find_all_categories(pagination)
+                    .map_err(map_category_error)?;
+                categories.extend(page.items);
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+        }
+
+        let mut paths: Vec<(i64, Vec<String>)> = category_paths(&categories).into_iter().collect();
+        // The oldest of the categories with the same path wins.
+        paths.sort_unstable_by_key(|(id, _)| *id);
+        let mut category_ids = HashMap::new();
+        for (id, names) in paths {
+            category_ids.entry(category_path(&names)).or_insert(id);
+        }
+        Ok(category_ids)
+    }
+
+    /// Creates the missing categories, parents first, and adds them to
+    /// `category_ids`.
+    fn create_categories(
+        &mut self,
+        missing_categories: BTreeMap<CategoryPath, String>,
+        category_ids: &mut HashMap<CategoryPath, i64>,
+    ) -> Result<(), ImportError> {
+        let mut category_repo = self.category_repo.lock().unwrap();
+        // Paths sort before the paths they are a prefix of.
+        for (path, name) in missing_categories {
+            let parent_id = category_ids.get(&path[..path.len() - 1]).copied();
+            let slug = unique_slug(slugify(&name), "category", |slug| {
+                category_repo.is_category_slug_taken(slug, None)
+            })
+            .map_err(map_category_error)?;
+            let category = category_repo
+                .create_category(name, slug, String::new(), parent_id)
+                .map_err(map_category_error)?;
+            category_ids.insert(path, category.id);
+        }
+        Ok(())
+    }
+}
+
+/// Reads the rows of a catalog file.
+fn read_rows(data: &[u8], format: ImportFormat) -> Result<Vec<RawRow>, ImportError> {
+    match format {
+        ImportFormat::Csv => {
+            let mut reader = csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(data);
+            let headers = reader
+                .headers()
+                .map_err(|_| ImportError::InvalidFile)?
+                .clone();
+
+            let mut rows = Vec::new();
+            for record in reader.records() {
+                match record {
+                    Ok(record) => {
+                        let line = record.position().map_or(0, |position| position.line());
+                        let row = record
+                            .deserialize::<ImportRow>(Some(&headers))
+                            .map_err(|err| csv_error_message(&err, &headers));
+                        rows.push((line as usize, row));
+                    }
+                    Err(err) => {
+                        let line = err.position().ok_or(ImportError::InvalidFile)?.line();
+                        rows.push((line as usize, Err(csv_error_message(&err, &headers))));
+                    }
+                }
+            }
+            Ok(rows)
+        }
+        ImportFormat::Json => {
+            let values: Vec<serde_json::Value> =
+                serde_json::from_slice(data).map_err(|_| ImportError::InvalidFile)?;
+            Ok(values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    let row =
+                        serde_json::from_value::<ImportRow>(value).map_err(|err| err.to_string());
+                    (index + 1, row)
+                })
+                .collect())
+        }
+    }
+}
+
+/// Describes why a CSV row cannot be read, naming the column at fault.
+fn csv_error_message(err: &csv::Error, headers: &csv::StringRecord) -> String {
+    match err.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => {
+            match err.field().and_then(|field| headers.get(field as usize)) {
+                Some(column) => format!("{}: {}", column, err.kind()),
+                None => err.kind().to_string(),
+            }
+        }
+        csv::ErrorKind::UnequalLengths { .. } => "wrong number of fields".to_string(),
+        _ => "unreadable row".to_string(),
+    }
+}
+
+/// Normalizes a row, returning it with the names of its category path.
+fn validate_row(
+    line: usize,
+    row: ImportRow,
+) -> Result<(ProductImport, Option<Vec<String>>), String> {
+    let name = row.name.trim().to_string();
+    if name.is_empty() {
+        return Err("name is required".to_string());
+    }
+    if !row.price.is_finite() || row.price < 0.0 {
+        return Err("invalid price".to_string());
+    }
+    if row.stock.is_some_and(|stock| stock < 0) {
+        return Err("invalid stock".to_string());
+    }
+
+    let sku = row
+        .sku
+        .map(|sku| sku.trim().to_string())
+        .filter(|sku| !sku.is_empty());
+    let slug = match row.slug.as_deref().map(str::trim) {
+        Some("") | None => None,
+        Some(slug) => Some(parse_slug(slug).map_err(|_| "invalid slug".to_string())?),
+    };
+    let category = match row.category.as_deref().map(str::trim) {
+        Some("") | None => None,
+        Some(category) => {
+            let names: Vec<String> = category
+                .split(CATEGORY_PATH_SEPARATOR)
+                .map(|name| name.trim().to_string())
+                .collect();
+            if names.iter().any(String::is_empty) {
+                return Err("invalid category".to_string());
+            }
+            Some(names)
+        }
+    };
+
+    Ok((
+        ProductImport {
+            line,
+            sku,
+            slug,
+            name,
+            description: row.description,
+            price: row.price,
+            stock: row.stock,
+            category_id: None,
+            status: row.status,
+        },
+        category,
+    ))
+}
+
+fn category_path(names: &[String]) -> CategoryPath {
+    names.iter().map(|name| name.to_lowercase()).collect()
+}
+
+fn import_error_message(err: ProductError) -> &'static str {
+    match err {
+        ProductError::SlugTaken => "slug already taken",
+        ProductError::SkuTaken => "sku already taken",
+        ProductError::InvalidCategory => "invalid category",
+        _ => "could not be saved",
+    }
+}
+
+fn map_category_error(err: CategoryError) -> ImportError {
+    match err {
+        CategoryError::InvalidData => ImportError::InvalidFile,
+        _ => ImportError::InternalError,
+    }
+}
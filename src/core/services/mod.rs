@@ -1,4 +1,14 @@
 pub mod email_service;
 pub mod user_service;
 pub mod product_service;
-pub mod category_service;
\ No newline at end of file
+pub mod category_service;
+pub mod cart_service;
+pub mod order_service;
+pub mod import_service;
+pub mod export_service;
+pub mod review_service;
+pub mod question_service;
+pub mod recommendation_service;
+pub mod view_service;
+pub mod download_service;
+pub mod subscription_service;
\ No newline at end of file
@@ -0,0 +1,333 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, Utc};
+use serde_json::{Map, Value};
+
+use crate::core::{
+    models::{
+        cart::CartItem,
+        customization::validate_customizations,
+        download::{DownloadGrant, DownloadSettings},
+        order::{Order, OrderError, OrderItem, OrderStatus, StockReservation},
+        pagination::{Page, Pagination},
+        product::ProductError,
+    },
+    ports::{
+        bundle_repository::BundleRepository, cart_repository::CartRepository,
+        order_repository::OrderRepository, product_repository::ProductRepository,
+        product_variant_repository::ProductVariantRepository,
+    },
+};
+
+#[derive(Clone)]
+pub struct OrderService {
+    pub(crate) order_repo: Arc<Mutex<dyn OrderRepository>>,
+    pub(crate) cart_repo: Arc<Mutex<dyn CartRepository>>,
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+    pub(crate) bundle_repo: Arc<Mutex<dyn BundleRepository>>,
+    pub(crate) download_settings: DownloadSettings,
+}
+
+pub fn new_order_service(
+    order_repo: Arc<Mutex<dyn OrderRepository>>,
+    cart_repo: Arc<Mutex<dyn CartRepository>>,
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+    bundle_repo: Arc<Mutex<dyn BundleRepository>>,
+    download_settings: DownloadSettings,
+) -> OrderService {
+    OrderService {
+        order_repo,
+        cart_repo,
+        product_repo,
+        variant_repo,
+        bundle_repo,
+        download_settings,
+    }
+}
+
+impl OrderService {
+    pub fn get_all(
+        &mut self,
+        user_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<Order>, OrderError> {
+        let mut order_repo = self.order_repo.lock().unwrap();
+        let orders = order_repo.find_orders_by_user_id(user_id, pagination)?;
+        Ok(orders)
+    }
+
+    pub fn get(
+        &mut self,
+        user_id: i64,
+        order_id: i64,
+    ) -> Result<(Order, Vec<OrderItem>), OrderError> {
+        let mut order_repo = self.order_repo.lock().unwrap();
+        let order = order_repo.find_order_by_id(order_id)?;
+        if order.user_id != user_id {
+            return Err(OrderError::NotFound);
+        }
+        let items = order_repo.find_order_items_by_order_id(order.id)?;
+        Ok((order, items))
+    }
+
+    /// Turns the given cart (or the active one) into a pending order.
+    ///
+    /// Products customers can no longer see are refused. Customizations are
+    /// validated again against the current product fields and priced at checkout
+    /// time; stock is reserved (on the variant when the line has one, on the
+    /// components for a bundle) in the same transaction the order is written and
+    /// the cart emptied in. Orders of digital products only do not require
+    /// shipping.
+    pub fn checkout(
+        &mut self,
+        user_id: i64,
+        cart_id: Option<i64>,
+    ) -> Result<(Order, Vec<OrderItem>), OrderError> {
+        let cart_items = {
+            let mut cart_repo = self.cart_repo.lock().unwrap();
+            let cart = match cart_id {
+                Some(cart_id) => cart_repo.find_cart_by_id(cart_id),
+                None => cart_repo.find_active_cart_by_user_id(user_id),
+            }
+            .map_err(|_| OrderError::NotFound)?;
+
+            if cart.user_id != user_id {
+                return Err(OrderError::NotFound);
+            }
+
+            cart_repo
+                .find_cart_items_by_cart_id(cart.id)
+                .map_err(|_| OrderError::DatabaseError)?
+        };
+        if cart_items.is_empty() {
+            return Err(OrderError::InvalidData);
+        }
+
+        let lines = self.price_lines(&cart_items)?;
+        let cart_item_ids = cart_items.iter().map(|item| item.id).collect();
+        self.place_order(user_id, &lines, cart_item_ids)
+    }
+
+    /// Places the pending order of a subscription run. The product is priced and
+    /// its stock reserved as at checkout, then `price` turns the unit price into
+    /// the one the subscriber pays. Like at checkout, a product customers can no
+    /// longer see fails with `ProductUnavailable`.
+    pub fn place_recurring_order(
+        &mut self,
+        user_id: i64,
+        product_id: i64,
+        variant_id: Option<i64>,
+        quantity: i32,
+        price: impl Fn(f64) -> f64,
+    ) -> Result<(Order, Vec<OrderItem>), OrderError> {
+        let now = Utc::now().naive_utc();
+        let items = [CartItem {
+            id: 0,
+            cart_id: 0,
+            product_id,
+            variant_id,
+            quantity,
+            customizations: Value::Object(Map::new()),
+            customization_price: 0.0,
+            created_at: now,
+            updated_at: now,
+        }];
+
+        let lines: Vec<(&CartItem, f64)> = self
+            .price_lines(&items)?
+            .into_iter()
+            .map(|(item, unit_price)| (item, price(unit_price)))
+            .collect();
+        self.place_order(user_id, &lines, vec![])
+    }
+
+    /// Records the payment of a pending order. Orders with something to ship move
+    /// on to processing, the others are delivered at once. Each item of a digital
+    /// product is granted downloads of its files, for a limited time.
+    pub fn mark_paid(&mut self, order_id: i64) -> Result<(Order, Vec<OrderItem>), OrderError> {
+        let (order, items) = {
+            let mut order_repo = self.order_repo.lock().unwrap();
+            let order = order_repo.find_order_by_id(order_id)?;
+            let items = order_repo.find_order_items_by_order_id(order.id)?;
+            (order, items)
+        };
+        if !matches!(order.status, OrderStatus::Pending) {
+            return Err(OrderError::InvalidStatusTransition);
+        }
+
+        let digital_product_ids: Vec<i64> = {
+            let product_ids: Vec<i64> = items.iter().map(|item| item.product_id).collect();
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo
+                .find_products_by_ids(&product_ids)
+                .map_err(|_| OrderError::DatabaseError)?
+                .into_iter()
+                .filter(|product| product.is_digital)
+                .map(|product| product.id)
+                .collect()
+        };
+
+        let now = Utc::now().naive_utc();
+        let expires_at = now + Duration::seconds(self.download_settings.link_ttl_seconds);
+        let grants = items
+            .iter()
+            .filter(|item| digital_product_ids.contains(&item.product_id))
+            .map(|item| DownloadGrant {
+                order_item_id: item.id,
+                max_downloads: self.download_settings.max_downloads,
+                download_count: 0,
+                expires_at,
+                created_at: now,
+                updated_at: now,
+            })
+            .collect();
+        let new_status = if order.requires_shipping {
+            OrderStatus::Processing
+        } else {
+            OrderStatus::Delivered
+        };
+
+        let mut order_repo = self.order_repo.lock().unwrap();
+        let order = order_repo.mark_order_paid(order.id, now, new_status, grants)?;
+        Ok((order, items))
+    }
+
+    // Private Methods
+
+    /// Writes a pending order of the priced lines, reserving their stock and
+    /// removing the cart items they come from.
+    fn place_order(
+        &self,
+        user_id: i64,
+        lines: &[(&CartItem, f64)],
+        cart_item_ids: Vec<i64>,
+    ) -> Result<(Order, Vec<OrderItem>), OrderError> {
+        let requires_shipping = self.requires_shipping(lines)?;
+        let reservations = self.stock_reservations(lines)?;
+
+        let now = Utc::now().naive_utc();
+        let order = Order {
+            id: 0,
+            user_id,
+            total_amount: lines
+                .iter()
+                .map(|(item, unit_price)| unit_price * item.quantity as f64)
+                .sum(),
+            status: OrderStatus::Pending,
+            requires_shipping,
+            paid_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let order_items = lines
+            .iter()
+            .map(|(item, unit_price)| OrderItem {
+                id: 0,
+                order_id: 0,
+                product_id: item.product_id,
+                variant_id: item.variant_id,
+                quantity: item.quantity,
+                price_at_time_of_order: *unit_price,
+                customizations: item.customizations.clone(),
+                created_at: now,
+                updated_at: now,
+            })
+            .collect();
+
+        let mut order_repo = self.order_repo.lock().unwrap();
+        order_repo.place_order(order, order_items, reservations, cart_item_ids)
+    }
+
+    /// Prices each item at the current prices. Products in the trash are not
+    /// found, and those not published fail with `ProductUnavailable`.
+    fn price_lines<'a>(
+        &self,
+        cart_items: &'a [CartItem],
+    ) -> Result<Vec<(&'a CartItem, f64)>, OrderError> {
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+        let now = Utc::now().naive_utc();
+        let mut lines = Vec::with_capacity(cart_items.len());
+
+        for item in cart_items {
+            let product =
+                product_repo
+                    .find_product_by_id(item.product_id)
+                    .map_err(|err| match err {
+                        ProductError::NotFound => OrderError::InvalidData,
+                        _ => OrderError::DatabaseError,
+                    })?;
+            if !product.is_published(now) {
+                return Err(OrderError::ProductUnavailable);
+            }
+
+            let (_, customization_price) =
+                validate_customizations(&product.customization_fields, &item.customizations)
+                    .map_err(|_| OrderError::InvalidData)?;
+
+            let unit_price = match item.variant_id {
+                Some(variant_id) => {
+                    let variant =
+                        variant_repo
+                            .find_variant_by_id(variant_id)
+                            .map_err(|err| match err {
+                                ProductError::NotFound => OrderError::InvalidData,
+                                _ => OrderError::DatabaseError,
+                            })?;
+                    variant.effective_price(&product)
+                }
+                None => product.price,
+            };
+
+            lines.push((item, unit_price + customization_price));
+        }
+
+        Ok(lines)
+    }
+
+    /// Whether any of the products ordered is physical. Products that no longer
+    /// exist are found by [`Self::price_lines`] already.
+    fn requires_shipping(&self, lines: &[(&CartItem, f64)]) -> Result<bool, OrderError> {
+        let product_ids: Vec<i64> = lines.iter().map(|(item, _)| item.product_id).collect();
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let products = product_repo
+            .find_products_by_ids(&product_ids)
+            .map_err(|_| OrderError::DatabaseError)?;
+        Ok(products.iter().any(|product| !product.is_digital))
+    }
+
+    /// Lists the stock each line takes. A bundle takes its quantity of every
+    /// component for each unit ordered.
+    fn stock_reservations(
+        &self,
+        lines: &[(&CartItem, f64)],
+    ) -> Result<Vec<StockReservation>, OrderError> {
+        let mut bundle_repo = self.bundle_repo.lock().unwrap();
+        let mut reservations = Vec::with_capacity(lines.len());
+
+        for (item, _) in lines {
+            if let Some(variant_id) = item.variant_id {
+                reservations.push(StockReservation::Variant(variant_id, item.quantity));
+                continue;
+            }
+            match bundle_repo.find_bundle_by_product_id(item.product_id) {
+                Ok(bundle) => {
+                    for component in bundle.items {
+                        reservations.push(StockReservation::Product(
+                            component.component_id,
+                            component.quantity * item.quantity,
+                        ));
+                    }
+                }
+                Err(ProductError::NotFound) => {
+                    reservations.push(StockReservation::Product(item.product_id, item.quantity));
+                }
+                Err(_) => return Err(OrderError::DatabaseError),
+            }
+        }
+
+        Ok(reservations)
+    }
+}
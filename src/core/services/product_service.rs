@@ -1,32 +1,375 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{Duration, NaiveDateTime, Utc};
 
 use crate::core::{
-    models::product::{Product, ProductError},
-    ports::product_repository::ProductRepository,
+    models::{
+        attribute::{Attribute, AttributeType, AttributeValue, ProductAttribute},
+        bundle::{Bundle, BundleItem},
+        customization::CustomizationField,
+        download::{DIGITAL_FILES_PREFIX, MAX_FILE_SIZE, ProductFile, sanitize_file_name},
+        pagination::{MAX_PAGE_LIMIT, Page, Pagination, SearchCursor, SearchPagination},
+        product::{
+            ImageRendition, ImageSize, MAX_IMAGE_DIMENSION, MAX_IMAGE_SIZE, Product, ProductError,
+            ProductFilter, ProductImage, ProductListing, ProductSearch, ProductSearchResult,
+            ProductSort, ProductStatus, ProductVariant, ProductVisibility, SearchSuggestion,
+            Variation, VariationOption, image_extension,
+        },
+        product_category::ProductCategory,
+        question::TOP_QUESTIONS_LIMIT,
+        review::MAX_RATING,
+        seo::{SeoMetadata, SlugLookup, parse_slug, slugify, unique_slug},
+    },
+    ports::{
+        attribute_repository::AttributeRepository,
+        blob_storage::{BlobStorage, BlobStorageError},
+        bundle_repository::BundleRepository,
+        image_processor::{ImageProcessor, ImageProcessorError},
+        product_file_repository::ProductFileRepository,
+        product_image_repository::ProductImageRepository,
+        product_repository::{ProductCategoryRepository, ProductRepository},
+        product_variant_repository::ProductVariantRepository,
+        question_repository::QuestionRepository,
+        recommendation_repository::RecommendationRepository,
+        search_index::{SearchHit, SearchIndex, SearchIndexError},
+        variation_repository::VariationRepository,
+    },
 };
+
+use super::recommendation_service::load_published_products;
+
+/// Full-text matches below which a search falls back to similar names.
+pub const MIN_SEARCH_RESULTS: usize = 5;
+const MAX_SIMILAR_RESULTS: usize = 20;
+/// Deepest a search can be paged into.
+pub const MAX_SEARCH_OFFSET: usize = 10_000;
+const DEFAULT_SUGGESTIONS: i64 = 10;
+const MAX_SUGGESTIONS: i64 = 20;
+
 #[derive(Clone)]
 pub struct ProductService {
     pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) variation_repo: Arc<Mutex<dyn VariationRepository>>,
+    pub(crate) variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+    pub(crate) product_category_repo: Arc<Mutex<dyn ProductCategoryRepository>>,
+    pub(crate) image_repo: Arc<Mutex<dyn ProductImageRepository>>,
+    pub(crate) blob_storage: Arc<Mutex<dyn BlobStorage>>,
+    pub(crate) image_processor: Arc<Mutex<dyn ImageProcessor>>,
+    pub(crate) search_index: Arc<Mutex<dyn SearchIndex>>,
+    pub(crate) question_repo: Arc<Mutex<dyn QuestionRepository>>,
+    pub(crate) recommendation_repo: Arc<Mutex<dyn RecommendationRepository>>,
+    pub(crate) attribute_repo: Arc<Mutex<dyn AttributeRepository>>,
+    pub(crate) bundle_repo: Arc<Mutex<dyn BundleRepository>>,
+    pub(crate) file_repo: Arc<Mutex<dyn ProductFileRepository>>,
 }
 
-pub fn new_product_service(product_repo: Arc<Mutex<dyn ProductRepository>>) -> ProductService {
-    ProductService { product_repo }
+#[allow(clippy::too_many_arguments)]
+pub fn new_product_service(
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    variation_repo: Arc<Mutex<dyn VariationRepository>>,
+    variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+    product_category_repo: Arc<Mutex<dyn ProductCategoryRepository>>,
+    image_repo: Arc<Mutex<dyn ProductImageRepository>>,
+    blob_storage: Arc<Mutex<dyn BlobStorage>>,
+    image_processor: Arc<Mutex<dyn ImageProcessor>>,
+    search_index: Arc<Mutex<dyn SearchIndex>>,
+    question_repo: Arc<Mutex<dyn QuestionRepository>>,
+    recommendation_repo: Arc<Mutex<dyn RecommendationRepository>>,
+    attribute_repo: Arc<Mutex<dyn AttributeRepository>>,
+    bundle_repo: Arc<Mutex<dyn BundleRepository>>,
+    file_repo: Arc<Mutex<dyn ProductFileRepository>>,
+) -> ProductService {
+    ProductService {
+        product_repo,
+        variation_repo,
+        variant_repo,
+        product_category_repo,
+        image_repo,
+        blob_storage,
+        image_processor,
+        search_index,
+        question_repo,
+        recommendation_repo,
+        attribute_repo,
+        bundle_repo,
+        file_repo,
+    }
 }
 
 impl ProductService {
-    pub fn get(&mut self, product_id: i64) -> Result<Product, ProductError> {
+    /// Loads a product with its specifications, bundle components, variants,
+    /// gallery, downloadable files, most answered questions and related
+    /// products. Products customers cannot see are not found unless every
+    /// product is visible.
+    pub fn get(
+        &mut self,
+        product_id: i64,
+        visibility: ProductVisibility,
+    ) -> Result<Product, ProductError> {
+        let mut product = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.find_product_by_id(product_id)?
+        };
+        if visibility == ProductVisibility::Published
+            && !product.is_published(Utc::now().naive_utc())
+        {
+            return Err(ProductError::NotFound);
+        }
+        self.load_variant_matrix(&mut product)?;
+
+        {
+            let mut attribute_repo = self.attribute_repo.lock().unwrap();
+            product.attributes = attribute_repo.find_product_attributes(product.id)?;
+        }
+
+        {
+            let mut bundle_repo = self.bundle_repo.lock().unwrap();
+            product.bundle = match bundle_repo.find_bundle_by_product_id(product.id) {
+                Ok(bundle) => Some(bundle),
+                Err(ProductError::NotFound) => None,
+                Err(err) => return Err(err),
+            };
+        }
+
+        {
+            let mut image_repo = self.image_repo.lock().unwrap();
+            product.images = image_repo.find_images_by_product_id(product.id)?;
+        }
+
+        if product.is_digital {
+            let mut file_repo = self.file_repo.lock().unwrap();
+            product.files = file_repo.find_files_by_product_id(product.id)?;
+        }
+
+        {
+            let mut question_repo = self.question_repo.lock().unwrap();
+            product.questions = question_repo
+                .find_top_questions(product.id, TOP_QUESTIONS_LIMIT)
+                .map_err(|_| ProductError::InternalError)?;
+        }
+
+        let related_ids: Vec<i64> = {
+            let mut recommendation_repo = self.recommendation_repo.lock().unwrap();
+            recommendation_repo
+                .find_recommendations(&[product.id])
+                .map_err(|_| ProductError::InternalError)?
+                .iter()
+                .map(|recommendation| recommendation.related_product_id)
+                .collect()
+        };
         let mut product_repo = self.product_repo.lock().unwrap();
-        let product = product_repo.find_product_by_id(product_id)?;
+        product.related_products = load_published_products(&mut *product_repo, &related_ids)?;
         Ok(product)
     }
 
-    pub fn search(&mut self, search: String) -> Result<Vec<Product>, ProductError> {
+    /// Looks a product up by slug. Former slugs resolve to
+    /// [`SlugLookup::Moved`] with the slug the product is now found under.
+    pub fn get_by_slug(
+        &mut self,
+        slug: &str,
+        visibility: ProductVisibility,
+    ) -> Result<SlugLookup<Product>, ProductError> {
+        let lookup = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.resolve_product_slug(slug)?
+        };
+        match lookup {
+            SlugLookup::Found(product_id) => {
+                Ok(SlugLookup::Found(self.get(product_id, visibility)?))
+            }
+            SlugLookup::Moved(slug) => Ok(SlugLookup::Moved(slug)),
+        }
+    }
+
+    /// Searches published products by relevance. The query accepts web search
+    /// syntax: quoted phrases, `or` and `-` to exclude a term.
+    ///
+    /// Results come a page at a time, up to [`MAX_SEARCH_OFFSET`] matches deep.
+    /// When a single page holds fewer than
+    /// [`MIN_SEARCH_RESULTS`] products, products with a similar name are
+    /// appended and the closest known name is suggested.
+    pub fn search(
+        &mut self,
+        search: String,
+        pagination: SearchPagination,
+    ) -> Result<ProductSearch, ProductError> {
+        let search = search.trim().to_string();
+        if search.is_empty() {
+            return Err(ProductError::InvalidData);
+        }
+        let offset = Self::search_offset(&pagination)?;
+
+        let mut hits = {
+            let mut search_index = self.search_index.lock().unwrap();
+            search_index
+                .search(&search, offset, pagination.fetch_limit() as usize)
+                .map_err(Self::map_search_index_error)?
+        };
+        let next_cursor = if hits.len() as i64 > pagination.limit {
+            hits.truncate(pagination.limit as usize);
+            Some(
+                SearchCursor {
+                    offset: offset + hits.len(),
+                }
+                .encode(),
+            )
+        } else {
+            None
+        };
         let mut product_repo: std::sync::MutexGuard<'_, dyn ProductRepository> =
             self.product_repo.lock().unwrap();
-        let product = product_repo.find_products_by_name(search)?;
-        Ok(product)
+        let mut results = Self::to_search_results(&mut *product_repo, hits)?;
+        if results.len() >= MIN_SEARCH_RESULTS || offset > 0 || next_cursor.is_some() {
+            return Ok(ProductSearch {
+                results,
+                did_you_mean: None,
+                next_cursor,
+            });
+        }
+
+        let hits = {
+            let mut search_index = self.search_index.lock().unwrap();
+            search_index
+                .find_similar(&search, MAX_SIMILAR_RESULTS)
+                .map_err(Self::map_search_index_error)?
+        };
+        for similar in Self::to_search_results(&mut *product_repo, hits)? {
+            if !results
+                .iter()
+                .any(|result| result.product.id == similar.product.id)
+            {
+                results.push(similar);
+            }
+        }
+
+        let did_you_mean = product_repo
+            .suggest_names(search.clone(), 1)?
+            .into_iter()
+            .map(|suggestion| suggestion.name)
+            .find(|name| !name.eq_ignore_ascii_case(&search));
+
+        Ok(ProductSearch {
+            results,
+            did_you_mean,
+            next_cursor: None,
+        })
+    }
+
+    /// Replaces the search index with the documents of every product, for
+    /// instance after switching search backends. Returns the number of products
+    /// indexed.
+    pub fn rebuild_search_index(&mut self) -> Result<usize, ProductError> {
+        let mut products = Vec::new();
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            let mut cursor = None;
+            loop {
+                let pagination = Pagination::new(cursor.as_deref(), Some(MAX_PAGE_LIMIT), false)
+                    .map_err(|_| ProductError::InternalError)?;
+                let page = product_repo.find_all_products(ProductVisibility::All, pagination)?;
+                products.extend(page.items);
+                cursor = page.next_cursor;
+                if cursor.is_none() {
+                    break;
+                }
+            }
+        }
+
+        let mut search_index = self.search_index.lock().unwrap();
+        search_index
+            .rebuild(&products)
+            .map_err(Self::map_search_index_error)?;
+        Ok(products.len())
     }
 
+    /// Completes a partially typed query with product and category names.
+    pub fn suggest(
+        &mut self,
+        query: String,
+        limit: Option<i64>,
+    ) -> Result<Vec<SearchSuggestion>, ProductError> {
+        let query = query.trim().to_string();
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+        let limit = limit
+            .unwrap_or(DEFAULT_SUGGESTIONS)
+            .clamp(1, MAX_SUGGESTIONS);
+
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let suggestions = product_repo.suggest_names(query, limit)?;
+        Ok(suggestions)
+    }
+
+    pub fn get_all(
+        &mut self,
+        visibility: ProductVisibility,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError> {
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let products = product_repo.find_all_products(visibility, pagination)?;
+        Ok(products)
+    }
+
+    /// Lists a page of the products matching `filter` in `sort` order. The facet
+    /// counts of the whole matching set come with the first page only.
+    pub fn query(
+        &mut self,
+        filter: ProductFilter,
+        visibility: ProductVisibility,
+        sort: ProductSort,
+        pagination: Pagination,
+    ) -> Result<ProductListing, ProductError> {
+        let invalid_price = |price: Option<f64>| price.is_some_and(|price| price < 0.0);
+        if invalid_price(filter.min_price)
+            || invalid_price(filter.max_price)
+            || filter
+                .min_price
+                .zip(filter.max_price)
+                .is_some_and(|(min_price, max_price)| min_price > max_price)
+            || filter
+                .min_rating
+                .is_some_and(|min_rating| !(0.0..=f64::from(MAX_RATING)).contains(&min_rating))
+            || filter.attributes.iter().any(|attribute| {
+                attribute
+                    .min
+                    .zip(attribute.max)
+                    .is_some_and(|(min, max)| min > max)
+            })
+        {
+            return Err(ProductError::InvalidData);
+        }
+
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let facets = if pagination.after.is_none() {
+            Some(product_repo.count_product_facets(&filter, visibility)?)
+        } else {
+            None
+        };
+        let products = product_repo.find_products(&filter, visibility, sort, pagination)?;
+        Ok(ProductListing { products, facets })
+    }
+
+    /// Lists the published products assigned to a category, whether as primary
+    /// or not.
+    pub fn get_by_category(
+        &mut self,
+        category_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<Product>, ProductError> {
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let products = product_repo.find_products_by_category_id(
+            category_id,
+            ProductVisibility::Published,
+            pagination,
+        )?;
+        Ok(products)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         &mut self,
         name: String,
@@ -34,14 +377,28 @@ impl ProductService {
         price: f64,
         stock: i32,
         product_image: Option<String>,
-        category_id: Option<i64>
+        category_id: Option<i64>,
+        status: ProductStatus,
     ) -> Result<Product, ProductError> {
         let mut product_repo = self.product_repo.lock().unwrap();
-        let product =
-            product_repo.create_product(name, description, price, stock, product_image, category_id)?;
+        let slug = unique_slug(slugify(&name), "product", |slug| {
+            product_repo.is_product_slug_taken(slug, None)
+        })?;
+        let product = product_repo.create_product(
+            name,
+            slug,
+            description,
+            price,
+            stock,
+            product_image,
+            category_id,
+            status,
+        )?;
+        self.index_product(&product);
         Ok(product)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
         id: i64,
@@ -50,11 +407,11 @@ impl ProductService {
         new_price: f64,
         new_stock: i32,
         new_product_image: Option<String>,
-        new_category_id: Option<i64>
+        new_category_id: Option<i64>,
     ) -> Result<Product, ProductError> {
         let mut product_repo: std::sync::MutexGuard<'_, dyn ProductRepository> =
             self.product_repo.lock().unwrap();
-            
+
         let product = product_repo.update_product(
             id,
             new_name,
@@ -62,23 +419,1186 @@ impl ProductService {
             new_price,
             new_stock,
             new_product_image,
-            new_category_id
+            new_category_id,
         )?;
-        
+
+        self.index_product(&product);
+
         Ok(product)
     }
 
-    pub fn delete(
+    /// Sets the status of a product along with its publication schedule. Only
+    /// products that are not published yet can be scheduled for publication,
+    /// and a schedule must publish before it archives.
+    pub fn update_status(
         &mut self,
         id: i64,
+        new_status: ProductStatus,
+        new_publish_at: Option<NaiveDateTime>,
+        new_unpublish_at: Option<NaiveDateTime>,
+    ) -> Result<Product, ProductError> {
+        if new_status == ProductStatus::Published && new_publish_at.is_some() {
+            return Err(ProductError::InvalidData);
+        }
+        if new_publish_at
+            .zip(new_unpublish_at)
+            .is_some_and(|(publish_at, unpublish_at)| publish_at >= unpublish_at)
+        {
+            return Err(ProductError::InvalidData);
+        }
+
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let product =
+            product_repo.update_product_status(id, new_status, new_publish_at, new_unpublish_at)?;
+        Ok(product)
+    }
+
+    /// Publishes and archives the products whose scheduled time has come.
+    /// Returns the number of products changed.
+    pub fn apply_schedules(&mut self) -> Result<usize, ProductError> {
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let changed = product_repo.apply_product_schedules(Utc::now().naive_utc())?;
+        Ok(changed)
+    }
+
+    /// Replaces the SEO metadata of a product, and its slug when `new_slug` is
+    /// given. The previous slug keeps redirecting to the product.
+    pub fn update_seo(
+        &mut self,
+        id: i64,
+        new_slug: Option<String>,
+        new_seo: SeoMetadata,
+    ) -> Result<Product, ProductError> {
+        let new_seo = new_seo.normalize().map_err(|_| ProductError::InvalidData)?;
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let product = product_repo.find_product_by_id(id)?;
+
+        let new_slug = match new_slug {
+            Some(new_slug) => {
+                let new_slug = parse_slug(&new_slug).map_err(|_| ProductError::InvalidData)?;
+                if product_repo.is_product_slug_taken(&new_slug, Some(product.id))? {
+                    return Err(ProductError::SlugTaken);
+                }
+                new_slug
+            }
+            None => product.slug,
+        };
+
+        let product = product_repo.update_product_seo(product.id, new_slug, new_seo)?;
+        Ok(product)
+    }
+
+    /// Moves a product to the trash. Its gallery is kept until it is purged.
+    pub fn delete(&mut self, id: i64) -> Result<(), ProductError> {
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.delete_product(id)?;
+        }
+
+        let mut search_index = self.search_index.lock().unwrap();
+        if let Err(err) = search_index.remove_product(id) {
+            eprintln!("Failed to remove product {} from the index: {:?}", id, err);
+        }
+        Ok(())
+    }
+
+    /// Takes a product back out of the trash.
+    pub fn restore(&mut self, id: i64) -> Result<Product, ProductError> {
+        let product = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.restore_product(id)?
+        };
+        self.index_product(&product);
+        Ok(product)
+    }
+
+    pub fn get_trash(&mut self, pagination: Pagination) -> Result<Page<Product>, ProductError> {
+        let mut product_repo = self.product_repo.lock().unwrap();
+        product_repo.find_deleted_products(pagination)
+    }
+
+    /// Deletes for good the products that have been in the trash for more than
+    /// `retention_days`, along with the stored files of their gallery and
+    /// downloads. Products that appear in an order stay in the trash. Returns
+    /// the number of products purged.
+    pub fn purge_trash(&mut self, retention_days: i64) -> Result<usize, ProductError> {
+        let deleted_before = Utc::now().naive_utc() - Duration::days(retention_days);
+        let product_ids = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.find_purgeable_product_ids(deleted_before)?
+        };
+
+        let mut purged = 0;
+        for product_id in product_ids {
+            let images = {
+                let mut image_repo = self.image_repo.lock().unwrap();
+                image_repo.find_images_by_product_id(product_id)?
+            };
+            let files = {
+                let mut file_repo = self.file_repo.lock().unwrap();
+                file_repo.find_files_by_product_id(product_id)?
+            };
+            {
+                let mut product_repo = self.product_repo.lock().unwrap();
+                match product_repo.purge_product(product_id) {
+                    Ok(()) => purged += 1,
+                    // Ordered, subscribed to or restored in the meantime.
+                    Err(ProductError::NotFound) => continue,
+                    Err(err) => return Err(err),
+                }
+            }
+
+            let mut blob_storage = self.blob_storage.lock().unwrap();
+            let file_keys = files.into_iter().map(|file| file.storage_key);
+            for key in images
+                .iter()
+                .flat_map(ProductImage::storage_keys)
+                .chain(file_keys)
+            {
+                // The rows are gone already; a file left behind only wastes space.
+                let _ = blob_storage.delete(&key);
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Stores an uploaded image and appends it to the product gallery.
+    ///
+    /// The format is detected from the content rather than trusted from the
+    /// request. Besides the original, a thumbnail, medium and large rendition are
+    /// stored, each also as WebP; sizes the image already fits in reuse the
+    /// full-size files instead of storing copies.
+    pub fn upload_image(
+        &mut self,
+        product_id: i64,
+        data: Vec<u8>,
+        alt_text: Option<String>,
+    ) -> Result<ProductImage, ProductError> {
+        if data.is_empty() || data.len() > MAX_IMAGE_SIZE {
+            return Err(ProductError::InvalidImage);
+        }
+        let alt_text = Self::normalize_alt_text(alt_text);
+
+        let (info, rendered) = {
+            let image_processor = self.image_processor.lock().unwrap();
+            let info = image_processor
+                .inspect(&data)
+                .map_err(Self::map_processor_error)?;
+            if info.width > MAX_IMAGE_DIMENSION || info.height > MAX_IMAGE_DIMENSION {
+                return Err(ProductError::InvalidImage);
+            }
+            let rendered = image_processor
+                .render(&data, &ImageSize::ALL)
+                .map_err(Self::map_processor_error)?;
+            (info, rendered)
+        };
+        let extension = image_extension(&info.content_type).ok_or(ProductError::InvalidImage)?;
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.find_product_by_id(product_id)?;
+        }
+
+        let stem = format!(
+            "products/{}/{}-{:016x}",
+            product_id,
+            Utc::now().timestamp_millis(),
+            rand::random::<u64>()
+        );
+        let storage_key = format!("{}.{}", stem, extension);
+
+        let mut blob_storage = self.blob_storage.lock().unwrap();
+        let mut stored: Vec<(String, String)> = Vec::new();
+        let mut store = |key: String, content_type: &str, data: Vec<u8>| {
+            if let Some((_, url)) = stored.iter().find(|(stored_key, _)| *stored_key == key) {
+                return Ok((key, url.clone()));
+            }
+            let url = blob_storage
+                .put(&key, content_type, data)
+                .map_err(Self::map_storage_error)?;
+            stored.push((key.clone(), url.clone()));
+            Ok::<_, ProductError>((key, url))
+        };
+
+        let result = (|| {
+            let (_, url) = store(storage_key.clone(), &info.content_type, data)?;
+
+            let mut renditions = Vec::with_capacity(rendered.len());
+            for rendition in rendered {
+                let full_size = rendition.width == info.width && rendition.height == info.height;
+                let name = if full_size {
+                    ImageSize::Original.name()
+                } else {
+                    rendition.size.name()
+                };
+
+                let key = if full_size {
+                    storage_key.clone()
+                } else {
+                    format!("{}_{}.{}", stem, name, extension)
+                };
+                let (storage_key, url) = store(key, &info.content_type, rendition.data)?;
+
+                let webp_key = if full_size && extension == "webp" {
+                    storage_key.clone()
+                } else {
+                    format!("{}_{}.webp", stem, name)
+                };
+                let (webp_storage_key, webp_url) =
+                    store(webp_key, "image/webp", rendition.webp_data)?;
+
+                renditions.push(ImageRendition {
+                    size: rendition.size,
+                    width: rendition.width,
+                    height: rendition.height,
+                    storage_key,
+                    url,
+                    webp_storage_key,
+                    webp_url,
+                });
+            }
+
+            let now = Utc::now().naive_utc();
+            let mut image_repo = self.image_repo.lock().unwrap();
+            image_repo.create_image(ProductImage {
+                id: 0,
+                product_id,
+                storage_key: storage_key.clone(),
+                url,
+                content_type: info.content_type.clone(),
+                width: i32::try_from(info.width).ok(),
+                height: i32::try_from(info.height).ok(),
+                alt_text,
+                position: 0,
+                is_primary: false,
+                renditions,
+                created_at: now,
+                updated_at: now,
+            })
+        })();
+
+        if result.is_err() {
+            for (key, _) in &stored {
+                let _ = blob_storage.delete(key);
+            }
+        }
+        result
+    }
+
+    pub fn update_image(
+        &mut self,
+        image_id: i64,
+        new_alt_text: Option<String>,
+    ) -> Result<ProductImage, ProductError> {
+        let mut image_repo = self.image_repo.lock().unwrap();
+        let image =
+            image_repo.update_image_alt_text(image_id, Self::normalize_alt_text(new_alt_text))?;
+        Ok(image)
+    }
+
+    pub fn reorder_images(
+        &mut self,
+        product_id: i64,
+        image_ids: Vec<i64>,
+    ) -> Result<Vec<ProductImage>, ProductError> {
+        let mut image_repo = self.image_repo.lock().unwrap();
+        let images = image_repo.reorder_images(product_id, image_ids)?;
+        Ok(images)
+    }
+
+    pub fn set_primary_image(&mut self, image_id: i64) -> Result<ProductImage, ProductError> {
+        let mut image_repo = self.image_repo.lock().unwrap();
+        let image = image_repo.set_primary_image(image_id)?;
+        Ok(image)
+    }
+
+    /// Removes an image from the gallery and deletes its stored file.
+    pub fn delete_image(&mut self, image_id: i64) -> Result<(), ProductError> {
+        let image = {
+            let mut image_repo = self.image_repo.lock().unwrap();
+            image_repo.delete_image(image_id)?
+        };
+
+        let mut blob_storage = self.blob_storage.lock().unwrap();
+        for key in image.storage_keys() {
+            blob_storage.delete(&key).map_err(Self::map_storage_error)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a stored file back, for storage backends that are not publicly reachable.
+    /// The files of digital products are only served through signed download links.
+    pub fn get_media(&mut self, storage_key: &str) -> Result<Vec<u8>, ProductError> {
+        if storage_key.starts_with(DIGITAL_FILES_PREFIX) {
+            return Err(ProductError::NotFound);
+        }
+        let mut blob_storage = self.blob_storage.lock().unwrap();
+        blob_storage
+            .get(storage_key)
+            .map_err(Self::map_storage_error)
+    }
+
+    /// Marks a product as delivered by download rather than shipped.
+    pub fn set_digital(
+        &mut self,
+        product_id: i64,
+        is_digital: bool,
+    ) -> Result<Product, ProductError> {
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.update_product_digital(product_id, is_digital)?;
+        }
+
+        self.get(product_id, ProductVisibility::All)
+    }
+
+    pub fn get_files(&mut self, product_id: i64) -> Result<Vec<ProductFile>, ProductError> {
+        let mut file_repo = self.file_repo.lock().unwrap();
+        file_repo.find_files_by_product_id(product_id)
+    }
+
+    /// Stores a file customers of a digital product can download once they have
+    /// paid. The storage key is unguessable and never disclosed, since public
+    /// storage backends serve any key they hold.
+    pub fn upload_file(
+        &mut self,
+        product_id: i64,
+        file_name: &str,
+        content_type: Option<String>,
+        data: Vec<u8>,
+    ) -> Result<ProductFile, ProductError> {
+        if data.is_empty() || data.len() > MAX_FILE_SIZE {
+            return Err(ProductError::InvalidData);
+        }
+        let file_name = sanitize_file_name(file_name).ok_or(ProductError::InvalidData)?;
+        let content_type = content_type
+            .filter(|content_type| !content_type.trim().is_empty())
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            if !product_repo.find_product_by_id(product_id)?.is_digital {
+                return Err(ProductError::InvalidData);
+            }
+        }
+
+        let storage_key = format!(
+            "{}{}/{}-{:032x}",
+            DIGITAL_FILES_PREFIX,
+            product_id,
+            Utc::now().timestamp_millis(),
+            rand::random::<u128>()
+        );
+        let size_bytes = data.len() as i64;
+
+        let mut blob_storage = self.blob_storage.lock().unwrap();
+        blob_storage
+            .put(&storage_key, &content_type, data)
+            .map_err(Self::map_storage_error)?;
+
+        let now = Utc::now().naive_utc();
+        let mut file_repo = self.file_repo.lock().unwrap();
+        let result = file_repo.create_file(ProductFile {
+            id: 0,
+            product_id,
+            storage_key: storage_key.clone(),
+            file_name,
+            content_type,
+            size_bytes,
+            created_at: now,
+            updated_at: now,
+        });
+        if result.is_err() {
+            let _ = blob_storage.delete(&storage_key);
+        }
+        result
+    }
+
+    /// Removes a file from a digital product and deletes it from the storage.
+    /// Customers who paid for the product can no longer download it.
+    pub fn delete_file(&mut self, file_id: i64) -> Result<(), ProductError> {
+        let file = {
+            let mut file_repo = self.file_repo.lock().unwrap();
+            file_repo.delete_file(file_id)?
+        };
+
+        let mut blob_storage = self.blob_storage.lock().unwrap();
+        blob_storage
+            .delete(&file.storage_key)
+            .map_err(Self::map_storage_error)
+    }
+
+    pub fn set_customization_fields(
+        &mut self,
+        product_id: i64,
+        fields: Vec<CustomizationField>,
+    ) -> Result<Vec<CustomizationField>, ProductError> {
+        for (index, field) in fields.iter().enumerate() {
+            let duplicated = fields[..index].iter().any(|other| other.name == field.name);
+            if field.name.trim().is_empty()
+                || duplicated
+                || field.extra_price < 0.0
+                || field.max_length.is_some_and(|max_length| max_length <= 0)
+            {
+                return Err(ProductError::InvalidData);
+            }
+        }
+
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let fields = product_repo.set_customization_fields(product_id, fields)?;
+        Ok(fields)
+    }
+
+    pub fn get_variations(
+        &mut self,
+        category_id: i64,
+    ) -> Result<Vec<(Variation, Vec<VariationOption>)>, ProductError> {
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        let variations = variation_repo.find_variations_by_category_id(category_id)?;
+
+        let mut result = Vec::with_capacity(variations.len());
+        for variation in variations {
+            let options = variation_repo.find_variation_options_by_variation_id(variation.id)?;
+            result.push((variation, options));
+        }
+        Ok(result)
+    }
+
+    pub fn create_variation(
+        &mut self,
+        category_id: i64,
+        name: String,
+    ) -> Result<Variation, ProductError> {
+        let name = Self::validate_label(name)?;
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        let variation = variation_repo.create_variation(category_id, name)?;
+        Ok(variation)
+    }
+
+    pub fn update_variation(
+        &mut self,
+        variation_id: i64,
+        new_name: String,
+    ) -> Result<Variation, ProductError> {
+        let new_name = Self::validate_label(new_name)?;
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        let variation = variation_repo.update_variation(variation_id, new_name)?;
+        Ok(variation)
+    }
+
+    pub fn delete_variation(&mut self, variation_id: i64) -> Result<(), ProductError> {
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        variation_repo.delete_variation(variation_id)?;
+        Ok(())
+    }
+
+    pub fn create_variation_option(
+        &mut self,
+        variation_id: i64,
+        value: String,
+    ) -> Result<VariationOption, ProductError> {
+        let value = Self::validate_label(value)?;
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        let option = variation_repo.create_variation_option(variation_id, value)?;
+        Ok(option)
+    }
+
+    pub fn update_variation_option(
+        &mut self,
+        variation_option_id: i64,
+        new_value: String,
+    ) -> Result<VariationOption, ProductError> {
+        let new_value = Self::validate_label(new_value)?;
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        let option = variation_repo.update_variation_option(variation_option_id, new_value)?;
+        Ok(option)
+    }
+
+    pub fn delete_variation_option(
+        &mut self,
+        variation_option_id: i64,
     ) -> Result<(), ProductError> {
-        let mut product_repo: std::sync::MutexGuard<'_, dyn ProductRepository> =
-            self.product_repo.lock().unwrap();
-        product_repo.delete_product(id)?;
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        variation_repo.delete_variation_option(variation_option_id)?;
+        Ok(())
+    }
+
+    /// Lists the attributes products of a category carry, those inherited from
+    /// its ancestors included.
+    pub fn get_attributes(&mut self, category_id: i64) -> Result<Vec<Attribute>, ProductError> {
+        let mut attribute_repo = self.attribute_repo.lock().unwrap();
+        let attributes = attribute_repo.find_inherited_attributes(&[category_id])?;
+        Ok(attributes)
+    }
+
+    pub fn create_attribute(
+        &mut self,
+        category_id: i64,
+        name: String,
+        data_type: AttributeType,
+        unit: Option<String>,
+        is_filterable: bool,
+        options: Vec<String>,
+    ) -> Result<Attribute, ProductError> {
+        let name = Self::validate_label(name)?;
+        let options = Self::validate_attribute_options(data_type, options)?;
+        let now = Utc::now().naive_utc();
+        let mut attribute_repo = self.attribute_repo.lock().unwrap();
+        let attribute = attribute_repo.create_attribute(Attribute {
+            id: 0,
+            category_id,
+            name,
+            data_type,
+            unit: Self::normalize_unit(unit),
+            is_filterable,
+            options,
+            created_at: now,
+            updated_at: now,
+        })?;
+        Ok(attribute)
+    }
+
+    /// Updates an attribute; its data type cannot change. Options of an enum
+    /// attribute can only be removed once no product uses them.
+    pub fn update_attribute(
+        &mut self,
+        attribute_id: i64,
+        new_name: String,
+        new_unit: Option<String>,
+        is_filterable: bool,
+        new_options: Vec<String>,
+    ) -> Result<Attribute, ProductError> {
+        let new_name = Self::validate_label(new_name)?;
+        let mut attribute_repo = self.attribute_repo.lock().unwrap();
+        let attribute = attribute_repo.find_attribute_by_id(attribute_id)?;
+        let new_options = Self::validate_attribute_options(attribute.data_type, new_options)?;
+        let attribute = attribute_repo.update_attribute(Attribute {
+            name: new_name,
+            unit: Self::normalize_unit(new_unit),
+            is_filterable,
+            options: new_options,
+            ..attribute
+        })?;
+        Ok(attribute)
+    }
+
+    pub fn delete_attribute(&mut self, attribute_id: i64) -> Result<(), ProductError> {
+        let mut attribute_repo = self.attribute_repo.lock().unwrap();
+        attribute_repo.delete_attribute(attribute_id)?;
+        Ok(())
+    }
+
+    /// Replaces the specifications of a product. Each attribute must be defined
+    /// on one of the product's categories or their ancestors, and each value
+    /// must suit the data type of its attribute.
+    pub fn set_attributes(
+        &mut self,
+        product_id: i64,
+        values: Vec<(i64, AttributeValue)>,
+    ) -> Result<Vec<ProductAttribute>, ProductError> {
+        let category_ids: Vec<i64> = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo
+                .find_product_by_id(product_id)?
+                .categories
+                .iter()
+                .map(|category| category.id)
+                .collect()
+        };
+
+        let mut attribute_repo = self.attribute_repo.lock().unwrap();
+        let attributes = attribute_repo.find_inherited_attributes(&category_ids)?;
+
+        let mut validated = Vec::with_capacity(values.len());
+        for (index, (attribute_id, value)) in values.iter().enumerate() {
+            if values[..index]
+                .iter()
+                .any(|(other, _)| other == attribute_id)
+            {
+                return Err(ProductError::InvalidData);
+            }
+            let value = attributes
+                .iter()
+                .find(|attribute| attribute.id == *attribute_id)
+                .and_then(|attribute| attribute.validate_value(value.clone()))
+                .ok_or(ProductError::InvalidAttribute)?;
+            validated.push((*attribute_id, value));
+        }
+
+        let attributes = attribute_repo.set_product_attributes(product_id, validated)?;
+        Ok(attributes)
+    }
+
+    /// Sells a product as a kit of other products, or replaces the components
+    /// of a bundle. With a discount, the price of the bundle follows the total
+    /// of its components; without one, the bundle keeps its own price. Either
+    /// way its stock follows the stock of the components.
+    pub fn set_bundle(
+        &mut self,
+        product_id: i64,
+        discount_percent: Option<f64>,
+        items: Vec<BundleItem>,
+    ) -> Result<Product, ProductError> {
+        if items.is_empty()
+            || discount_percent.is_some_and(|percent| !(0.0..100.0).contains(&percent))
+        {
+            return Err(ProductError::InvalidData);
+        }
+        for (index, item) in items.iter().enumerate() {
+            if item.quantity <= 0
+                || items[..index]
+                    .iter()
+                    .any(|other| other.component_id == item.component_id)
+            {
+                return Err(ProductError::InvalidData);
+            }
+        }
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.find_product_by_id(product_id)?;
+            for item in &items {
+                product_repo.find_product_by_id(item.component_id)?;
+            }
+        }
+
+        let is_component = {
+            let mut bundle_repo = self.bundle_repo.lock().unwrap();
+            !bundle_repo
+                .find_bundle_ids_by_component_id(product_id)?
+                .is_empty()
+        };
+        if is_component
+            || self.has_variants(product_id)?
+            || items.iter().any(|item| item.component_id == product_id)
+        {
+            return Err(ProductError::InvalidBundle);
+        }
+        for item in &items {
+            if self.is_bundle(item.component_id)? || self.has_variants(item.component_id)? {
+                return Err(ProductError::InvalidBundle);
+            }
+        }
+
+        {
+            let now = Utc::now().naive_utc();
+            let mut bundle_repo = self.bundle_repo.lock().unwrap();
+            bundle_repo.save_bundle(Bundle {
+                product_id,
+                discount_percent,
+                items,
+                created_at: now,
+                updated_at: now,
+            })?;
+        }
+
+        let product = self.get(product_id, ProductVisibility::All)?;
+        self.index_product(&product);
+        Ok(product)
+    }
+
+    /// Sells a bundle as a product of its own again, keeping the stock and
+    /// price it last had.
+    pub fn remove_bundle(&mut self, product_id: i64) -> Result<Product, ProductError> {
+        {
+            let mut bundle_repo = self.bundle_repo.lock().unwrap();
+            bundle_repo.delete_bundle(product_id)?;
+        }
+
+        self.get(product_id, ProductVisibility::All)
+    }
+
+    /// Assigns an additional category to a product; the first category assigned
+    /// becomes the primary one.
+    pub fn add_category(
+        &mut self,
+        product_id: i64,
+        category_id: i64,
+    ) -> Result<Product, ProductError> {
+        {
+            let product_category_repo = self.product_category_repo.lock().unwrap();
+            let is_primary = product_category_repo
+                .find_categories_by_product_id(product_id)?
+                .is_empty();
+            let now = Utc::now().naive_utc();
+            product_category_repo.create_product_category(ProductCategory {
+                id: 0,
+                product_id,
+                category_id,
+                is_primary,
+                created_at: now,
+                updated_at: now,
+            })?;
+        }
+
+        let product = self.get(product_id, ProductVisibility::All)?;
+        self.index_product(&product);
+        Ok(product)
+    }
+
+    /// Unassigns a secondary category from a product, detaching the variation
+    /// options of the category and dropping the values of attributes the
+    /// product no longer inherits. The primary category cannot be removed until
+    /// another one is made primary, nor a category whose options variants of
+    /// the product still use.
+    pub fn remove_category(
+        &mut self,
+        product_id: i64,
+        category_id: i64,
+    ) -> Result<Product, ProductError> {
+        let option_ids = self.category_variation_options(product_id, category_id)?;
+        {
+            let product_category_repo = self.product_category_repo.lock().unwrap();
+            let product_category = product_category_repo
+                .find_categories_by_product_id(product_id)?
+                .into_iter()
+                .find(|product_category| product_category.category_id == category_id)
+                .ok_or(ProductError::NotFound)?;
+            if product_category.is_primary {
+                return Err(ProductError::InvalidCategory);
+            }
+            product_category_repo.delete_product_category(product_category.id)?;
+        }
+
+        {
+            let mut variation_repo = self.variation_repo.lock().unwrap();
+            for option_id in option_ids {
+                variation_repo.remove_product_variation_option(product_id, option_id)?;
+            }
+        }
+
+        {
+            let mut attribute_repo = self.attribute_repo.lock().unwrap();
+            attribute_repo.delete_stale_product_attributes(product_id)?;
+        }
+
+        let product = self.get(product_id, ProductVisibility::All)?;
+        self.index_product(&product);
+        Ok(product)
+    }
+
+    pub fn set_primary_category(
+        &mut self,
+        product_id: i64,
+        category_id: i64,
+    ) -> Result<Product, ProductError> {
+        {
+            let product_category_repo = self.product_category_repo.lock().unwrap();
+            product_category_repo.set_primary_product_category(product_id, category_id)?;
+        }
+
+        self.get(product_id, ProductVisibility::All)
+    }
+
+    /// Offers a variation option on a product. The option must belong to a
+    /// variation of one of the product's categories.
+    pub fn add_variation_option(
+        &mut self,
+        product_id: i64,
+        variation_option_id: i64,
+    ) -> Result<Product, ProductError> {
+        let mut product = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.find_product_by_id(product_id)?
+        };
+
+        {
+            let mut variation_repo = self.variation_repo.lock().unwrap();
+            let option = variation_repo.find_variation_option_by_id(variation_option_id)?;
+            let variation = variation_repo.find_variation_by_id(option.variation_id)?;
+
+            if !product
+                .categories
+                .iter()
+                .any(|category| category.id == variation.category_id)
+            {
+                return Err(ProductError::InvalidVariationOption);
+            }
+
+            variation_repo.add_product_variation_option(product.id, option.id)?;
+        }
+
+        self.load_variant_matrix(&mut product)?;
+        Ok(product)
+    }
+
+    /// Stops offering a variation option on a product. Fails while a variant
+    /// still uses the option.
+    pub fn remove_variation_option(
+        &mut self,
+        product_id: i64,
+        variation_option_id: i64,
+    ) -> Result<Product, ProductError> {
+        let mut product = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo.find_product_by_id(product_id)?
+        };
+
+        {
+            let mut variant_repo = self.variant_repo.lock().unwrap();
+            let in_use = variant_repo
+                .find_variants_by_product_id(product.id)?
+                .iter()
+                .any(|variant| variant.options.iter().any(|o| o.id == variation_option_id));
+            if in_use {
+                return Err(ProductError::InvalidVariant);
+            }
+        }
+
+        {
+            let mut variation_repo = self.variation_repo.lock().unwrap();
+            variation_repo.remove_product_variation_option(product.id, variation_option_id)?;
+        }
+
+        self.load_variant_matrix(&mut product)?;
+        Ok(product)
+    }
+
+    /// Creates a sellable variant from one option per variation offered by the product.
+    pub fn create_variant(
+        &mut self,
+        product_id: i64,
+        sku: String,
+        price: Option<f64>,
+        stock: i32,
+        image: Option<String>,
+        variation_option_ids: Vec<i64>,
+    ) -> Result<ProductVariant, ProductError> {
+        let (sku, variation_option_ids) =
+            self.validate_variant(product_id, None, sku, price, stock, variation_option_ids)?;
+
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+        let variant = variant_repo.create_variant(
+            product_id,
+            sku,
+            price,
+            stock,
+            image,
+            variation_option_ids,
+        )?;
+        Ok(variant)
+    }
+
+    pub fn update_variant(
+        &mut self,
+        variant_id: i64,
+        new_sku: String,
+        new_price: Option<f64>,
+        new_stock: i32,
+        new_image: Option<String>,
+        new_variation_option_ids: Vec<i64>,
+    ) -> Result<ProductVariant, ProductError> {
+        let variant = {
+            let mut variant_repo = self.variant_repo.lock().unwrap();
+            variant_repo.find_variant_by_id(variant_id)?
+        };
+
+        let (new_sku, new_variation_option_ids) = self.validate_variant(
+            variant.product_id,
+            Some(variant.id),
+            new_sku,
+            new_price,
+            new_stock,
+            new_variation_option_ids,
+        )?;
+
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+        let variant = variant_repo.update_variant(
+            variant.id,
+            new_sku,
+            new_price,
+            new_stock,
+            new_image,
+            new_variation_option_ids,
+        )?;
+        Ok(variant)
+    }
+
+    pub fn delete_variant(&mut self, variant_id: i64) -> Result<(), ProductError> {
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+        variant_repo.delete_variant(variant_id)?;
+        Ok(())
+    }
+
+    // Private Methods
+
+    fn normalize_alt_text(alt_text: Option<String>) -> Option<String> {
+        alt_text
+            .map(|alt_text| alt_text.trim().to_string())
+            .filter(|alt_text| !alt_text.is_empty())
+    }
+
+    fn map_storage_error(err: BlobStorageError) -> ProductError {
+        match err {
+            BlobStorageError::NotFound | BlobStorageError::InvalidKey => ProductError::NotFound,
+            BlobStorageError::InternalError => ProductError::InternalError,
+        }
+    }
+
+    fn map_processor_error(err: ImageProcessorError) -> ProductError {
+        match err {
+            ImageProcessorError::UnsupportedFormat | ImageProcessorError::InvalidImage => {
+                ProductError::InvalidImage
+            }
+            ImageProcessorError::InternalError => ProductError::InternalError,
+        }
+    }
+
+    /// Number of matches to skip for `pagination`; cursors past
+    /// [`MAX_SEARCH_OFFSET`] are rejected.
+    fn search_offset(pagination: &SearchPagination) -> Result<usize, ProductError> {
+        match pagination.after {
+            Some(after) if after.offset > MAX_SEARCH_OFFSET => Err(ProductError::InvalidData),
+            Some(after) => Ok(after.offset),
+            None => Ok(0),
+        }
+    }
+
+    fn map_search_index_error(err: SearchIndexError) -> ProductError {
+        match err {
+            SearchIndexError::InternalError => ProductError::InternalError,
+        }
+    }
+
+    /// Updates the document of a product whose changes are already saved. A
+    /// failure is only logged: the save stands, and
+    /// [`ProductService::rebuild_search_index`] catches the index up.
+    fn index_product(&self, product: &Product) {
+        let mut search_index = self.search_index.lock().unwrap();
+        if let Err(err) = search_index.index_products(std::slice::from_ref(product)) {
+            eprintln!("Failed to index product {}: {:?}", product.id, err);
+        }
+    }
+
+    /// Pairs search hits with their products, keeping the order of the hits.
+    /// Hits of products customers cannot see are dropped.
+    fn to_search_results(
+        product_repo: &mut dyn ProductRepository,
+        hits: Vec<SearchHit>,
+    ) -> Result<Vec<ProductSearchResult>, ProductError> {
+        let now = Utc::now().naive_utc();
+        let ids: Vec<i64> = hits.iter().map(|hit| hit.product_id).collect();
+        let mut products: HashMap<i64, Product> = product_repo
+            .find_products_by_ids(&ids)?
+            .into_iter()
+            .filter(|product| product.is_published(now))
+            .map(|product| (product.id, product))
+            .collect();
+
+        Ok(hits
+            .into_iter()
+            .filter_map(|hit| {
+                products
+                    .remove(&hit.product_id)
+                    .map(|product| ProductSearchResult {
+                        product,
+                        rank: hit.rank,
+                        snippet: hit.snippet,
+                    })
+            })
+            .collect())
+    }
+
+    fn validate_label(label: String) -> Result<String, ProductError> {
+        let label = label.trim().to_string();
+        if label.is_empty() {
+            return Err(ProductError::InvalidData);
+        }
+        Ok(label)
+    }
+
+    /// Trims and deduplicates the options of an enum attribute, which needs at
+    /// least one. Other data types take none.
+    fn validate_attribute_options(
+        data_type: AttributeType,
+        options: Vec<String>,
+    ) -> Result<Vec<String>, ProductError> {
+        if data_type != AttributeType::Enum {
+            return if options.is_empty() {
+                Ok(options)
+            } else {
+                Err(ProductError::InvalidData)
+            };
+        }
+
+        let mut validated: Vec<String> = Vec::with_capacity(options.len());
+        for option in options {
+            let option = Self::validate_label(option)?;
+            if !validated.contains(&option) {
+                validated.push(option);
+            }
+        }
+        if validated.is_empty() {
+            return Err(ProductError::InvalidData);
+        }
+        Ok(validated)
+    }
+
+    fn normalize_unit(unit: Option<String>) -> Option<String> {
+        unit.map(|unit| unit.trim().to_string())
+            .filter(|unit| !unit.is_empty())
+    }
+
+    fn is_bundle(&self, product_id: i64) -> Result<bool, ProductError> {
+        let mut bundle_repo = self.bundle_repo.lock().unwrap();
+        match bundle_repo.find_bundle_by_product_id(product_id) {
+            Ok(_) => Ok(true),
+            Err(ProductError::NotFound) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn has_variants(&self, product_id: i64) -> Result<bool, ProductError> {
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+        Ok(!variant_repo
+            .find_variants_by_product_id(product_id)?
+            .is_empty())
+    }
+
+    /// Fills the variations, offered options and variants of a product.
+    fn load_variant_matrix(&self, product: &mut Product) -> Result<(), ProductError> {
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+
+        product.variation_options =
+            variation_repo.find_variation_options_by_product_id(product.id)?;
+
+        let mut variation_ids: Vec<i64> = product
+            .variation_options
+            .iter()
+            .map(|option| option.variation_id)
+            .collect();
+        variation_ids.sort_unstable();
+        variation_ids.dedup();
+
+        product.variations = variation_ids
+            .into_iter()
+            .map(|variation_id| variation_repo.find_variation_by_id(variation_id))
+            .collect::<Result<_, _>>()?;
+
+        product.variants = variant_repo.find_variants_by_product_id(product.id)?;
         Ok(())
     }
 
+    fn validate_variant(
+        &self,
+        product_id: i64,
+        variant_id: Option<i64>,
+        sku: String,
+        price: Option<f64>,
+        stock: i32,
+        mut variation_option_ids: Vec<i64>,
+    ) -> Result<(String, Vec<i64>), ProductError> {
+        let sku = Self::validate_label(sku)?;
+        if price.is_some_and(|price| price < 0.0) || stock < 0 {
+            return Err(ProductError::InvalidData);
+        }
+
+        // The stock of bundles and their components lives on the products.
+        let is_component = {
+            let mut bundle_repo = self.bundle_repo.lock().unwrap();
+            !bundle_repo
+                .find_bundle_ids_by_component_id(product_id)?
+                .is_empty()
+        };
+        if is_component || self.is_bundle(product_id)? {
+            return Err(ProductError::InvalidBundle);
+        }
+
+        variation_option_ids.sort_unstable();
+        variation_option_ids.dedup();
+        if variation_option_ids.is_empty() {
+            return Err(ProductError::InvalidVariant);
+        }
+
+        let offered = {
+            let mut variation_repo = self.variation_repo.lock().unwrap();
+            variation_repo.find_variation_options_by_product_id(product_id)?
+        };
+
+        let mut variation_ids = Vec::with_capacity(variation_option_ids.len());
+        for option_id in &variation_option_ids {
+            let option = offered
+                .iter()
+                .find(|option| option.id == *option_id)
+                .ok_or(ProductError::InvalidVariationOption)?;
+            if variation_ids.contains(&option.variation_id) {
+                return Err(ProductError::InvalidVariant);
+            }
+            variation_ids.push(option.variation_id);
+        }
+
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+        let duplicated = variant_repo
+            .find_variants_by_product_id(product_id)?
+            .iter()
+            .filter(|variant| Some(variant.id) != variant_id)
+            .any(|variant| {
+                let mut ids: Vec<i64> = variant.options.iter().map(|option| option.id).collect();
+                ids.sort_unstable();
+                ids == variation_option_ids
+            });
+        if duplicated {
+            return Err(ProductError::InvalidVariant);
+        }
+
+        Ok((sku, variation_option_ids))
+    }
+
+    /// Lists the product's variation options that belong to `category_id`,
+    /// leaving variants alone: fails with `VariationOptionInUse` while a
+    /// variant of the product uses any of them.
+    fn category_variation_options(
+        &self,
+        product_id: i64,
+        category_id: i64,
+    ) -> Result<Vec<i64>, ProductError> {
+        let mut variation_repo = self.variation_repo.lock().unwrap();
+        let mut variant_repo = self.variant_repo.lock().unwrap();
+        let variants = variant_repo.find_variants_by_product_id(product_id)?;
 
-    // TODO Complete variations
-    // TODO Complete variation options
+        let mut option_ids = Vec::new();
+        for option in variation_repo.find_variation_options_by_product_id(product_id)? {
+            let variation = variation_repo.find_variation_by_id(option.variation_id)?;
+            if variation.category_id != category_id {
+                continue;
+            }
+            if variants
+                .iter()
+                .any(|variant| variant.options.iter().any(|o| o.id == option.id))
+            {
+                return Err(ProductError::VariationOptionInUse);
+            }
+            option_ids.push(option.id);
+        }
+        Ok(option_ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_offset_starts_at_zero() {
+        let pagination = SearchPagination::new(None, None).unwrap();
+        assert_eq!(ProductService::search_offset(&pagination).unwrap(), 0);
+    }
+
+    #[test]
+    fn search_offset_follows_the_cursor() {
+        let cursor = SearchCursor { offset: 40 }.encode();
+        let pagination = SearchPagination::new(Some(&cursor), None).unwrap();
+        assert_eq!(ProductService::search_offset(&pagination).unwrap(), 40);
+    }
+
+    #[test]
+    fn search_offset_rejects_huge_offsets() {
+        let cursor = SearchCursor { offset: usize::MAX }.encode();
+        let pagination = SearchPagination::new(Some(&cursor), None).unwrap();
+        assert!(matches!(
+            ProductService::search_offset(&pagination),
+            Err(ProductError::InvalidData)
+        ));
+    }
 }
@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+
+use crate::core::{
+    models::{
+        moderation::ModerationStatus,
+        order::OrderError,
+        pagination::{Page, Pagination},
+        product::ProductError,
+        question::{MAX_QUESTION_LENGTH, ProductAnswer, ProductQuestion, QuestionError},
+        user::UserRole,
+    },
+    ports::{
+        order_repository::OrderRepository, product_repository::ProductRepository,
+        question_repository::QuestionRepository, user_repository::UserRepository,
+    },
+};
+
+use super::email_service::EmailService;
+
+#[derive(Clone)]
+pub struct QuestionService {
+    pub(crate) question_repo: Arc<Mutex<dyn QuestionRepository>>,
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) order_repo: Arc<Mutex<dyn OrderRepository>>,
+    pub(crate) user_repo: Arc<Mutex<dyn UserRepository>>,
+    pub(crate) email_service: Arc<Mutex<dyn EmailService>>,
+}
+
+pub fn new_question_service(
+    question_repo: Arc<Mutex<dyn QuestionRepository>>,
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    order_repo: Arc<Mutex<dyn OrderRepository>>,
+    user_repo: Arc<Mutex<dyn UserRepository>>,
+    email_service: Arc<Mutex<dyn EmailService>>,
+) -> QuestionService {
+    QuestionService {
+        question_repo,
+        product_repo,
+        order_repo,
+        user_repo,
+        email_service,
+    }
+}
+
+impl QuestionService {
+    /// Submits a question about a product. The question waits for a manager
+    /// before being shown.
+    pub fn ask(
+        &mut self,
+        user_id: i64,
+        product_id: i64,
+        body: String,
+    ) -> Result<ProductQuestion, QuestionError> {
+        let body = validate_body(body)?;
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo
+                .find_product_by_id(product_id)
+                .map_err(|err| match err {
+                    ProductError::NotFound => QuestionError::NotFound,
+                    _ => QuestionError::DatabaseError,
+                })?;
+        }
+
+        let now = Utc::now().naive_utc();
+        let mut question_repo = self.question_repo.lock().unwrap();
+        question_repo.create_question(ProductQuestion {
+            id: 0,
+            product_id,
+            user_id,
+            body,
+            status: ModerationStatus::Pending,
+            answer_count: 0,
+            answers: vec![],
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Lists the approved questions of a product with their approved answers,
+    /// newest first.
+    pub fn get_by_product(
+        &mut self,
+        product_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<ProductQuestion>, QuestionError> {
+        let mut question_repo = self.question_repo.lock().unwrap();
+        let questions = question_repo.find_questions_by_product_id(product_id, pagination)?;
+        Ok(questions)
+    }
+
+    /// Lists the questions with the given status, oldest first, for moderation.
+    pub fn get_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<ProductQuestion>, QuestionError> {
+        let mut question_repo = self.question_repo.lock().unwrap();
+        let questions = question_repo.find_questions_by_status(status, pagination)?;
+        Ok(questions)
+    }
+
+    /// Lists the answers with the given status, oldest first, for moderation.
+    pub fn get_answers_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<ProductAnswer>, QuestionError> {
+        let mut question_repo = self.question_repo.lock().unwrap();
+        let answers = question_repo.find_answers_by_status(status, pagination)?;
+        Ok(answers)
+    }
+
+    /// Approves or rejects a question; only approved questions can be
+    /// answered.
+    pub fn moderate_question(
+        &mut self,
+        id: i64,
+        status: ModerationStatus,
+    ) -> Result<ProductQuestion, QuestionError> {
+        if status == ModerationStatus::Pending {
+            return Err(QuestionError::InvalidData);
+        }
+
+        let mut question_repo = self.question_repo.lock().unwrap();
+        question_repo.update_question_status(id, status)
+    }
+
+    /// Answers an approved question. Answers of the staff are published right
+    /// away, those of customers who received the product wait for a manager.
+    pub fn answer(
+        &mut self,
+        user_id: i64,
+        user_role: UserRole,
+        question_id: i64,
+        body: String,
+    ) -> Result<ProductAnswer, QuestionError> {
+        let body = validate_body(body)?;
+
+        let question = {
+            let mut question_repo = self.question_repo.lock().unwrap();
+            question_repo.find_question_by_id(question_id)?
+        };
+        if question.status != ModerationStatus::Approved {
+            return Err(QuestionError::NotFound);
+        }
+
+        let is_staff = matches!(user_role, UserRole::Manager | UserRole::Admin);
+        let is_verified_buyer = {
+            let mut order_repo = self.order_repo.lock().unwrap();
+            order_repo
+                .has_delivered_product(user_id, question.product_id)
+                .map_err(|err| match err {
+                    OrderError::NotFound => QuestionError::NotFound,
+                    _ => QuestionError::DatabaseError,
+                })?
+        };
+        if !is_staff && !is_verified_buyer {
+            return Err(QuestionError::PermissionDenied);
+        }
+
+        let now = Utc::now().naive_utc();
+        let answer = {
+            let mut question_repo = self.question_repo.lock().unwrap();
+            question_repo.create_answer(ProductAnswer {
+                id: 0,
+                question_id,
+                user_id,
+                body,
+                status: if is_staff {
+                    ModerationStatus::Approved
+                } else {
+                    ModerationStatus::Pending
+                },
+                is_staff,
+                is_verified_buyer,
+                created_at: now,
+                updated_at: now,
+            })?
+        };
+
+        if answer.status == ModerationStatus::Approved {
+            self.notify_asker(&question, &answer);
+        }
+        Ok(answer)
+    }
+
+    /// Approves or rejects an answer. The asker is notified the first time an
+    /// answer is approved.
+    pub fn moderate_answer(
+        &mut self,
+        id: i64,
+        status: ModerationStatus,
+    ) -> Result<ProductAnswer, QuestionError> {
+        if status == ModerationStatus::Pending {
+            return Err(QuestionError::InvalidData);
+        }
+
+        let (previous, answer, question) = {
+            let mut question_repo = self.question_repo.lock().unwrap();
+            let previous = question_repo.find_answer_by_id(id)?;
+            let answer = question_repo.update_answer_status(id, status)?;
+            let question = question_repo.find_question_by_id(answer.question_id)?;
+            (previous, answer, question)
+        };
+
+        if previous.status != ModerationStatus::Approved
+            && answer.status == ModerationStatus::Approved
+        {
+            self.notify_asker(&question, &answer);
+        }
+        Ok(answer)
+    }
+
+    /// Emails the asker that their question has a published answer. Failures
+    /// are logged, as the answer is already saved.
+    fn notify_asker(&self, question: &ProductQuestion, answer: &ProductAnswer) {
+        if question.user_id == answer.user_id {
+            return;
+        }
+
+        let asker = {
+            let mut user_repo = self.user_repo.lock().unwrap();
+            match user_repo.find_by_id(question.user_id) {
+                Ok(user) => user,
+                Err(err) => {
+                    eprintln!(
+                        "Failed to load the asker of question {}: {:?}",
+                        question.id, err
+                    );
+                    return;
+                }
+            }
+        };
+
+        let mut context = HashMap::new();
+        context.insert("product_id".to_string(), question.product_id.to_string());
+        context.insert("question".to_string(), question.body.clone());
+        context.insert("answer".to_string(), answer.body.clone());
+
+        let email_service = self.email_service.lock().unwrap();
+        if let Err(err) = email_service.send_email(
+            &asker.email,
+            "Your question was answered",
+            "question_answered",
+            context,
+        ) {
+            eprintln!(
+                "Failed to notify the asker of question {}: {}",
+                question.id, err
+            );
+        }
+    }
+}
+
+fn validate_body(body: String) -> Result<String, QuestionError> {
+    let body = body.trim().to_string();
+    if body.is_empty() || body.chars().count() > MAX_QUESTION_LENGTH {
+        return Err(QuestionError::InvalidData);
+    }
+    Ok(body)
+}
@@ -0,0 +1,119 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use chrono::Utc;
+
+use crate::core::{
+    models::{
+        product::{Product, ProductError},
+        recommendation::RecommendationError,
+    },
+    ports::{
+        cart_repository::{CartError, CartRepository},
+        product_repository::ProductRepository,
+        recommendation_repository::RecommendationRepository,
+    },
+};
+
+#[derive(Clone)]
+pub struct RecommendationService {
+    pub(crate) recommendation_repo: Arc<Mutex<dyn RecommendationRepository>>,
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) cart_repo: Arc<Mutex<dyn CartRepository>>,
+    /// Related products kept per product and suggested per cart.
+    pub(crate) limit: i64,
+}
+
+pub fn new_recommendation_service(
+    recommendation_repo: Arc<Mutex<dyn RecommendationRepository>>,
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    cart_repo: Arc<Mutex<dyn CartRepository>>,
+    limit: i64,
+) -> RecommendationService {
+    RecommendationService {
+        recommendation_repo,
+        product_repo,
+        cart_repo,
+        limit,
+    }
+}
+
+impl RecommendationService {
+    /// Computes the related products of every product again from the orders
+    /// and categories. Returns the number of recommendations added or changed.
+    pub fn refresh(&mut self) -> Result<usize, RecommendationError> {
+        let mut recommendation_repo = self.recommendation_repo.lock().unwrap();
+        recommendation_repo.refresh_recommendations(self.limit.max(1))
+    }
+
+    /// Suggests published products related to those in the active cart of the
+    /// user, the ones related to several items first. Products already in the
+    /// cart are left out.
+    pub fn get_for_cart(&mut self, user_id: i64) -> Result<Vec<Product>, RecommendationError> {
+        let product_ids: HashSet<i64> = {
+            let mut cart_repo = self.cart_repo.lock().unwrap();
+            let cart = match cart_repo.find_active_cart_by_user_id(user_id) {
+                Ok(cart) => cart,
+                Err(CartError::NotFound) => return Ok(vec![]),
+                Err(_) => return Err(RecommendationError::DatabaseError),
+            };
+            cart_repo
+                .find_cart_items_by_cart_id(cart.id)
+                .map_err(|_| RecommendationError::DatabaseError)?
+                .iter()
+                .map(|item| item.product_id)
+                .collect()
+        };
+        if product_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let recommendations = {
+            let mut recommendation_repo = self.recommendation_repo.lock().unwrap();
+            recommendation_repo
+                .find_recommendations(&product_ids.iter().copied().collect::<Vec<_>>())?
+        };
+        let mut scores: HashMap<i64, f64> = HashMap::new();
+        for recommendation in recommendations {
+            if !product_ids.contains(&recommendation.related_product_id) {
+                *scores.entry(recommendation.related_product_id).or_default() +=
+                    recommendation.score;
+            }
+        }
+        let mut scores: Vec<(i64, f64)> = scores.into_iter().collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        // Fetches a few extra products to make up for those not published.
+        let related_ids: Vec<i64> = scores
+            .iter()
+            .take(self.limit.max(1) as usize * 2)
+            .map(|(id, _)| *id)
+            .collect();
+        let mut product_repo = self.product_repo.lock().unwrap();
+        let mut products = load_published_products(&mut *product_repo, &related_ids)
+            .map_err(|_| RecommendationError::DatabaseError)?;
+        products.truncate(self.limit.max(1) as usize);
+        Ok(products)
+    }
+}
+
+/// Loads the published products among `ids`, in the order of `ids`.
+pub(crate) fn load_published_products(
+    product_repo: &mut dyn ProductRepository,
+    ids: &[i64],
+) -> Result<Vec<Product>, ProductError> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let now = Utc::now().naive_utc();
+    let mut products: HashMap<i64, Product> = product_repo
+        .find_products_by_ids(ids)?
+        .into_iter()
+        .filter(|product| product.is_published(now))
+        .map(|product| (product.id, product))
+        .collect();
+    Ok(ids.iter().filter_map(|id| products.remove(id)).collect())
+}
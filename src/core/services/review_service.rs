@@ -0,0 +1,159 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+
+use crate::core::{
+    models::{
+        moderation::ModerationStatus,
+        order::OrderError,
+        pagination::{Page, Pagination},
+        product::ProductError,
+        review::{MAX_RATING, MAX_REVIEW_LENGTH, MIN_RATING, Review, ReviewError},
+    },
+    ports::{
+        order_repository::OrderRepository, product_repository::ProductRepository,
+        review_repository::ReviewRepository,
+    },
+};
+
+#[derive(Clone)]
+pub struct ReviewService {
+    pub(crate) review_repo: Arc<Mutex<dyn ReviewRepository>>,
+    pub(crate) order_repo: Arc<Mutex<dyn OrderRepository>>,
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+}
+
+pub fn new_review_service(
+    review_repo: Arc<Mutex<dyn ReviewRepository>>,
+    order_repo: Arc<Mutex<dyn OrderRepository>>,
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+) -> ReviewService {
+    ReviewService {
+        review_repo,
+        order_repo,
+        product_repo,
+    }
+}
+
+impl ReviewService {
+    /// Submits the review of a product by a customer who received it. The
+    /// review waits for a manager before being shown.
+    pub fn create(
+        &mut self,
+        user_id: i64,
+        product_id: i64,
+        rating: i32,
+        body: String,
+    ) -> Result<Review, ReviewError> {
+        let body = body.trim().to_string();
+        if !(MIN_RATING..=MAX_RATING).contains(&rating)
+            || body.is_empty()
+            || body.chars().count() > MAX_REVIEW_LENGTH
+        {
+            return Err(ReviewError::InvalidData);
+        }
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo
+                .find_product_by_id(product_id)
+                .map_err(|err| match err {
+                    ProductError::NotFound => ReviewError::NotFound,
+                    _ => ReviewError::DatabaseError,
+                })?;
+        }
+        {
+            let mut order_repo = self.order_repo.lock().unwrap();
+            let delivered = order_repo
+                .has_delivered_product(user_id, product_id)
+                .map_err(map_order_error)?;
+            if !delivered {
+                return Err(ReviewError::NotPurchased);
+            }
+        }
+
+        let now = Utc::now().naive_utc();
+        let mut review_repo = self.review_repo.lock().unwrap();
+        review_repo.create_review(Review {
+            id: 0,
+            product_id,
+            user_id,
+            rating,
+            body,
+            status: ModerationStatus::Pending,
+            response: None,
+            responded_at: None,
+            helpful_count: 0,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Lists the approved reviews of a product, newest first.
+    pub fn get_by_product(
+        &mut self,
+        product_id: i64,
+        pagination: Pagination,
+    ) -> Result<Page<Review>, ReviewError> {
+        let mut review_repo = self.review_repo.lock().unwrap();
+        let reviews = review_repo.find_reviews_by_product_id(product_id, pagination)?;
+        Ok(reviews)
+    }
+
+    /// Lists the reviews with the given status, oldest first, for moderation.
+    pub fn get_by_status(
+        &mut self,
+        status: ModerationStatus,
+        pagination: Pagination,
+    ) -> Result<Page<Review>, ReviewError> {
+        let mut review_repo = self.review_repo.lock().unwrap();
+        let reviews = review_repo.find_reviews_by_status(status, pagination)?;
+        Ok(reviews)
+    }
+
+    /// Approves or rejects a review. Approved reviews count towards the rating
+    /// of their product.
+    pub fn moderate(&mut self, id: i64, status: ModerationStatus) -> Result<Review, ReviewError> {
+        if status == ModerationStatus::Pending {
+            return Err(ReviewError::InvalidData);
+        }
+
+        let mut review_repo = self.review_repo.lock().unwrap();
+        review_repo.update_review_status(id, status)
+    }
+
+    /// Answers a review publicly on behalf of the store; an empty response
+    /// removes the answer.
+    pub fn respond(&mut self, id: i64, response: String) -> Result<Review, ReviewError> {
+        let response = response.trim().to_string();
+        if response.chars().count() > MAX_REVIEW_LENGTH {
+            return Err(ReviewError::InvalidData);
+        }
+
+        let mut review_repo = self.review_repo.lock().unwrap();
+        review_repo
+            .update_review_response(id, Some(response).filter(|response| !response.is_empty()))
+    }
+
+    /// Counts a customer finding an approved review helpful, once per customer.
+    /// Authors cannot vote for their own reviews.
+    pub fn vote_helpful(&mut self, user_id: i64, id: i64) -> Result<Review, ReviewError> {
+        let mut review_repo = self.review_repo.lock().unwrap();
+        let review = review_repo.find_review_by_id(id)?;
+        if review.status != ModerationStatus::Approved {
+            return Err(ReviewError::NotFound);
+        }
+        if review.user_id == user_id {
+            return Err(ReviewError::InvalidData);
+        }
+
+        review_repo.add_helpful_vote(id, user_id)
+    }
+}
+
+fn map_order_error(err: OrderError) -> ReviewError {
+    match err {
+        OrderError::NotFound => ReviewError::NotFound,
+        _ => ReviewError::DatabaseError,
+    }
+}
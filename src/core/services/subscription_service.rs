@@ -0,0 +1,446 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use chrono::{NaiveDateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::core::{
+    models::{
+        customization::validate_customizations,
+        order::OrderError,
+        product::ProductError,
+        subscription::{
+            MAX_INTERVAL_DAYS, MAX_SUBSCRIPTION_QUANTITY, Subscription, SubscriptionError,
+            SubscriptionPlan, SubscriptionRunReport, SubscriptionStatus,
+        },
+    },
+    ports::{
+        product_repository::ProductRepository,
+        product_variant_repository::ProductVariantRepository,
+        subscription_repository::SubscriptionRepository, user_repository::UserRepository,
+    },
+};
+
+use super::{email_service::EmailService, order_service::OrderService};
+
+#[derive(Clone)]
+pub struct SubscriptionService {
+    pub(crate) subscription_repo: Arc<Mutex<dyn SubscriptionRepository>>,
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+    pub(crate) user_repo: Arc<Mutex<dyn UserRepository>>,
+    pub(crate) order_service: Arc<Mutex<OrderService>>,
+    pub(crate) email_service: Arc<Mutex<dyn EmailService>>,
+}
+
+pub fn new_subscription_service(
+    subscription_repo: Arc<Mutex<dyn SubscriptionRepository>>,
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    variant_repo: Arc<Mutex<dyn ProductVariantRepository>>,
+    user_repo: Arc<Mutex<dyn UserRepository>>,
+    order_service: Arc<Mutex<OrderService>>,
+    email_service: Arc<Mutex<dyn EmailService>>,
+) -> SubscriptionService {
+    SubscriptionService {
+        subscription_repo,
+        product_repo,
+        variant_repo,
+        user_repo,
+        order_service,
+        email_service,
+    }
+}
+
+impl SubscriptionService {
+    pub fn get_plans(
+        &mut self,
+        product_id: i64,
+        active_only: bool,
+    ) -> Result<Vec<SubscriptionPlan>, SubscriptionError> {
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.find_plans_by_product_id(product_id, active_only)
+    }
+
+    /// Offers a product on a schedule of `interval_days`, at a discount.
+    pub fn create_plan(
+        &mut self,
+        product_id: i64,
+        interval_days: i32,
+        discount_percent: f64,
+    ) -> Result<SubscriptionPlan, SubscriptionError> {
+        if !(1..=MAX_INTERVAL_DAYS).contains(&interval_days) {
+            return Err(SubscriptionError::InvalidData);
+        }
+        Self::validate_discount(discount_percent)?;
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo
+                .find_product_by_id(product_id)
+                .map_err(Self::map_product_error)?;
+        }
+
+        let now = Utc::now().naive_utc();
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.create_plan(SubscriptionPlan {
+            id: 0,
+            product_id,
+            interval_days,
+            discount_percent,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Changes the discount of a plan, applied from the next run on, and whether
+    /// it can be subscribed to. The interval of a plan cannot change.
+    pub fn update_plan(
+        &mut self,
+        plan_id: i64,
+        discount_percent: f64,
+        is_active: bool,
+    ) -> Result<SubscriptionPlan, SubscriptionError> {
+        Self::validate_discount(discount_percent)?;
+
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        let plan = subscription_repo.find_plan_by_id(plan_id)?;
+        subscription_repo.update_plan(SubscriptionPlan {
+            discount_percent,
+            is_active,
+            ..plan
+        })
+    }
+
+    /// Deletes a plan nobody ever subscribed to; others can only be deactivated.
+    pub fn delete_plan(&mut self, plan_id: i64) -> Result<(), SubscriptionError> {
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.delete_plan(plan_id)
+    }
+
+    /// Subscribes the user to an active plan. The first order is placed at
+    /// `first_run_at`, or on the next run of the scheduler when unset. Products
+    /// customers cannot see, and products that require customizations, cannot
+    /// be subscribed to; products with variants need one chosen.
+    pub fn subscribe(
+        &mut self,
+        user_id: i64,
+        plan_id: i64,
+        variant_id: Option<i64>,
+        quantity: i32,
+        first_run_at: Option<NaiveDateTime>,
+    ) -> Result<Subscription, SubscriptionError> {
+        if !(1..=MAX_SUBSCRIPTION_QUANTITY).contains(&quantity) {
+            return Err(SubscriptionError::InvalidData);
+        }
+
+        let plan = {
+            let mut subscription_repo = self.subscription_repo.lock().unwrap();
+            subscription_repo.find_plan_by_id(plan_id)?
+        };
+        if !plan.is_active {
+            return Err(SubscriptionError::NotFound);
+        }
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            let product = product_repo
+                .find_product_by_id(plan.product_id)
+                .map_err(Self::map_product_error)?;
+            if !product.is_published(Utc::now().naive_utc()) {
+                return Err(SubscriptionError::ProductUnavailable);
+            }
+            validate_customizations(&product.customization_fields, &Value::Object(Map::new()))
+                .map_err(|_| SubscriptionError::InvalidData)?;
+        }
+
+        {
+            let mut variant_repo = self.variant_repo.lock().unwrap();
+            let has_variants = !variant_repo
+                .find_variants_by_product_id(plan.product_id)
+                .map_err(Self::map_product_error)?
+                .is_empty();
+            match variant_id {
+                Some(variant_id) => {
+                    let variant = variant_repo
+                        .find_variant_by_id(variant_id)
+                        .map_err(Self::map_product_error)?;
+                    if variant.product_id != plan.product_id {
+                        return Err(SubscriptionError::InvalidData);
+                    }
+                }
+                None if has_variants => return Err(SubscriptionError::InvalidData),
+                None => {}
+            }
+        }
+
+        let now = Utc::now().naive_utc();
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.create_subscription(Subscription {
+            id: 0,
+            user_id,
+            plan,
+            variant_id,
+            quantity,
+            status: SubscriptionStatus::Active,
+            next_run_at: first_run_at.map_or(now, |first_run_at| first_run_at.max(now)),
+            last_order_id: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn get_all(&mut self, user_id: i64) -> Result<Vec<Subscription>, SubscriptionError> {
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.find_subscriptions_by_user_id(user_id)
+    }
+
+    /// Stops ordering until the subscription is resumed.
+    pub fn pause(
+        &mut self,
+        user_id: i64,
+        subscription_id: i64,
+    ) -> Result<Subscription, SubscriptionError> {
+        let subscription = self.find_own(user_id, subscription_id)?;
+        if subscription.status != SubscriptionStatus::Active {
+            return Err(SubscriptionError::InvalidStatusTransition);
+        }
+
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.update_subscription_status(
+            subscription.id,
+            SubscriptionStatus::Paused,
+            subscription.next_run_at,
+        )
+    }
+
+    /// Orders again on schedule; a run that fell due while paused is placed on
+    /// the next run of the scheduler.
+    pub fn resume(
+        &mut self,
+        user_id: i64,
+        subscription_id: i64,
+    ) -> Result<Subscription, SubscriptionError> {
+        let subscription = self.find_own(user_id, subscription_id)?;
+        if subscription.status != SubscriptionStatus::Paused {
+            return Err(SubscriptionError::InvalidStatusTransition);
+        }
+
+        let now = Utc::now().naive_utc();
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.update_subscription_status(
+            subscription.id,
+            SubscriptionStatus::Active,
+            subscription.next_run_at.max(now),
+        )
+    }
+
+    /// Moves the next run one interval later without ordering.
+    pub fn skip(
+        &mut self,
+        user_id: i64,
+        subscription_id: i64,
+    ) -> Result<Subscription, SubscriptionError> {
+        let subscription = self.find_own(user_id, subscription_id)?;
+        if subscription.status == SubscriptionStatus::Cancelled {
+            return Err(SubscriptionError::InvalidStatusTransition);
+        }
+
+        let next_run_at = subscription
+            .plan
+            .next_run_after(subscription.next_run_at, Utc::now().naive_utc());
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.update_subscription_status(
+            subscription.id,
+            subscription.status,
+            next_run_at,
+        )
+    }
+
+    pub fn cancel(
+        &mut self,
+        user_id: i64,
+        subscription_id: i64,
+    ) -> Result<Subscription, SubscriptionError> {
+        let subscription = self.find_own(user_id, subscription_id)?;
+        if subscription.status == SubscriptionStatus::Cancelled {
+            return Err(SubscriptionError::InvalidStatusTransition);
+        }
+
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        subscription_repo.update_subscription_status(
+            subscription.id,
+            SubscriptionStatus::Cancelled,
+            subscription.next_run_at,
+        )
+    }
+
+    /// Places an order for every active subscription due and notifies its user.
+    ///
+    /// Each run is claimed by moving the subscription to its next run first, so
+    /// concurrent passes never order twice. Runs whose product is out of stock or
+    /// no longer available are skipped, and the user told so; runs that fail
+    /// otherwise are put back to be tried again on the next pass.
+    pub fn run_due(&mut self) -> Result<SubscriptionRunReport, SubscriptionError> {
+        let now = Utc::now().naive_utc();
+        let due = {
+            let mut subscription_repo = self.subscription_repo.lock().unwrap();
+            subscription_repo.find_due_subscriptions(now)?
+        };
+
+        let mut report = SubscriptionRunReport::default();
+        for subscription in due {
+            let next_run_at = subscription
+                .plan
+                .next_run_after(subscription.next_run_at, now);
+            {
+                let mut subscription_repo = self.subscription_repo.lock().unwrap();
+                if !subscription_repo.claim_subscription_run(
+                    subscription.id,
+                    subscription.next_run_at,
+                    next_run_at,
+                )? {
+                    continue;
+                }
+            }
+
+            let result = {
+                let mut order_service = self.order_service.lock().unwrap();
+                order_service.place_recurring_order(
+                    subscription.user_id,
+                    subscription.plan.product_id,
+                    subscription.variant_id,
+                    subscription.quantity,
+                    |unit_price| subscription.plan.discounted_price(unit_price),
+                )
+            };
+
+            let mut context = HashMap::new();
+            context.insert("subscription_id".to_string(), subscription.id.to_string());
+            context.insert("next_run_at".to_string(), next_run_at.to_string());
+            match result {
+                Ok((order, _)) => {
+                    report.ordered += 1;
+                    {
+                        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+                        subscription_repo.set_last_order(subscription.id, order.id)?;
+                    }
+                    context.insert("order_id".to_string(), order.id.to_string());
+                    context.insert("total_amount".to_string(), order.total_amount.to_string());
+                    self.notify(
+                        &subscription,
+                        "Your subscription order was placed",
+                        "subscription_order_placed",
+                        context,
+                    );
+                }
+                Err(
+                    err @ (OrderError::OutOfStock
+                    | OrderError::ProductUnavailable
+                    | OrderError::InvalidData),
+                ) => {
+                    report.skipped += 1;
+                    let reason = match err {
+                        OrderError::OutOfStock => "out_of_stock",
+                        _ => "unavailable",
+                    };
+                    context.insert("reason".to_string(), reason.to_string());
+                    self.notify(
+                        &subscription,
+                        "Your subscription order was skipped",
+                        "subscription_run_skipped",
+                        context,
+                    );
+                }
+                Err(err) => {
+                    report.failed += 1;
+                    eprintln!(
+                        "Failed to place the order of subscription {}: {:?}",
+                        subscription.id, err
+                    );
+                    let mut subscription_repo = self.subscription_repo.lock().unwrap();
+                    subscription_repo.claim_subscription_run(
+                        subscription.id,
+                        next_run_at,
+                        subscription.next_run_at,
+                    )?;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    // Private Methods
+
+    fn find_own(
+        &self,
+        user_id: i64,
+        subscription_id: i64,
+    ) -> Result<Subscription, SubscriptionError> {
+        let mut subscription_repo = self.subscription_repo.lock().unwrap();
+        let subscription = subscription_repo.find_subscription_by_id(subscription_id)?;
+        if subscription.user_id != user_id {
+            return Err(SubscriptionError::NotFound);
+        }
+        Ok(subscription)
+    }
+
+    /// Emails the subscriber about a run. Failures are only logged, the run
+    /// being done already.
+    fn notify(
+        &self,
+        subscription: &Subscription,
+        subject: &str,
+        template_name: &str,
+        mut context: HashMap<String, String>,
+    ) {
+        let product_name = {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo
+                .find_product_by_id(subscription.plan.product_id)
+                .map(|product| product.name)
+                .unwrap_or_default()
+        };
+        context.insert("product_name".to_string(), product_name);
+
+        let user = {
+            let mut user_repo = self.user_repo.lock().unwrap();
+            match user_repo.find_by_id(subscription.user_id) {
+                Ok(user) => user,
+                Err(err) => {
+                    eprintln!(
+                        "Failed to notify the user of subscription {}: {:?}",
+                        subscription.id, err
+                    );
+                    return;
+                }
+            }
+        };
+
+        let email_service = self.email_service.lock().unwrap();
+        if let Err(err) = email_service.send_email(&user.email, subject, template_name, context) {
+            eprintln!(
+                "Failed to notify the user of subscription {}: {}",
+                subscription.id, err
+            );
+        }
+    }
+
+    fn validate_discount(discount_percent: f64) -> Result<(), SubscriptionError> {
+        if (0.0..100.0).contains(&discount_percent) {
+            Ok(())
+        } else {
+            Err(SubscriptionError::InvalidData)
+        }
+    }
+
+    fn map_product_error(err: ProductError) -> SubscriptionError {
+        match err {
+            ProductError::NotFound => SubscriptionError::NotFound,
+            _ => SubscriptionError::InternalError,
+        }
+    }
+}
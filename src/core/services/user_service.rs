@@ -127,9 +127,8 @@ impl UserService {
             .validate_refresh_token(&refresh_token)
             .map_err(|_| AuthError::InvalidCredentials)?;
 
-        Ok(self
-            .generate_access_token(payload.user_id)
-            .map_err(|_| AuthError::InternalError)?)
+        self.generate_access_token(payload.user_id)
+            .map_err(|_| AuthError::InternalError)
     }
 
     pub fn authorization(&mut self, access_token: String) -> Result<User, AuthError> {
@@ -200,12 +199,9 @@ impl UserService {
 
     fn verify_password(&self, password: String, hashed_password: String) -> bool {
         match PasswordHash::new(&hashed_password) {
-            Ok(parsed_hash) => {
-                match Argon2::default().verify_password(password.as_bytes(), &parsed_hash) {
-                    Ok(_) => true,
-                    Err(_) => false,
-                }
-            }
+            Ok(parsed_hash) => Argon2::default()
+                .verify_password(password.as_bytes(), &parsed_hash)
+                .is_ok(),
             Err(_) => false,
         }
     }
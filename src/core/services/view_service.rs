@@ -0,0 +1,79 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+
+use crate::core::{
+    models::{
+        product::Product,
+        view::{ViewError, ViewSettings, Viewer},
+    },
+    ports::{
+        product_repository::ProductRepository, product_view_repository::ProductViewRepository,
+    },
+};
+
+use super::recommendation_service::load_published_products;
+
+#[derive(Clone)]
+pub struct ViewService {
+    pub(crate) view_repo: Arc<Mutex<dyn ProductViewRepository>>,
+    pub(crate) product_repo: Arc<Mutex<dyn ProductRepository>>,
+    pub(crate) settings: ViewSettings,
+}
+
+pub fn new_view_service(
+    view_repo: Arc<Mutex<dyn ProductViewRepository>>,
+    product_repo: Arc<Mutex<dyn ProductRepository>>,
+    settings: ViewSettings,
+) -> ViewService {
+    ViewService {
+        view_repo,
+        product_repo,
+        settings,
+    }
+}
+
+impl ViewService {
+    /// Counts a view of a product, and adds it to the recently viewed products
+    /// of the viewer when they are known.
+    pub fn record(&mut self, viewer: Option<&Viewer>, product_id: i64) -> Result<(), ViewError> {
+        let mut view_repo = self.view_repo.lock().unwrap();
+        view_repo.record_view(
+            viewer,
+            product_id,
+            Utc::now().naive_utc(),
+            self.settings.recent_limit,
+            self.settings.anonymous_ttl_seconds,
+        )
+    }
+
+    /// Lists the published products the viewer saw last, latest first.
+    pub fn get_recently_viewed(&mut self, viewer: &Viewer) -> Result<Vec<Product>, ViewError> {
+        let product_ids = {
+            let mut view_repo = self.view_repo.lock().unwrap();
+            view_repo.find_recently_viewed(viewer, self.settings.recent_limit)?
+        };
+
+        let mut product_repo = self.product_repo.lock().unwrap();
+        load_published_products(&mut *product_repo, &product_ids)
+            .map_err(|_| ViewError::InternalError)
+    }
+
+    /// Saves the views counted since the last flush to the database and updates
+    /// the trending views of the products. Returns the number of products
+    /// viewed.
+    pub fn flush(&mut self) -> Result<usize, ViewError> {
+        let mut view_repo = self.view_repo.lock().unwrap();
+        let views = view_repo.take_view_counts()?;
+
+        {
+            let mut product_repo = self.product_repo.lock().unwrap();
+            product_repo
+                .add_product_views(&views, Utc::now().date_naive(), self.settings.trending_days)
+                .map_err(|_| ViewError::InternalError)?;
+        }
+
+        view_repo.clear_view_counts()?;
+        Ok(views.len())
+    }
+}